@@ -18,6 +18,7 @@ type fieldType =
 
 type field = {
   fieldName: string,
+  dbFieldName: string,
   fieldType: fieldType,
   isArray: bool,
   isNullable: bool,
@@ -25,6 +26,7 @@ type field = {
   isIndex: bool,
   linkedEntity: option<string>,
   defaultValue: option<string>,
+  description: option<string>,
 }
 
 type derivedFromField = {
@@ -42,11 +44,19 @@ let mkField = (
   ~isPrimaryKey=false,
   ~isIndex=false,
   ~linkedEntity=?,
+  ~dbFieldName=?,
+  ~description=?,
   fieldName,
   fieldType,
 ) =>
   {
     fieldName,
+    //Linked entities are stored as an id column unless a custom column name is given
+    dbFieldName: switch (dbFieldName, linkedEntity) {
+    | (Some(dbFieldName), _) => dbFieldName
+    | (None, Some(_)) => fieldName ++ "_id"
+    | (None, None) => fieldName
+    },
     fieldType,
     isArray,
     isNullable,
@@ -54,6 +64,7 @@ let mkField = (
     isIndex,
     linkedEntity,
     defaultValue: default,
+    description,
   }->Field
 
 let mkDerivedFromField = (fieldName, ~derivedFromEntity, ~derivedFromField) =>
@@ -71,7 +82,13 @@ let getUserDefinedFieldName = fieldOrDerived =>
 
 let isLinkedEntityField = field => field.linkedEntity->Option.isSome
 
-let getDbFieldName = field =>
+let getDbFieldName = field => field.dbFieldName
+
+/*
+Gets the key of the field on the entity record, which differs
+from the db column when a custom name is set with @field(name: "...")
+*/
+let getRecordKey = field =>
   field->isLinkedEntityField ? field.fieldName ++ "_id" : field.fieldName
 
 let getFieldName = fieldOrDerived =>
@@ -80,20 +97,38 @@ let getFieldName = fieldOrDerived =>
   | DerivedFrom({fieldName}) => fieldName
   }
 
+/*
+A tsvector column generated from the given text fields
+with a GIN index and a search function of the same name
+*/
+type fullTextIndex = {
+  name: string,
+  language: string,
+  fields: array<string>,
+}
+
+let mkFullTextIndex = (name, ~language, ~fields) => {name, language, fields}
+
 type table = {
   tableName: string,
   fields: array<fieldOrDerived>,
   compositeIndices: array<array<string>>,
+  fullTextIndices: array<fullTextIndex>,
+  description: option<string>,
 }
 
 let mkTable: 'b. (
   ~compositeIndices: array<array<string>>=?,
+  ~fullTextIndices: array<fullTextIndex>=?,
+  ~description: string=?,
   ~fields: array<fieldOrDerived>,
   string,
-) => 'c = (~compositeIndices=[], ~fields, tableName) => {
+) => 'c = (~compositeIndices=[], ~fullTextIndices=[], ~description=?, ~fields, tableName) => {
   tableName,
   fields,
   compositeIndices,
+  fullTextIndices,
+  description,
 }
 
 let getPrimaryKeyFieldNames = table =>
@@ -151,6 +186,15 @@ let getFieldByName = (table, fieldNameSearch) =>
 
 exception NonExistingTableField(string)
 
+/*
+Maps the key of a field on the entity record to its db column
+*/
+let getDbFieldNameByRecordKey = (table, recordKey) =>
+  switch table->getFields->Js.Array2.find(field => field->getRecordKey == recordKey) {
+  | Some(field) => field->getDbFieldName
+  | None => recordKey
+  }
+
 /*
 Gets all composite indicies (whether they are single indices or not)
 And maps the fields defined to their actual db name (some have _id suffix)
@@ -1,6 +1,6 @@
 use crate::constants::project_paths::{DEFAULT_CONFIG_PATH, DEFAULT_GENERATED_PATH};
 
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use clap_markdown::MarkdownOptions;
 use strum::{Display, EnumIter, EnumString};
 use subenum::subenum;
@@ -14,6 +14,11 @@ pub struct CommandLineArgs {
     pub command: CommandType,
     #[command(flatten)]
     pub project_paths: ProjectPaths,
+    ///The package manager used to install the packages, build the generated code and
+    ///run its db and benchmark scripts. It needs to be installed already. Yarn needs to
+    ///be version 2 or later.
+    #[arg(global = true, long, value_enum, env = "ENVIO_PACKAGE_MANAGER", default_value_t = PackageManager::Pnpm)]
+    pub package_manager: PackageManager,
 }
 
 impl CommandLineArgs {
@@ -46,18 +51,31 @@ pub enum CommandType {
     Init(InitArgs),
 
     /// Development commands for starting, stopping, and restarting the indexer with automatic codegen for any changed files
-    Dev,
+    Dev(DevArgs),
 
     /// Stop the local environment - delete the database and stop all processes (including Docker) for the current directory
     Stop,
 
     ///Generate indexing code from user-defined configuration & schema files
-    Codegen,
+    Codegen(CodegenArgs),
 
     ///Prints a summary of the benchmark data after running the indexer
     ///with envio start --bench flag or setting 'ENVIO_SAVE_BENCHMARK_DATA=true'
     BenchmarkSummary,
 
+    ///Inspect and repair the persisted state used to detect changes to the project
+    #[command(subcommand)]
+    State(StateSubcommands),
+
+    ///List and copy the codegen templates that can be overridden by the templates
+    ///directory of the project
+    #[command(subcommand)]
+    Templates(TemplatesSubcommands),
+
+    ///Inspect the entities of the schema
+    #[command(subcommand)]
+    Schema(SchemaSubcommands),
+
     ///Prepare local environment for envio testing
     // #[clap(hide = true)]
     #[command(subcommand)]
@@ -88,6 +106,67 @@ pub enum JsonSchema {
     Fuel,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum PackageManager {
+    #[default]
+    Pnpm,
+    Npm,
+    Yarn,
+    Bun,
+}
+
+///Controls the package install and build that run after the code is generated
+#[derive(Debug, Args, Clone, Default)]
+pub struct PostCodegenArgs {
+    ///Skip installing packages after codegen, for example when node_modules is
+    ///restored from a cache
+    #[arg(long, action, env = "ENVIO_NO_INSTALL")]
+    pub no_install: bool,
+    ///Install the packages exactly as in the lockfile and fail if it needs an update
+    #[arg(
+        long,
+        action,
+        env = "ENVIO_FROZEN_LOCKFILE",
+        conflicts_with = "no_install"
+    )]
+    pub frozen_lockfile: bool,
+    ///Skip building the generated ReScript code after codegen
+    #[arg(long, action, env = "ENVIO_SKIP_BUILD")]
+    pub skip_build: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct CodegenArgs {
+    ///Check that the generated directory matches the config, schema and abis without
+    ///writing any files or installing packages. Exits with an error listing the files
+    ///that differ.
+    #[arg(long, action)]
+    pub check: bool,
+    ///Also write the db schema the indexer migrations create as plain SQL, to the
+    ///given path relative to the project root. With --check, fails if the file
+    ///doesn't match.
+    #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = "schema.sql")]
+    pub emit_sql: Option<String>,
+    #[command(flatten)]
+    pub post_codegen: PostCodegenArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct DevArgs {
+    ///Watch the config, schema, abi and handler files and rerun codegen and migrations
+    ///and restart the indexer whenever they change
+    #[arg(short = 'w', long, action)]
+    pub watch: bool,
+    ///When only the handlers changed, replay the events stored in raw_events instead of
+    ///fetching them again. Falls back to a full resync if the raw events don't cover
+    ///every processed block.
+    #[arg(long, action)]
+    pub replay_raw_events: bool,
+    #[command(flatten)]
+    pub post_codegen: PostCodegenArgs,
+}
+
 #[derive(Debug, Args)]
 pub struct StartArgs {
     ///Clear your database and restart indexing from scratch
@@ -96,6 +175,11 @@ pub struct StartArgs {
     ///Saves benchmark data to a file during indexing
     #[arg(short = 'b', long, action)]
     pub bench: bool,
+    ///Clear your database except for the raw events and reindex by replaying them.
+    ///Falls back to restarting from scratch if the raw events don't cover every
+    ///processed block.
+    #[arg(long, action, conflicts_with = "restart")]
+    pub replay: bool,
 }
 
 #[derive(Debug, Subcommand)]
@@ -116,6 +200,62 @@ pub enum LocalDockerSubcommands {
     Down,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum StateSubcommands {
+    ///Print the persisted state in the generated file and the db next to the current
+    ///state of the project, with the fields that changed
+    Show,
+    ///Overwrite the persisted state file with the current state, so that codegen
+    ///treats the generated code as up to date
+    ResetFile,
+    ///Overwrite the persisted state in the db with the current state, so that the
+    ///db is treated as migrated without dropping any data
+    SetDb,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TemplatesSubcommands {
+    ///Print the paths of the codegen templates, which is where an override goes
+    ///within the templates directory
+    List,
+    ///Copy the given templates into the templates directory as a starting point for
+    ///overriding them. A directory copies all the templates in it. Existing files are
+    ///never overwritten.
+    Dump {
+        ///Paths as printed by envio templates list, for example
+        ///dynamic/codegen/src/Handlers.res.hbs
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SchemaSubcommands {
+    ///Print an entity relationship diagram of the schema, with the fields, indices and
+    ///the relationships of linked and derived fields
+    Diagram(DiagramArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct DiagramArgs {
+    ///Format of the diagram
+    #[arg(short, long, value_enum, default_value_t = DiagramFormat::Mermaid)]
+    pub format: DiagramFormat,
+    ///Write the diagram to the given file instead of printing it
+    #[arg(long)]
+    pub output: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum DiagramFormat {
+    ///Mermaid erDiagram, which GitHub renders in markdown
+    #[default]
+    Mermaid,
+    ///Graphviz DOT
+    Dot,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum DbMigrateSubcommands {
     ///Migrate latest schema to database
@@ -168,6 +168,11 @@ pub mod evm {
                 save_full_history: None,
                 field_selection: None,
                 raw_events: None,
+                hasura: None,
+                graphql_client: None,
+                validators: None,
+                hooks: None,
+                local_services: None,
             })
         }
 
@@ -299,6 +304,11 @@ pub mod fuel {
                 schema: None,
                 contracts: None,
                 raw_events: None,
+                hasura: None,
+                graphql_client: None,
+                validators: None,
+                hooks: None,
+                local_services: None,
                 networks: network_configs,
             }
         }
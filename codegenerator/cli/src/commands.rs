@@ -1,24 +1,38 @@
 use anyhow::Context;
 use std::path::Path;
 
-async fn execute_command(
-    cmd: &str,
-    args: Vec<&str>,
-    current_dir: &Path,
-) -> anyhow::Result<std::process::ExitStatus> {
-    Ok(tokio::process::Command::new(cmd)
-        .args(&args)
+fn make_command(cmd: &str, args: &[&str], current_dir: &Path) -> tokio::process::Command {
+    let mut command = tokio::process::Command::new(cmd);
+    command
+        .args(args)
         .current_dir(current_dir)
         .stdin(std::process::Stdio::null()) //passes null on any stdinprompt
-        .kill_on_drop(true) //needed so that dropped threads calling this will also drop
-        //the child process
+        //needed so that dropped threads calling this will also drop the child process
+        .kill_on_drop(true);
+    command
+}
+
+fn spawn_command(
+    cmd: &str,
+    args: &[&str],
+    current_dir: &Path,
+) -> anyhow::Result<tokio::process::Child> {
+    make_command(cmd, args, current_dir)
         .spawn()
         .context(format!(
             "Failed to spawn command {} {} at {} as child process",
             cmd,
             args.join(" "),
             current_dir.to_str().unwrap_or("bad_path")
-        ))?
+        ))
+}
+
+async fn execute_command(
+    cmd: &str,
+    args: Vec<&str>,
+    current_dir: &Path,
+) -> anyhow::Result<std::process::ExitStatus> {
+    Ok(spawn_command(cmd, &args, current_dir)?
         .wait()
         .await
         .context(format!(
@@ -29,26 +43,213 @@ async fn execute_command(
         ))?)
 }
 
+pub mod package_manager {
+    use crate::cli_args::clap_definitions::PackageManager;
+
+    impl PackageManager {
+        pub fn get_command(&self) -> &'static str {
+            match self {
+                Self::Pnpm => "pnpm",
+                Self::Npm => "npm",
+                Self::Yarn => "yarn",
+                Self::Bun => "bun",
+            }
+        }
+
+        pub fn get_install_args(&self, frozen_lockfile: bool) -> Vec<&'static str> {
+            match (self, frozen_lockfile) {
+                (Self::Pnpm, false) => vec!["install", "--no-frozen-lockfile", "--prefer-offline"],
+                (Self::Pnpm, true) => vec!["install", "--frozen-lockfile", "--prefer-offline"],
+                (Self::Npm, false) => vec!["install", "--prefer-offline"],
+                (Self::Npm, true) => vec!["ci", "--prefer-offline"],
+                (Self::Yarn, false) => vec!["install"],
+                (Self::Yarn, true) => vec!["install", "--immutable"],
+                (Self::Bun, false) => vec!["install"],
+                (Self::Bun, true) => vec!["install", "--frozen-lockfile"],
+            }
+        }
+
+        ///Args to run a binary of the installed packages, like rescript
+        pub fn get_exec_args<'a>(&self, bin_args: &[&'a str]) -> Vec<&'a str> {
+            let prefix: &[&str] = match self {
+                Self::Pnpm | Self::Yarn => &[],
+                Self::Npm => &["exec", "--"],
+                Self::Bun => &["run"],
+            };
+            prefix.iter().chain(bin_args).copied().collect()
+        }
+
+        ///Args to run a script of the package.json in the current dir, like db-up
+        pub fn get_run_args<'a>(&self, script: &'a str) -> Vec<&'a str> {
+            match self {
+                Self::Pnpm | Self::Yarn => vec![script],
+                Self::Npm | Self::Bun => vec!["run", script],
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use crate::cli_args::clap_definitions::PackageManager;
+
+        #[test]
+        fn install_args_of_each_package_manager() {
+            let cases = [
+                (
+                    PackageManager::Pnpm,
+                    false,
+                    "install --no-frozen-lockfile --prefer-offline",
+                ),
+                (
+                    PackageManager::Pnpm,
+                    true,
+                    "install --frozen-lockfile --prefer-offline",
+                ),
+                (PackageManager::Npm, false, "install --prefer-offline"),
+                (PackageManager::Npm, true, "ci --prefer-offline"),
+                (PackageManager::Yarn, false, "install"),
+                (PackageManager::Yarn, true, "install --immutable"),
+                (PackageManager::Bun, false, "install"),
+                (PackageManager::Bun, true, "install --frozen-lockfile"),
+            ];
+            for (package_manager, frozen_lockfile, expected) in cases {
+                assert_eq!(
+                    package_manager.get_install_args(frozen_lockfile).join(" "),
+                    expected,
+                    "{package_manager} with frozen_lockfile {frozen_lockfile}"
+                );
+            }
+        }
+
+        #[test]
+        fn exec_and_run_args_of_each_package_manager() {
+            let cases = [
+                (PackageManager::Pnpm, "pnpm rescript clean", "pnpm db-up"),
+                (
+                    PackageManager::Npm,
+                    "npm exec -- rescript clean",
+                    "npm run db-up",
+                ),
+                (PackageManager::Yarn, "yarn rescript clean", "yarn db-up"),
+                (
+                    PackageManager::Bun,
+                    "bun run rescript clean",
+                    "bun run db-up",
+                ),
+            ];
+            for (package_manager, expected_exec, expected_run) in cases {
+                let command = package_manager.get_command();
+                let exec_args = package_manager.get_exec_args(&["rescript", "clean"]);
+                let run_args = package_manager.get_run_args("db-up");
+                assert_eq!(format!("{command} {}", exec_args.join(" ")), expected_exec);
+                assert_eq!(format!("{command} {}", run_args.join(" ")), expected_run);
+            }
+        }
+    }
+}
+
 pub mod rescript {
     use super::execute_command;
+    use crate::cli_args::clap_definitions::PackageManager;
     use anyhow::Result;
     use std::path::PathBuf;
 
-    pub async fn clean(path: &PathBuf) -> Result<std::process::ExitStatus> {
-        let args = vec!["rescript", "clean"];
-        execute_command("pnpm", args, path).await
+    pub async fn clean(
+        path: &PathBuf,
+        package_manager: PackageManager,
+    ) -> Result<std::process::ExitStatus> {
+        let args = package_manager.get_exec_args(&["rescript", "clean"]);
+        execute_command(package_manager.get_command(), args, path).await
     }
 
-    pub async fn build(path: &PathBuf) -> Result<std::process::ExitStatus> {
-        let args = vec!["rescript"];
-        execute_command("pnpm", args, path).await
+    pub async fn build(
+        path: &PathBuf,
+        package_manager: PackageManager,
+    ) -> Result<std::process::ExitStatus> {
+        let args = package_manager.get_exec_args(&["rescript"]);
+        execute_command(package_manager.get_command(), args, path).await
+    }
+}
+
+pub mod hooks {
+    use super::execute_command;
+    use crate::project_paths::ParsedProjectPaths;
+    use anyhow::{anyhow, Context, Result};
+
+    ///The shell running the hook commands and its flag for running a command string
+    #[cfg(windows)]
+    const SHELL: (&str, &str) = ("cmd", "/C");
+    #[cfg(not(windows))]
+    const SHELL: (&str, &str) = ("sh", "-c");
+
+    ///Runs the commands of a config hook in order with the project root as the
+    ///working dir, failing on the first command that exits unsuccessfully
+    pub async fn run_hook_commands(
+        hook_name: &str,
+        commands: &[String],
+        project_paths: &ParsedProjectPaths,
+    ) -> Result<()> {
+        for command in commands {
+            println!("Running {hook_name} hook: {command}");
+            let (shell, command_flag) = SHELL;
+            let exit = execute_command(
+                shell,
+                vec![command_flag, command],
+                &project_paths.project_root,
+            )
+            .await
+            .context(format!("Failed running {hook_name} hook: {command}"))?;
+            if !exit.success() {
+                return Err(anyhow!(
+                    "The {hook_name} hook '{command}' failed with {exit}, aborting codegen"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::run_hook_commands;
+        use crate::project_paths::ParsedProjectPaths;
+        use tempdir::TempDir;
+
+        //The commands are written for sh
+        #[cfg(unix)]
+        #[tokio::test]
+        async fn runs_hooks_in_project_root_until_one_fails() {
+            let dir = TempDir::new("hooks").unwrap();
+            let project_root = dir.path().to_str().unwrap();
+            let project_paths =
+                ParsedProjectPaths::new(project_root, "generated", "config.yaml").unwrap();
+            let commands = vec![
+                "echo abi > abi.json".to_string(),
+                "exit 3".to_string(),
+                "touch not_run".to_string(),
+            ];
+
+            let err = run_hook_commands("pre_codegen", &commands, &project_paths)
+                .await
+                .unwrap_err();
+
+            assert!(err.to_string().contains("'exit 3' failed"));
+            assert!(dir.path().join("abi.json").exists());
+            assert!(!dir.path().join("not_run").exists());
+        }
     }
 }
 
 pub mod codegen {
-    use super::{execute_command, rescript};
+    use super::{execute_command, hooks, make_command, rescript};
     use crate::{
-        config_parsing::system_config::SystemConfig, hbs_templating, template_dirs::TemplateDirs,
+        cli_args::clap_definitions::{PackageManager, PostCodegenArgs},
+        config_parsing::system_config::SystemConfig,
+        hbs_templating::{
+            self, graphql_client_templates::GRAPHQL_CLIENT_DIR,
+            validators_templates::get_disabled_output_paths,
+        },
+        persisted_state::{CodegenScope, PersistedState},
+        template_dirs::{TemplateDirs, TemplateOverrides},
     };
     use anyhow::{self, Context, Result};
     use std::path::PathBuf;
@@ -76,54 +277,82 @@ pub mod codegen {
         Ok(())
     }
 
-    pub async fn check_and_install_pnpm(current_dir: &PathBuf) -> Result<()> {
-        // Check if pnpm is already installed
-        let check_pnpm = execute_command("pnpm", vec!["--version"], current_dir).await;
+    ///Fails when the package manager can't be run. It never gets installed, since
+    ///global installs are often not allowed, for example in sandboxes and CI.
+    async fn check_package_manager_installed(
+        package_manager: PackageManager,
+        current_dir: &PathBuf,
+    ) -> Result<()> {
+        let command = package_manager.get_command();
+        let is_installed = make_command(command, &["--version"], current_dir)
+            .output()
+            .await
+            .is_ok_and(|output| output.status.success());
 
-        // If pnpm is not installed, run the installation command
-        match check_pnpm {
-            Ok(status) if status.success() => {
-                println!("Package pnpm is already installed. Continuing...");
-            }
-            _ => {
-                println!("Package pnpm is not installed. Installing now...");
-                let args = vec!["install", "--global", "pnpm"];
-                execute_command("npm", args, current_dir).await?;
-            }
+        if is_installed {
+            return Ok(());
         }
-        Ok(())
+
+        Err(anyhow::anyhow!(
+            "EE117: The package manager {command} is not installed. Install it, or choose an \
+             installed one with --package-manager or the ENVIO_PACKAGE_MANAGER env var."
+        ))
     }
 
-    pub async fn pnpm_install(
+    pub async fn install_packages(
         project_paths: &ParsedProjectPaths,
+        post_codegen: &PostCodegenArgs,
+        package_manager: PackageManager,
     ) -> Result<std::process::ExitStatus> {
-        println!("Checking for pnpm package...");
         let current_dir = &project_paths.project_root;
-        check_and_install_pnpm(current_dir).await?;
+        check_package_manager_installed(package_manager, current_dir).await?;
 
-        let args = vec!["install", "--no-frozen-lockfile", "--prefer-offline"];
-        execute_command("pnpm", args, current_dir).await
+        let args = package_manager.get_install_args(post_codegen.frozen_lockfile);
+        execute_command(package_manager.get_command(), args, current_dir).await
     }
 
     async fn run_post_codegen_command_sequence(
         project_paths: &ParsedProjectPaths,
+        codegen_scope: &CodegenScope,
+        post_codegen: &PostCodegenArgs,
+        package_manager: PackageManager,
     ) -> anyhow::Result<std::process::ExitStatus> {
-        println!("Installing packages... ");
-        let exit1 = pnpm_install(project_paths).await?;
-        if !exit1.success() {
-            return Ok(exit1);
+        //Targeted codegen never touches the generated package.json, so installed
+        //packages are still valid and the incremental rescript build picks up the
+        //regenerated files without cleaning
+        let is_targeted = matches!(codegen_scope, CodegenScope::Targeted(_));
+        let has_installed_packages = project_paths.project_root.join("node_modules").exists();
+        let has_package_changes = !(is_targeted && has_installed_packages);
+
+        if !has_package_changes {
+            println!("Skipping package install and clean build, no package changes");
+        } else if post_codegen.no_install {
+            println!("Skipping package install");
+        } else {
+            println!("Installing packages... ");
+            let exit1 = install_packages(project_paths, post_codegen, package_manager).await?;
+            if !exit1.success() {
+                return Ok(exit1);
+            }
         }
 
-        println!("Clean build directory");
-        let exit2 = rescript::clean(&project_paths.generated)
-            .await
-            .context("Failed running rescript clean")?;
-        if !exit2.success() {
-            return Ok(exit2);
+        if post_codegen.skip_build {
+            println!("Skipping build");
+            return Ok(std::process::ExitStatus::default());
+        }
+
+        if has_package_changes {
+            println!("Clean build directory");
+            let exit2 = rescript::clean(&project_paths.generated, package_manager)
+                .await
+                .context("Failed running rescript clean")?;
+            if !exit2.success() {
+                return Ok(exit2);
+            }
         }
 
         println!("Building code");
-        let exit3 = rescript::build(&project_paths.generated)
+        let exit3 = rescript::build(&project_paths.generated, package_manager)
             .await
             .context("Failed running rescript build")?;
         if !exit3.success() {
@@ -136,6 +365,9 @@ pub mod codegen {
     pub async fn run_codegen(
         config: &SystemConfig,
         project_paths: &ParsedProjectPaths,
+        codegen_scope: &CodegenScope,
+        post_codegen: &PostCodegenArgs,
+        package_manager: PackageManager,
     ) -> anyhow::Result<()> {
         let template_dirs = TemplateDirs::new();
         fs::create_dir_all(&project_paths.generated).await?;
@@ -144,38 +376,102 @@ pub mod codegen {
             hbs_templating::codegen_templates::ProjectTemplate::from_config(config, project_paths)
                 .context("Failed creating project template")?;
 
-        template_dirs
-            .get_codegen_static_dir()?
-            .extract(&project_paths.generated)
-            .context("Failed extracting static codegen files")?;
+        let overrides = TemplateOverrides::new(&project_paths.project_root);
+        for path in overrides.get_unmatched_paths()? {
+            println!(
+                "WARNING: Template override {} doesn't match any codegen template and is \
+                 ignored. Run envio templates list to see the templates that can be overridden.",
+                overrides.get_dir().join(path).display()
+            );
+        }
+
+        //Static files only change between envio versions or overrides, which always
+        //needs a full codegen
+        if let CodegenScope::Full = codegen_scope {
+            template_dirs
+                .get_codegen_static_dir()?
+                .extract_with_overrides(&project_paths.generated, &overrides)
+                .context("Failed extracting static codegen files")?;
+
+            //Otherwise the client stays around after disabling it in the config
+            let graphql_client_dir = project_paths.generated.join(GRAPHQL_CLIENT_DIR);
+            if !config.enable_graphql_client && graphql_client_dir.exists() {
+                fs::remove_dir_all(&graphql_client_dir)
+                    .await
+                    .context("Failed removing the disabled graphql client")?;
+            }
+
+            //Same for the validators of disabled formats
+            for path in get_disabled_output_paths(&config.validator_formats) {
+                let path = project_paths.generated.join(path);
+                if path.is_dir() {
+                    fs::remove_dir_all(&path)
+                        .await
+                        .context("Failed removing the disabled validators")?;
+                } else if path.exists() {
+                    fs::remove_file(&path)
+                        .await
+                        .context("Failed removing the disabled validators")?;
+                }
+            }
+        }
 
         template
-            .generate_templates(project_paths)
+            .generate_templates(project_paths, codegen_scope)
             .context("Failed generating dynamic codegen files")?;
 
-        run_post_codegen_command_sequence(project_paths)
-            .await
-            .context("Failed running post codegen command sequence")?;
+        hooks::run_hook_commands("post_codegen", &config.hooks.post_codegen, project_paths).await?;
+        //What the hooks change can't be rendered again, so the generated files are hashed
+        //as the hooks left them to detect when they change afterwards
+        if !config.hooks.post_codegen.is_empty() {
+            let mut persisted_state = PersistedState::get_current_state(config)
+                .context("Failed getting current indexer state")?;
+            persisted_state.set_generated_files(
+                project_paths,
+                template
+                    .get_output_paths(project_paths)
+                    .context("Failed getting codegen output paths")?,
+            )?;
+            persisted_state.write_to_file(project_paths)?;
+        }
+
+        run_post_codegen_command_sequence(
+            project_paths,
+            codegen_scope,
+            post_codegen,
+            package_manager,
+        )
+        .await
+        .context("Failed running post codegen command sequence")?;
 
         Ok(())
     }
 }
 
 pub mod start {
-    use super::execute_command;
+    use super::make_command;
     use crate::project_paths::ParsedProjectPaths;
-    use anyhow::anyhow;
+    use anyhow::{anyhow, Context};
+    use std::time::Duration;
+    use tokio::process::Child;
 
-    pub async fn start_indexer(
+    ///Time given to the indexer to shut down before it gets killed
+    const INDEXER_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+    ///Spawns the indexer as a child process without waiting for it to exit,
+    ///opening the hasura console first if a url is given. The indexer runs in its
+    ///own process group, so that it can be stopped together with the node process
+    ///that npm runs. Ctrl+c doesn't reach it, it's forwarded by stop_indexer instead.
+    pub fn spawn_indexer(
         project_paths: &ParsedProjectPaths,
         should_use_raw_events_worker: bool,
-        should_open_hasura: bool,
-    ) -> anyhow::Result<()> {
-        if should_open_hasura {
-            println!("Opening Hasura console at http://localhost:8080 ...");
-            if let Err(_) = open::that_detached("http://localhost:8080") {
+        hasura_console_url: Option<&str>,
+    ) -> anyhow::Result<Child> {
+        if let Some(hasura_console_url) = hasura_console_url {
+            println!("Opening Hasura console at {hasura_console_url} ...");
+            if let Err(_) = open::that_detached(hasura_console_url) {
                 println!(
-                    "Unable to open http://localhost:8080 in your browser automatically for you. \
+                    "Unable to open {hasura_console_url} in your browser automatically for you. \
                      You can open that link yourself to view hasura"
                 );
             }
@@ -191,7 +487,34 @@ pub mod start {
             args.push("--sync-from-raw-events");
         }
 
-        let exit = execute_command(cmd, args, current_dir).await?;
+        let mut command = make_command(cmd, &args, current_dir);
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(command.as_std_mut(), 0);
+
+        command.spawn().context(format!(
+            "Failed to spawn command {cmd} {} at {} as child process",
+            args.join(" "),
+            current_dir.display()
+        ))
+    }
+
+    pub async fn start_indexer(
+        project_paths: &ParsedProjectPaths,
+        should_use_raw_events_worker: bool,
+        hasura_console_url: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let mut indexer = spawn_indexer(
+            project_paths,
+            should_use_raw_events_worker,
+            hasura_console_url,
+        )?;
+
+        let exit = tokio::select! {
+            exit = indexer.wait() => exit.context("Failed to exit the indexer child process")?,
+            _ = tokio::signal::ctrl_c() => {
+                return stop_indexer(&mut indexer).await;
+            }
+        };
 
         if !exit.success() {
             return Err(anyhow!(
@@ -205,10 +528,131 @@ pub mod start {
         );
         Ok(())
     }
+
+    ///Sends the signal to the process group of the indexer, which reaches npm and the
+    ///node process it runs
+    #[cfg(unix)]
+    fn signal_indexer(indexer: &Child, signal: libc::c_int) {
+        if let Some(pid) = indexer.id() {
+            // SAFETY: kill only sends a signal and doesn't touch any memory of this process.
+            // The pid is of a child that hasn't been waited on yet, so it and its process
+            // group id can't have been reused by another process.
+            unsafe {
+                libc::kill(-(pid as libc::pid_t), signal);
+            }
+        }
+    }
+
+    ///Asks the indexer to shut down the same way ctrl+c would and kills it
+    ///if it hasn't exited within the shutdown timeout
+    pub async fn stop_indexer(indexer: &mut Child) -> anyhow::Result<()> {
+        #[cfg(unix)]
+        signal_indexer(indexer, libc::SIGINT);
+
+        //Without signals there is no graceful way to ask the process to shut down
+        #[cfg(not(unix))]
+        indexer
+            .start_kill()
+            .context("Failed killing the indexer child process")?;
+
+        match tokio::time::timeout(INDEXER_SHUTDOWN_TIMEOUT, indexer.wait()).await {
+            Ok(exit) => {
+                exit.context("Failed waiting for the indexer to shut down")?;
+            }
+            Err(_) => {
+                println!("Indexer did not shut down in time, killing the process");
+                #[cfg(unix)]
+                signal_indexer(indexer, libc::SIGKILL);
+                indexer
+                    .kill()
+                    .await
+                    .context("Failed killing the indexer child process")?;
+            }
+        }
+
+        Ok(())
+    }
 }
 pub mod docker {
     use super::execute_command;
     use crate::project_paths::ParsedProjectPaths;
+    use anyhow::{anyhow, Context};
+
+    ///Whether any container of the compose project in the generated dir is running.
+    ///The project name comes from the compose file, so other indexers aren't included.
+    pub async fn is_compose_project_running(
+        project_paths: &ParsedProjectPaths,
+    ) -> anyhow::Result<bool> {
+        is_running(project_paths, None).await
+    }
+
+    ///Whether any container of the given compose project, or the one named in the
+    ///compose file if there is none, is running
+    async fn is_running(
+        project_paths: &ParsedProjectPaths,
+        project_name: Option<&str>,
+    ) -> anyhow::Result<bool> {
+        let mut args = vec!["compose"];
+        if let Some(project_name) = project_name {
+            args.extend(["--project-name", project_name]);
+        }
+        args.extend(["ps", "--quiet", "--status", "running"]);
+
+        let output = tokio::process::Command::new("docker")
+            .args(args)
+            .current_dir(&project_paths.generated)
+            .stdin(std::process::Stdio::null())
+            .output()
+            .await
+            .context("Failed running docker compose ps. Make sure docker is installed")?;
+
+        if !output.status.success() {
+            return Err(anyhow!(
+                "docker compose ps failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(!output.stdout.iter().all(u8::is_ascii_whitespace))
+    }
+
+    ///Name of the compose project that envio versions from before the compose file
+    ///named it started. Docker compose named it after the generated dir, keeping
+    ///only the characters allowed in project names.
+    fn get_legacy_project_name(project_paths: &ParsedProjectPaths) -> Option<String> {
+        let dir_name = project_paths.generated.file_name()?.to_string_lossy();
+        let name: String = dir_name
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+            .collect();
+        let name = name.trim_start_matches(['-', '_']);
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    ///Gets the name of the compose project started by a previous envio version when
+    ///its containers are still running. They keep the ports and aren't stopped by
+    ///envio stop, since it only stops the project named in the compose file.
+    pub async fn get_running_legacy_project(
+        project_paths: &ParsedProjectPaths,
+        compose_project_name: &str,
+    ) -> anyhow::Result<Option<String>> {
+        match get_legacy_project_name(project_paths) {
+            Some(legacy_project_name)
+                if legacy_project_name != compose_project_name
+                    && is_running(project_paths, Some(&legacy_project_name)).await? =>
+            {
+                Ok(Some(legacy_project_name))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    ///The command that stops the containers of a compose project started by a previous
+    ///envio version and deletes its volumes, like envio stop did
+    pub fn get_legacy_project_down_command(legacy_project_name: &str) -> String {
+        format!("docker compose --project-name {legacy_project_name} down -v")
+    }
 
     pub async fn docker_compose_up_d(
         project_paths: &ParsedProjectPaths,
@@ -228,6 +672,28 @@ pub mod docker {
 
         execute_command(cmd, args, current_dir).await
     }
+
+    #[cfg(test)]
+    mod test {
+        use super::get_legacy_project_name;
+        use crate::project_paths::ParsedProjectPaths;
+
+        #[test]
+        fn legacy_project_is_named_after_the_generated_dir() {
+            let get_name = |generated: &str| {
+                let project_paths =
+                    ParsedProjectPaths::new("indexer", generated, "config.yaml").unwrap();
+                get_legacy_project_name(&project_paths)
+            };
+
+            assert_eq!(get_name("generated"), Some("generated".to_string()));
+            assert_eq!(
+                get_name("out/My.Generated"),
+                Some("mygenerated".to_string())
+            );
+            assert_eq!(get_name("__"), None);
+        }
+    }
 }
 
 pub mod db_migrate {
@@ -236,53 +702,63 @@ pub mod db_migrate {
     use std::process::ExitStatus;
 
     use super::execute_command;
-    use crate::{persisted_state::PersistedState, project_paths::ParsedProjectPaths};
+    use crate::{
+        cli_args::clap_definitions::PackageManager, dev_services::PostgresSettings,
+        persisted_state::PersistedState, project_paths::ParsedProjectPaths,
+    };
 
     pub async fn run_up_migrations(
         project_paths: &ParsedProjectPaths,
         persisted_state: &PersistedState,
+        postgres: &PostgresSettings,
+        package_manager: PackageManager,
     ) -> anyhow::Result<()> {
-        let args = vec!["db-up"];
+        let args = package_manager.get_run_args("db-up");
         let current_dir = &project_paths.generated;
-        let exit = execute_command("pnpm", args, current_dir).await?;
+        let exit = execute_command(package_manager.get_command(), args, current_dir).await?;
 
         if !exit.success() {
             return Err(anyhow!("Failed to run db migrations"));
         }
 
         persisted_state
-            .upsert_to_db()
+            .upsert_to_db(postgres)
             .await
             .context("Failed to upsert persisted state table")?;
         Ok(())
     }
 
-    pub async fn run_drop_schema(project_paths: &ParsedProjectPaths) -> anyhow::Result<ExitStatus> {
-        let args = vec!["db-down"];
+    pub async fn run_drop_schema(
+        project_paths: &ParsedProjectPaths,
+        package_manager: PackageManager,
+    ) -> anyhow::Result<ExitStatus> {
+        let args = package_manager.get_run_args("db-down");
         let current_dir = &project_paths.generated;
-        execute_command("pnpm", args, current_dir).await
+        execute_command(package_manager.get_command(), args, current_dir).await
     }
 
     pub async fn run_db_setup(
         project_paths: &ParsedProjectPaths,
         should_drop_raw_events: bool,
         persisted_state: &PersistedState,
+        postgres: &PostgresSettings,
+        package_manager: PackageManager,
     ) -> anyhow::Result<()> {
-        let arg = if should_drop_raw_events {
+        let script = if should_drop_raw_events {
             "db-setup"
         } else {
             "db-setup-keep-raw-events"
         };
-        let args = vec![arg];
+        let args = package_manager.get_run_args(script);
         let current_dir = &project_paths.generated;
-        let exit = execute_command("pnpm", args, current_dir).await?;
+        let exit = execute_command(package_manager.get_command(), args, current_dir).await?;
 
         if !exit.success() {
             return Err(anyhow!("Failed to run db migrations"));
         }
 
         persisted_state
-            .upsert_to_db()
+            .upsert_to_db(postgres)
             .await
             .context("Failed to upsert persisted state table")?;
         Ok(())
@@ -291,13 +767,16 @@ pub mod db_migrate {
 
 pub mod benchmark {
     use super::execute_command;
-    use crate::project_paths::ParsedProjectPaths;
+    use crate::{cli_args::clap_definitions::PackageManager, project_paths::ParsedProjectPaths};
     use anyhow::{anyhow, Result};
 
-    pub async fn print_summary(project_paths: &ParsedProjectPaths) -> Result<()> {
-        let args = vec!["print-benchmark-summary"];
+    pub async fn print_summary(
+        project_paths: &ParsedProjectPaths,
+        package_manager: PackageManager,
+    ) -> Result<()> {
+        let args = package_manager.get_run_args("print-benchmark-summary");
         let current_dir = &project_paths.generated;
-        let exit = execute_command("pnpm", args, current_dir).await?;
+        let exit = execute_command(package_manager.get_command(), args, current_dir).await?;
 
         if !exit.success() {
             return Err(anyhow!("Failed printing benchmark summary"));
@@ -6,7 +6,10 @@ use super::{
     },
 };
 use crate::{
-    constants::project_paths::DEFAULT_SCHEMA_PATH,
+    constants::{
+        project_paths::DEFAULT_SCHEMA_PATH,
+        reserved_keywords::ENVIO_INTERNAL_RESERVED_POSTGRES_TABLES,
+    },
     hbs_templating::codegen_templates::DerivedFieldTemplate,
     project_paths::{path_utils, ParsedProjectPaths},
     rescript_types::RescriptTypeIdent,
@@ -15,8 +18,8 @@ use crate::{
 use anyhow::{anyhow, Context};
 use ethers::abi::ethabi::ParamType as EthAbiParamType;
 use graphql_parser::schema::{
-    Definition, Directive, Document, EnumType, Field as ObjField, ObjectType, Type as ObjType,
-    TypeDefinition, Value,
+    Definition, Directive, Document, EnumType, Field as ObjField, InterfaceType, ObjectType,
+    Type as ObjType, TypeDefinition, UnionType, Value,
 };
 use itertools::Itertools;
 use serde::{Serialize, Serializer};
@@ -24,18 +27,24 @@ use std::{
     collections::{HashMap, HashSet},
     fmt::{self},
     path::PathBuf,
+    str::FromStr,
 };
+use strum::IntoEnumIterator;
 use subenum::subenum;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Schema {
     pub entities: HashMap<String, Entity>,
     pub enums: HashMap<String, GraphQLEnum>,
+    pub interfaces: HashMap<String, Interface>,
+    pub unions: HashMap<String, Union>,
 }
 
 enum TypeDef<'a> {
     Entity(&'a Entity),
     Enum,
+    Interface,
+    Union,
 }
 
 impl Schema {
@@ -43,10 +52,17 @@ impl Schema {
         Schema {
             entities: HashMap::new(),
             enums: HashMap::new(),
+            interfaces: HashMap::new(),
+            unions: HashMap::new(),
         }
     }
 
-    pub fn new(entities: Vec<Entity>, enums: Vec<GraphQLEnum>) -> anyhow::Result<Self> {
+    pub fn new(
+        entities: Vec<Entity>,
+        enums: Vec<GraphQLEnum>,
+        interfaces: Vec<Interface>,
+        unions: Vec<Union>,
+    ) -> anyhow::Result<Self> {
         let entities = unique_hashmap::from_vec_no_duplicates(
             entities.into_iter().map(|e| (e.name.clone(), e)).collect(),
         )
@@ -55,11 +71,28 @@ impl Schema {
             enums.into_iter().map(|e| (e.name.clone(), e)).collect(),
         )
         .context("Found enums with duplicate names")?;
+        let interfaces = unique_hashmap::from_vec_no_duplicates(
+            interfaces
+                .into_iter()
+                .map(|i| (i.name.clone(), i))
+                .collect(),
+        )
+        .context("Found interfaces with duplicate names")?;
+        let unions = unique_hashmap::from_vec_no_duplicates(
+            unions.into_iter().map(|u| (u.name.clone(), u)).collect(),
+        )
+        .context("Found unions with duplicate names")?;
 
-        Self { entities, enums }.validate()
+        Self {
+            entities,
+            enums,
+            interfaces,
+            unions,
+        }
+        .validate()
     }
 
-    fn from_document(document: Document<String>) -> anyhow::Result<Self> {
+    pub(crate) fn from_document(document: Document<String>) -> anyhow::Result<Self> {
         let entities = document
             .definitions
             .iter()
@@ -90,7 +123,45 @@ impl Schema {
             .collect::<anyhow::Result<Vec<GraphQLEnum>>>()
             .context("Failed constructing enums in schema from document")?;
 
-        Self::new(entities, enums)
+        let interfaces = document
+            .definitions
+            .iter()
+            .filter_map(|d| match d {
+                Definition::TypeDefinition(type_def) => Some(type_def),
+                _ => None,
+            })
+            .filter_map(|type_def| match type_def {
+                TypeDefinition::Interface(obj) => Some(obj),
+                _ => None,
+            })
+            .map(|obj| Interface::from_interface(obj))
+            .collect::<anyhow::Result<Vec<Interface>>>()
+            .context("Failed constructing interfaces in schema from document")?;
+
+        let unions = document
+            .definitions
+            .iter()
+            .filter_map(|d| match d {
+                Definition::TypeDefinition(type_def) => Some(type_def),
+                _ => None,
+            })
+            .filter_map(|type_def| match type_def {
+                TypeDefinition::Union(obj) => Some(obj),
+                _ => None,
+            })
+            .map(|obj| Union::from_union(obj))
+            .collect::<Vec<Union>>();
+
+        let (entities, entity_type_enums) =
+            add_polymorphic_entity_types(entities, &interfaces, &unions)
+                .context("Failed adding the entity types of interface and union references")?;
+
+        Self::new(
+            entities,
+            [enums, entity_type_enums].concat(),
+            interfaces,
+            unions,
+        )
     }
 
     pub fn parse_from_file(
@@ -123,9 +194,14 @@ impl Schema {
     fn validate(self) -> anyhow::Result<Self> {
         self.check_enum_type_defs()?
             .check_schema_for_reserved_words()?
-            .check_duplicate_naming_between_enums_and_entities()?
+            .check_duplicate_naming_between_type_defs()?
+            .check_interface_implementations()?
+            .check_union_members()?
+            .check_aggregations()?
+            .check_full_text_indexes()?
             .check_related_type_defs_exist()?
-            .validate_entity_field_types()
+            .validate_entity_field_types()?
+            .check_db_names()
     }
 
     fn get_all_enum_type_names(&self) -> Vec<String> {
@@ -144,6 +220,19 @@ impl Schema {
             .map(|v| v.name.clone())
             .collect()
     }
+    fn get_all_interface_type_names(&self) -> Vec<String> {
+        self.interfaces.keys().cloned().collect()
+    }
+    fn get_all_interface_field_names(&self) -> Vec<String> {
+        self.interfaces
+            .values()
+            .flat_map(|v| v.fields.values())
+            .map(|v| v.name.clone())
+            .collect()
+    }
+    fn get_all_union_type_names(&self) -> Vec<String> {
+        self.unions.keys().cloned().collect()
+    }
 
     fn check_enum_type_defs(self) -> anyhow::Result<Self> {
         match check_enums_for_internal_reserved_words(self.get_all_enum_type_names()) {
@@ -161,6 +250,9 @@ impl Schema {
             self.get_all_enum_values(),
             self.get_all_entity_type_names(),
             self.get_all_entity_field_names(),
+            self.get_all_interface_type_names(),
+            self.get_all_interface_field_names(),
+            self.get_all_union_type_names(),
         ]
         .concat();
 
@@ -173,15 +265,21 @@ impl Schema {
         }
     }
 
-    fn check_duplicate_naming_between_enums_and_entities(self) -> anyhow::Result<Self> {
-        let duplicate_names = self
-            .get_all_enum_type_names()
-            .into_iter()
-            .filter(|k| self.entities.get(k).is_some())
-            .collect::<Vec<_>>();
+    fn check_duplicate_naming_between_type_defs(self) -> anyhow::Result<Self> {
+        let duplicate_names = [
+            self.get_all_enum_type_names(),
+            self.get_all_entity_type_names(),
+            self.get_all_interface_type_names(),
+            self.get_all_union_type_names(),
+        ]
+        .concat()
+        .into_iter()
+        .duplicates()
+        .sorted()
+        .collect::<Vec<_>>();
         if !duplicate_names.is_empty() {
             Err(anyhow!(
-                "EE214: Schema contains the following enums and entities with the same name, all \
+                "EE214: Schema contains the following type definitions with the same name, all \
                  type definitions must be unique in the schema: {}",
                 duplicate_names.join(", ")
             ))
@@ -190,18 +288,244 @@ impl Schema {
         }
     }
 
+    /// Checks that every interface an entity implements exists and that the entity declares
+    /// each of the interface fields with the same type.
+    fn check_interface_implementations(self) -> anyhow::Result<Self> {
+        for entity in self.entities.values().sorted_by_key(|e| &e.name) {
+            for interface_name in &entity.interfaces {
+                let interface = self.interfaces.get(interface_name).ok_or_else(|| {
+                    anyhow!(
+                        "EE218: Entity '{}' implements '{}', but no interface with that name is \
+                         defined in the schema",
+                        entity.name,
+                        interface_name
+                    )
+                })?;
+
+                for interface_field in interface.get_fields() {
+                    match entity.fields.get(&interface_field.name) {
+                        None => Err(anyhow!(
+                            "EE219: Entity '{}' implements interface '{}' but does not declare \
+                             its field '{}: {}'",
+                            entity.name,
+                            interface.name,
+                            interface_field.name,
+                            interface_field.field_type
+                        ))?,
+                        Some(field)
+                            if !field.field_type.is_same_type(&interface_field.field_type) =>
+                        {
+                            Err(anyhow!(
+                                "EE219: Field '{}' on entity '{}' has type '{}', but interface \
+                                 '{}' declares it as '{}'",
+                                field.name,
+                                entity.name,
+                                field.field_type,
+                                interface.name,
+                                interface_field.field_type
+                            ))?
+                        }
+                        Some(_) => (),
+                    }
+                }
+            }
+        }
+
+        for interface in self.interfaces.values().sorted_by_key(|i| &i.name) {
+            if self.get_implementors(&interface.name).is_empty() {
+                Err(anyhow!(
+                    "EE218: Interface '{}' is not implemented by any entity in the schema",
+                    interface.name
+                ))?
+            }
+        }
+
+        Ok(self)
+    }
+
+    fn check_union_members(self) -> anyhow::Result<Self> {
+        for union in self.unions.values().sorted_by_key(|u| &u.name) {
+            for member in &union.members {
+                if !self.entities.contains_key(member) {
+                    Err(anyhow!(
+                        "EE220: Union '{}' contains member '{}' which is not an entity. Union \
+                         members must be entity type definitions",
+                        union.name,
+                        member
+                    ))?
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    fn check_aggregations(self) -> anyhow::Result<Self> {
+        for entity in self.entities.values().sorted_by_key(|e| &e.name) {
+            if let Some(aggregation) = &entity.aggregation {
+                aggregation
+                    .validate(entity, &self)
+                    .context(format!("Invalid aggregation entity {}", entity.name))?;
+            }
+        }
+        Ok(self)
+    }
+
+    fn check_full_text_indexes(self) -> anyhow::Result<Self> {
+        //Each fulltext index creates a search function which becomes a root field in Hasura, so
+        //the names need to be unique and can't clash with entity names or the root fields of
+        //the entity tables
+        let names = self
+            .entities
+            .values()
+            .flat_map(|entity| entity.full_text_indexes.iter().map(|index| &index.name))
+            .collect::<Vec<_>>();
+
+        let duplicate_names = names.iter().duplicates().sorted().collect::<Vec<_>>();
+        if !duplicate_names.is_empty() {
+            return Err(anyhow!(
+                "EE224: Schema contains the following fulltext names more than once, all fulltext \
+                 names must be unique: {}",
+                duplicate_names.iter().join(", ")
+            ));
+        }
+
+        let reserved_names = self
+            .entities
+            .values()
+            .flat_map(|entity| {
+                let table_name = entity.get_table_name();
+                [
+                    entity.name.clone(),
+                    format!("{table_name}_by_pk"),
+                    format!("{table_name}_aggregate"),
+                    table_name,
+                ]
+            })
+            .collect::<HashSet<_>>();
+        let clashing_names = names
+            .into_iter()
+            .filter(|name| reserved_names.contains(*name))
+            .sorted()
+            .collect::<Vec<_>>();
+        if !clashing_names.is_empty() {
+            return Err(anyhow!(
+                "EE224: The following fulltext names clash with entity names, table names or the \
+                 <table>_by_pk and <table>_aggregate query names: {}",
+                clashing_names.iter().join(", ")
+            ));
+        }
+
+        Ok(self)
+    }
+
+    fn check_db_names(self) -> anyhow::Result<Self> {
+        let table_names = self
+            .entities
+            .values()
+            .map(|entity| entity.get_table_name())
+            .collect::<Vec<_>>();
+
+        let duplicate_table_names = table_names.iter().duplicates().sorted().collect::<Vec<_>>();
+        if !duplicate_table_names.is_empty() {
+            return Err(anyhow!(
+                "EE225: Schema contains entities with the same table name, all table names must \
+                 be unique: {}",
+                duplicate_table_names.iter().join(", ")
+            ));
+        }
+
+        //Each entity table also gets a history table with the _history suffix
+        let reserved_table_names = table_names
+            .iter()
+            .filter(|table_name| {
+                ENVIO_INTERNAL_RESERVED_POSTGRES_TABLES.contains(&table_name.as_str())
+                    || table_names.contains(&format!("{table_name}_history"))
+            })
+            .sorted()
+            .collect::<Vec<_>>();
+        if !reserved_table_names.is_empty() {
+            return Err(anyhow!(
+                "EE225: Schema contains table names that clash with internal tables. Please use \
+                 @entity(name: \"...\") to set a different table name: {}",
+                reserved_table_names.iter().join(", ")
+            ));
+        }
+
+        for entity in self.entities.values().sorted_by_key(|e| &e.name) {
+            let column_names = entity
+                .get_fields()
+                .into_iter()
+                .filter(|field| !field.field_type.is_derived_from())
+                .map(|field| field.get_db_column_name(&self))
+                .chain(
+                    entity
+                        .full_text_indexes
+                        .iter()
+                        .map(|index| Ok(index.name.clone())),
+                )
+                .chain([Ok("db_write_timestamp".to_string())])
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            let duplicate_column_names = column_names
+                .iter()
+                .duplicates()
+                .sorted()
+                .collect::<Vec<_>>();
+            if !duplicate_column_names.is_empty() {
+                return Err(anyhow!(
+                    "EE225: Entity '{}' contains the following column names more than once: {}",
+                    entity.name,
+                    duplicate_column_names.iter().join(", ")
+                ));
+            }
+        }
+
+        Ok(self)
+    }
+
     fn try_get_type_def(&self, name: &String) -> anyhow::Result<TypeDef> {
-        match (self.entities.get(name), self.enums.get(name)) {
-            (None, None) => Err(anyhow!("No type definition '{}' exists in schema", name)),
-            (Some(_), Some(_)) => Err(anyhow!(
-                "Both an enum and an entity type definition '{}' exist in schema",
+        let mut type_defs = vec![
+            self.entities.get(name).map(TypeDef::Entity),
+            self.enums.get(name).map(|_| TypeDef::Enum),
+            self.interfaces.get(name).map(|_| TypeDef::Interface),
+            self.unions.get(name).map(|_| TypeDef::Union),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        match type_defs.len() {
+            0 => Err(anyhow!("No type definition '{}' exists in schema", name)),
+            1 => Ok(type_defs.remove(0)),
+            _ => Err(anyhow!(
+                "Multiple type definitions named '{}' exist in schema",
                 name
             )),
-            (Some(entity), None) => Ok(TypeDef::Entity(entity)),
-            (None, Some(_)) => Ok(TypeDef::Enum),
         }
     }
 
+    /// Returns the entities that can be referenced through the given interface or union,
+    /// sorted by name. Returns an empty list for any other type definition.
+    pub fn get_implementors(&self, name: &String) -> Vec<&Entity> {
+        let implementors = match (self.interfaces.get(name), self.unions.get(name)) {
+            (Some(interface), _) => self
+                .entities
+                .values()
+                .filter(|e| e.interfaces.contains(&interface.name))
+                .collect(),
+            (None, Some(union)) => union
+                .members
+                .iter()
+                .filter_map(|member| self.entities.get(member))
+                .collect(),
+            (None, None) => vec![],
+        };
+        implementors
+            .into_iter()
+            .sorted_by_key(|e| &e.name)
+            .collect()
+    }
+
     fn check_related_type_defs_exist(self) -> anyhow::Result<Self> {
         for entity in self.entities.values() {
             for rel in entity.get_relationships() {
@@ -216,9 +540,10 @@ impl Schema {
                         let type_def = self.try_get_type_def(name)?;
 
                         match type_def {
-                            TypeDef::Enum => Err(anyhow!(
-                                "Cannot derive field {derived_from_field} from enum {name}. \
-                                 derivedFrom is intended to be used with Entity type definitions"
+                            TypeDef::Enum | TypeDef::Interface | TypeDef::Union => Err(anyhow!(
+                                "Cannot derive field {derived_from_field} from {name}. \
+                                     derivedFrom is intended to be used with Entity type \
+                                     definitions"
                             ))?,
                             TypeDef::Entity(derived_entity) => {
                                 match derived_entity.fields.get(derived_from_field) {
@@ -228,6 +553,13 @@ impl Schema {
                                     ))?,
                                     Some(field) => match field.field_type.get_underlying_scalar() {
                                         GqlScalar::Custom(name) if name == entity.name => (),
+                                        //A relationship to an interface or union that includes
+                                        //this entity can also be looked up in reverse
+                                        GqlScalar::Custom(name)
+                                            if self
+                                                .get_implementors(&name)
+                                                .iter()
+                                                .any(|e| e.name == entity.name) => {}
                                         GqlScalar::ID | GqlScalar::String => (),
                                         _ => Err(anyhow!(
                                             "Derived field '{derived_from_field}' on entity \
@@ -325,11 +657,165 @@ impl GraphQLEnum {
     }
 }
 
+/// An `interface` type definition. Interfaces have no table of their own, they declare the
+/// fields that every implementing entity is required to have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interface {
+    pub name: String,
+    pub fields: HashMap<String, Field>,
+}
+
+impl Interface {
+    fn new(name: &str, fields: Vec<Field>) -> anyhow::Result<Self> {
+        let fields = unique_hashmap::from_vec_no_duplicates(
+            fields.into_iter().map(|f| (f.name.clone(), f)).collect(),
+        )
+        .context(format!(
+            "Found fields with duplicate names on Interface {name}"
+        ))?;
+
+        Ok(Self {
+            name: name.to_string(),
+            fields,
+        })
+    }
+
+    fn from_interface(interface: &InterfaceType<String>) -> anyhow::Result<Self> {
+        let name = &interface.name;
+
+        let has_id = interface.fields.iter().any(|field| field.name == "id");
+        if !has_id {
+            return Err(anyhow!(
+                "No 'id' field found on interface {}. Please add an 'id' field to your interface.",
+                name
+            ));
+        }
+
+        if !interface.implements_interfaces.is_empty() {
+            return Err(anyhow!(
+                "Interface {} implements other interfaces, which is not supported. Please \
+                 declare the fields directly on the interface.",
+                name
+            ));
+        }
+
+        let fields = interface
+            .fields
+            .iter()
+            .map(Field::from_obj_field)
+            .collect::<anyhow::Result<Vec<Field>>>()
+            .context(format!("Failed parsing fields on interface {name}"))?;
+
+        Self::new(name, fields).context(format!("Failed constructing interface {name}"))
+    }
+
+    /// Returns the fields of this [`Interface`] sorted by field name.
+    pub fn get_fields(&self) -> Vec<&Field> {
+        self.fields.values().sorted_by_key(|v| &v.name).collect()
+    }
+}
+
+/// A `union` type definition, grouping entities that can be referenced by the same field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Union {
+    pub name: String,
+    pub members: Vec<String>,
+}
+
+impl Union {
+    fn from_union(union: &UnionType<String>) -> Self {
+        Self {
+            name: union.name.clone(),
+            members: union.types.clone(),
+        }
+    }
+}
+
+///Name of the enum of the entities implementing an interface or union
+pub fn get_entity_type_enum_name(polymorphic_type_name: &str) -> String {
+    format!("{polymorphic_type_name}EntityType")
+}
+
+///Name of the field storing which entity an interface or union reference points to
+pub fn get_entity_type_field_name(field_name: &str) -> String {
+    format!("{field_name}_type")
+}
+
+///Ids are only unique within the table of an entity, so a reference to an interface or
+///union also stores the type of the referenced entity. Every interface and union gets an
+///enum of its implementing entities, and every field referencing one gets a companion
+///field of that enum next to its id.
+fn add_polymorphic_entity_types(
+    entities: Vec<Entity>,
+    interfaces: &[Interface],
+    unions: &[Union],
+) -> anyhow::Result<(Vec<Entity>, Vec<GraphQLEnum>)> {
+    let implementors_by_type: HashMap<&String, Vec<String>> = interfaces
+        .iter()
+        .map(|interface| {
+            let implementors = entities
+                .iter()
+                .filter(|entity| entity.interfaces.contains(&interface.name))
+                .map(|entity| entity.name.clone())
+                .sorted()
+                .collect();
+            (&interface.name, implementors)
+        })
+        .chain(unions.iter().map(|union| {
+            let members = union.members.iter().cloned().sorted().collect();
+            (&union.name, members)
+        }))
+        .collect();
+
+    let entity_type_enums = implementors_by_type
+        .iter()
+        .sorted_by_key(|(name, _)| *name)
+        .map(|(name, implementors)| {
+            GraphQLEnum::new(get_entity_type_enum_name(name), implementors.clone())
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let entities = entities
+        .into_iter()
+        .map(|mut entity| {
+            let type_fields = entity
+                .get_fields()
+                .into_iter()
+                .filter_map(|field| field.get_entity_type_field(&implementors_by_type))
+                .collect::<Vec<_>>();
+            for type_field in type_fields {
+                if entity.fields.contains_key(&type_field.name) {
+                    Err(anyhow!(
+                        "EE226: Field '{}' on entity '{}' clashes with the field storing the \
+                         entity type of the interface or union reference next to it. Please \
+                         rename it.",
+                        type_field.name,
+                        entity.name
+                    ))?
+                }
+                entity.fields.insert(type_field.name.clone(), type_field);
+            }
+            Ok(entity)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok((entities, entity_type_enums))
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Entity {
     pub name: String,
+    ///Custom table name set with @entity(name: "...")
+    pub db_name: Option<String>,
     pub fields: HashMap<String, Field>,
     pub multi_field_indexes: Vec<MultiFieldIndex>,
+    ///Names of the interfaces this entity implements
+    pub interfaces: Vec<String>,
+    ///Set when the entity is declared with an @aggregation directive
+    pub aggregation: Option<Aggregation>,
+    pub full_text_indexes: Vec<FullTextIndex>,
+    ///The GraphQL description of the entity type, if any
+    pub description: Option<String>,
 }
 
 impl Entity {
@@ -375,8 +861,13 @@ impl Entity {
 
         Ok(Self {
             name: name.to_string(),
+            db_name: None,
             fields,
             multi_field_indexes,
+            interfaces: vec![],
+            aggregation: None,
+            full_text_indexes: vec![],
+            description: None,
         })
     }
 
@@ -425,7 +916,7 @@ impl Entity {
             ))?;
 
         // Map each field in the ObjectType to a Field, passing the indexed status
-        let fields = obj
+        let mut fields = obj
             .fields
             .iter()
             .map(
@@ -434,11 +925,70 @@ impl Entity {
             .collect::<anyhow::Result<Vec<Field>>>()
             .context(format!("Failed parsing fields on entity {name}"))?;
 
+        let db_name = match obj
+            .directives
+            .iter()
+            .filter(|directive| directive.name == "entity")
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            [] => None,
+            [directive] => get_db_name_from_directive(directive)
+                .context(format!("Failed parsing @entity directive on entity {name}"))?,
+            _ => Err(anyhow!(
+                "EE225: Cannot use more than one @entity directive on entity {name}"
+            ))?,
+        };
+
+        let aggregation = Aggregation::from_object(obj)
+            .context(format!("Failed parsing aggregation on entity {name}"))?;
+
+        let full_text_indexes = obj
+            .directives
+            .iter()
+            .filter(|directive| directive.name == "fulltext")
+            .map(FullTextIndex::from_directive)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .context(format!(
+                "Failed parsing fulltext directives on entity {name}"
+            ))?;
+
+        if aggregation.is_some() {
+            if fields
+                .iter()
+                .any(|f| f.name == Aggregation::INTERVAL_FIELD_NAME)
+            {
+                return Err(anyhow!(
+                    "EE221: The field '{}' is generated for aggregation entities. Please remove \
+                     it from entity {name}",
+                    Aggregation::INTERVAL_FIELD_NAME
+                ));
+            }
+            fields.push(Aggregation::interval_field());
+        }
+
         let entity = Self::new(name, fields, multi_field_indexes)
             .context(format!("Failed constructing entity {name}",))?;
 
+        for full_text_index in &full_text_indexes {
+            full_text_index.validate(&entity)?;
+        }
+
         // Here, store indexed information somewhere within your entity structure or handle them accordingly
-        Ok(entity)
+        Ok(Self {
+            db_name,
+            interfaces: obj.implements_interfaces.clone(),
+            aggregation,
+            full_text_indexes,
+            description: parse_description(&obj.description),
+            ..entity
+        })
+    }
+
+    /// Returns the name of the table of this [`Entity`] in the db, which is the entity name
+    /// unless a custom name is set with @entity(name: "...")
+    pub fn get_table_name(&self) -> String {
+        self.db_name.clone().unwrap_or_else(|| self.name.clone())
     }
 
     /// Returns the fields of this [`Entity`] sorted by field name.
@@ -484,7 +1034,7 @@ impl Entity {
                         |e| Some(Err(e)),
                         |type_def| match type_def {
                             TypeDef::Entity(entity) => Some(Ok((field, entity))),
-                            TypeDef::Enum => None,
+                            TypeDef::Enum | TypeDef::Interface | TypeDef::Union => None,
                         },
                     )
                 } else {
@@ -524,55 +1074,796 @@ impl Entity {
     }
 }
 
-///  used to get the positive integers in the directives from the GraphQL schema.
-fn get_positive_integer(arg_value: &Value<String>) -> anyhow::Result<u32> {
-    match arg_value {
-        Value::Int(i) => {
-            let val = i
-                .as_i64()
-                .context("EE217: Failed to convert value to i64")?;
-            if val < 0 {
-                return Err(anyhow!("EE217: Value must be a positive integer"));
-            }
-            Ok(val as u32)
-        }
-        _ => Err(anyhow!("EE217: Value must be an integer")),
-    }
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::EnumString, strum::EnumIter,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum AggregationInterval {
+    Hour,
+    Day,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Field {
-    pub name: String,
-    pub field_type: FieldType,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum AggregateFn {
+    Sum,
+    Count,
+    Min,
+    Max,
+    First,
+    Last,
 }
 
-impl Field {
-    fn from_obj_field(field: &ObjField<String>) -> anyhow::Result<Self> {
-        // Collect directives
-        let derived_from_directives = field
-            .directives
-            .iter()
-            .filter(|&directive| directive.name == "derivedFrom")
-            .collect::<Vec<&Directive<'_, String>>>();
+/// An `@aggregate(fn: "sum", arg: "amount")` directive on a field of an aggregation entity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregate {
+    pub function: AggregateFn,
+    ///Field on the source entity being aggregated, not used by count
+    pub arg: Option<String>,
+}
 
-        let indexed_directives = field
-            .directives
-            .iter()
-            .filter(|&directive| directive.name == "index")
-            .collect::<Vec<&Directive<'_, String>>>();
+///Aggregation entity columns with the expressions computing them, and the grouped by expressions
+type RollupColumns = (Vec<(String, String)>, Vec<String>);
 
-        let config_directives = field
+/// An `@aggregation(intervals: ["hour", "day"], source: "Swap")` directive on an entity.
+///
+/// Aggregation entities are rolled up from the source entity for each interval. Fields with an
+/// @aggregate directive are computed, any other fields (besides id, timestamp and the generated
+/// interval field) are dimensions grouped by from the source entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregation {
+    pub source: String,
+    pub intervals: Vec<AggregationInterval>,
+    pub aggregates: HashMap<String, Aggregate>,
+}
+
+impl Aggregation {
+    pub const INTERVAL_FIELD_NAME: &'static str = "interval";
+    pub const TIMESTAMP_FIELD_NAME: &'static str = "timestamp";
+
+    fn interval_field() -> Field {
+        Field {
+            name: Self::INTERVAL_FIELD_NAME.to_string(),
+            db_name: None,
+            field_type: FieldType::RegularField {
+                field_type: UserDefinedFieldType::NonNullType(Box::new(
+                    UserDefinedFieldType::Single(GqlScalar::String),
+                )),
+                has_indexed_directive: true,
+            },
+            description: Some("The interval the rollup was aggregated over".to_string()),
+        }
+    }
+
+    fn from_object(obj: &ObjectType<String>) -> anyhow::Result<Option<Self>> {
+        let aggregation_directives = obj
             .directives
             .iter()
-            .filter(|&directive| directive.name == "config")
-            .collect::<Vec<&Directive<'_, String>>>();
+            .filter(|directive| directive.name == "aggregation")
+            .collect::<Vec<_>>();
 
-        // Validate directive usage
-        let derived_from_count = derived_from_directives.len();
-        let indexed_count = indexed_directives.len();
-        let config_count = config_directives.len();
+        let aggregates = obj
+            .fields
+            .iter()
+            .filter_map(|field| {
+                field
+                    .directives
+                    .iter()
+                    .find(|directive| directive.name == "aggregate")
+                    .map(|directive| {
+                        Aggregate::from_directive(directive)
+                            .context(format!("Failed parsing @aggregate on field {}", field.name))
+                            .map(|aggregate| (field.name.clone(), aggregate))
+                    })
+            })
+            .collect::<anyhow::Result<HashMap<_, _>>>()?;
+
+        let directive = match aggregation_directives.as_slice() {
+            [] if aggregates.is_empty() => return Ok(None),
+            [] => Err(anyhow!(
+                "EE222: The @aggregate directive can only be used on entities with an \
+                 @aggregation directive"
+            ))?,
+            [directive] => *directive,
+            _ => Err(anyhow!(
+                "EE221: Cannot use more than one @aggregation directive on an entity"
+            ))?,
+        };
 
-        if derived_from_count > 1 || indexed_count > 1 || config_count > 1 {
+        let mut source = None;
+        let mut intervals = vec![];
+        for (arg_name, arg_value) in &directive.arguments {
+            match (arg_name.as_str(), arg_value) {
+                ("source", Value::String(name)) => source = Some(name.clone()),
+                ("intervals", Value::List(values)) => {
+                    intervals = values
+                        .iter()
+                        .map(|v| match v {
+                            Value::String(interval) => AggregationInterval::from_str(interval)
+                                .map_err(|_| {
+                                    anyhow!(
+                                        "EE221: Unknown aggregation interval '{interval}'. \
+                                         Supported intervals are: {}",
+                                        AggregationInterval::iter().join(", ")
+                                    )
+                                }),
+                            _ => Err(anyhow!("EE221: Listed intervals should be strings")),
+                        })
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                }
+                (arg_name, _) => Err(anyhow!(
+                    "EE221: Invalid argument '{arg_name}' in @aggregation directive. Eg. \
+                     @aggregation(intervals: [\"hour\", \"day\"], source: \"Swap\")"
+                ))?,
+            }
+        }
+
+        let source = source
+            .ok_or_else(|| anyhow!("EE221: Missing 'source' argument in @aggregation directive"))?;
+
+        if intervals.is_empty() {
+            Err(anyhow!(
+                "EE221: The @aggregation directive requires at least one interval"
+            ))?
+        }
+
+        let intervals = intervals.into_iter().unique().collect();
+
+        Ok(Some(Self {
+            source,
+            intervals,
+            aggregates,
+        }))
+    }
+
+    /// Returns the aggregated fields of the entity sorted by field name.
+    pub fn get_aggregates(&self) -> Vec<(&String, &Aggregate)> {
+        self.aggregates
+            .iter()
+            .sorted_by_key(|(name, _)| *name)
+            .collect()
+    }
+
+    /// Returns the fields of the aggregation entity that are grouped by, sorted by field name.
+    pub fn get_dimension_fields<'a>(&self, entity: &'a Entity) -> Vec<&'a Field> {
+        entity
+            .get_fields()
+            .into_iter()
+            .filter(|field| {
+                !field.is_primary_key()
+                    && field.name != Self::INTERVAL_FIELD_NAME
+                    && field.name != Self::TIMESTAMP_FIELD_NAME
+                    && !self.aggregates.contains_key(&field.name)
+            })
+            .collect()
+    }
+
+    /// Returns the quoted column of the field on the given entity. Columns of the aggregation
+    /// entity and of its source entity can have different custom names, so they are looked up
+    /// separately by field name.
+    fn get_column(entity: &Entity, field_name: &str, schema: &Schema) -> anyhow::Result<String> {
+        let field = entity.fields.get(field_name).ok_or_else(|| {
+            anyhow!(
+                "Unexpected, field {field_name} is missing on entity {}",
+                entity.name
+            )
+        })?;
+        Ok(format!("\"{}\"", field.get_db_column_name(schema)?))
+    }
+
+    fn get_source<'a>(&self, schema: &'a Schema) -> anyhow::Result<&'a Entity> {
+        schema
+            .entities
+            .get(&self.source)
+            .ok_or_else(|| anyhow!("Unexpected, aggregation source {} is missing", self.source))
+    }
+
+    /// Returns the bucket expression and the expression of the bucket id for the source entity
+    /// rows. The dimensions are serialized as a json array, so that null values are kept and
+    /// different buckets can't end up with the same id.
+    fn get_bucket_expressions(
+        &self,
+        interval: &AggregationInterval,
+        entity: &Entity,
+        schema: &Schema,
+    ) -> anyhow::Result<(String, String)> {
+        let source = self.get_source(schema)?;
+        let source_timestamp = Self::get_column(source, Self::TIMESTAMP_FIELD_NAME, schema)?;
+        let bucket = format!("date_trunc('{interval}', {source_timestamp})");
+        let source_dimensions = self
+            .get_dimension_fields(entity)
+            .into_iter()
+            .map(|field| Self::get_column(source, &field.name, schema))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let id = format!(
+            "concat('{interval}-', extract(epoch from {bucket})::bigint, '-', \
+             jsonb_build_array({})::text)",
+            source_dimensions.join(", ")
+        );
+        Ok((bucket, id))
+    }
+
+    /// Builds the query returning the bucket of the interval for each of the source entity ids
+    /// passed as the $1 parameter. The bucket start is returned in seconds.
+    pub fn get_buckets_query(
+        &self,
+        interval: &AggregationInterval,
+        entity: &Entity,
+        schema: &Schema,
+    ) -> anyhow::Result<String> {
+        let (bucket, id) = self.get_bucket_expressions(interval, entity, schema)?;
+        Ok(format!(
+            "SELECT \"id\" AS \"source_id\", {id} AS \"id\", extract(epoch from {bucket})::float8 \
+             AS \"bucket_start\" FROM \"public\".\"{}\" WHERE \"id\" = ANY($1);",
+            self.get_source(schema)?.get_table_name(),
+        ))
+    }
+
+    /// Returns the columns of the aggregation entity with the expressions computing them from
+    /// the source entity rows, and the expressions the source rows are grouped by.
+    fn get_rollup_columns(
+        &self,
+        interval: &AggregationInterval,
+        entity: &Entity,
+        schema: &Schema,
+    ) -> anyhow::Result<RollupColumns> {
+        let source = self.get_source(schema)?;
+        let source_timestamp = Self::get_column(source, Self::TIMESTAMP_FIELD_NAME, schema)?;
+        let (bucket, id) = self.get_bucket_expressions(interval, entity, schema)?;
+
+        let mut columns = vec![
+            ("\"id\"".to_string(), id),
+            (
+                Self::get_column(entity, Self::INTERVAL_FIELD_NAME, schema)?,
+                format!("'{interval}'"),
+            ),
+            (
+                Self::get_column(entity, Self::TIMESTAMP_FIELD_NAME, schema)?,
+                bucket.clone(),
+            ),
+        ];
+        let mut group_by = vec![bucket];
+        for field in self.get_dimension_fields(entity) {
+            let source_dimension = Self::get_column(source, &field.name, schema)?;
+            columns.push((
+                Self::get_column(entity, &field.name, schema)?,
+                source_dimension.clone(),
+            ));
+            group_by.push(source_dimension);
+        }
+
+        for (field_name, aggregate) in self.get_aggregates() {
+            let arg = match &aggregate.arg {
+                Some(arg) => Self::get_column(source, arg, schema)?,
+                None => String::new(),
+            };
+            let value = match aggregate.function {
+                AggregateFn::Sum => format!("SUM({arg})"),
+                AggregateFn::Count => "COUNT(*)".to_string(),
+                AggregateFn::Min => format!("MIN({arg})"),
+                AggregateFn::Max => format!("MAX({arg})"),
+                AggregateFn::First => {
+                    format!("(array_agg({arg} ORDER BY {source_timestamp} ASC))[1]")
+                }
+                AggregateFn::Last => {
+                    format!("(array_agg({arg} ORDER BY {source_timestamp} DESC))[1]")
+                }
+            };
+            let field = entity
+                .fields
+                .get(field_name)
+                .ok_or_else(|| anyhow!("Unexpected, aggregated field {field_name} is missing"))?;
+            //Sums and counts return wider types than the column, so the value is cast back to
+            //the column type to be parsed like any other row of the entity. Enum values keep
+            //their type since only min, max, first and last apply to them.
+            let value = match field
+                .get_postgres_field(schema, entity)?
+                .map(|pg_field| pg_field.field_type)
+            {
+                Some(
+                    field_type @ (PGPrimitive::Integer
+                    | PGPrimitive::Numeric(_)
+                    | PGPrimitive::DoublePrecision),
+                ) => format!("CAST({value} AS {})", field_type.get_sql_type()),
+                _ => value,
+            };
+            columns.push((Self::get_column(entity, field_name, schema)?, value));
+        }
+
+        Ok((columns, group_by))
+    }
+
+    /// Builds the query recomputing the aggregation rows of the bucket ids passed as the $1
+    /// parameter. The $2 and $3 parameters are the first and last bucket start in seconds, so
+    /// only the source rows of the touched buckets are scanned. Buckets without any source rows
+    /// are not returned.
+    pub fn get_rollup_query(
+        &self,
+        interval: &AggregationInterval,
+        entity: &Entity,
+        schema: &Schema,
+    ) -> anyhow::Result<String> {
+        let source = self.get_source(schema)?;
+        let source_timestamp = Self::get_column(source, Self::TIMESTAMP_FIELD_NAME, schema)?;
+        let (_, id) = self.get_bucket_expressions(interval, entity, schema)?;
+        let (columns, group_by) = self.get_rollup_columns(interval, entity, schema)?;
+
+        Ok(format!(
+            "SELECT {} FROM \"public\".\"{}\" WHERE {source_timestamp} >= to_timestamp($2) AND \
+             {source_timestamp} < to_timestamp($3) + interval '1 {interval}' AND {id} = ANY($1) \
+             GROUP BY {};",
+            columns
+                .iter()
+                .map(|(column, value)| format!("{value} AS {column}"))
+                .join(", "),
+            source.get_table_name(),
+            group_by.join(", "),
+        ))
+    }
+
+    /// Builds the insert filling the interval from all the source entity rows when the
+    /// aggregation table has no rows of the interval yet, eg. when the aggregation entity was
+    /// added to a schema whose db already has indexed source rows.
+    pub fn get_backfill_query(
+        &self,
+        interval: &AggregationInterval,
+        entity: &Entity,
+        schema: &Schema,
+    ) -> anyhow::Result<String> {
+        let (columns, group_by) = self.get_rollup_columns(interval, entity, schema)?;
+        let table_name = entity.get_table_name();
+
+        Ok(format!(
+            "INSERT INTO \"public\".\"{table_name}\" ({}) SELECT {} FROM \"public\".\"{}\" WHERE \
+             NOT EXISTS (SELECT 1 FROM \"public\".\"{table_name}\" WHERE {} = '{interval}') \
+             GROUP BY {};",
+            columns.iter().map(|(column, _)| column).join(", "),
+            columns.iter().map(|(_, value)| value).join(", "),
+            self.get_source(schema)?.get_table_name(),
+            Self::get_column(entity, Self::INTERVAL_FIELD_NAME, schema)?,
+            group_by.join(", "),
+        ))
+    }
+
+    fn validate(&self, entity: &Entity, schema: &Schema) -> anyhow::Result<()> {
+        let source = match schema.entities.get(&self.source) {
+            Some(source) if source.aggregation.is_none() => source,
+            _ => Err(anyhow!(
+                "EE221: The aggregation source '{}' must be an entity without an @aggregation \
+                 directive",
+                self.source
+            ))?,
+        };
+
+        let timestamp_type = UserDefinedFieldType::NonNullType(Box::new(
+            UserDefinedFieldType::Single(GqlScalar::Timestamp),
+        ));
+        for (entity_name, checked_entity) in [(&entity.name, entity), (&source.name, source)] {
+            let has_timestamp = checked_entity
+                .fields
+                .get(Self::TIMESTAMP_FIELD_NAME)
+                .is_some_and(|f| {
+                    f.field_type.is_same_type(&FieldType::RegularField {
+                        field_type: timestamp_type.clone(),
+                        has_indexed_directive: false,
+                    })
+                });
+            if !has_timestamp {
+                Err(anyhow!(
+                    "EE221: Entity '{entity_name}' must have a '{}: Timestamp!' field to be used \
+                     in an aggregation",
+                    Self::TIMESTAMP_FIELD_NAME
+                ))?
+            }
+        }
+
+        for (field_name, aggregate) in self.get_aggregates() {
+            let field = entity
+                .fields
+                .get(field_name)
+                .ok_or_else(|| anyhow!("Unexpected, aggregated field {field_name} is missing"))?;
+            aggregate
+                .validate(field, source)
+                .context(format!("Invalid @aggregate on field {field_name}"))?;
+        }
+
+        for field in self.get_dimension_fields(entity) {
+            match source.fields.get(&field.name) {
+                Some(source_field)
+                    if !field.field_type.is_derived_from()
+                        && field.field_type.is_same_type(&source_field.field_type) => {}
+                _ => Err(anyhow!(
+                    "EE223: Field '{}' on aggregation entity '{}' has no @aggregate directive, so \
+                     it is grouped by and must exist with the same type '{}' on the source entity \
+                     '{}'",
+                    field.name,
+                    entity.name,
+                    field.field_type,
+                    source.name
+                ))?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Aggregate {
+    fn from_directive(directive: &Directive<'_, String>) -> anyhow::Result<Self> {
+        let mut function = None;
+        let mut arg = None;
+        for (arg_name, arg_value) in &directive.arguments {
+            match (arg_name.as_str(), arg_value) {
+                ("fn", Value::String(name)) => {
+                    function = Some(AggregateFn::from_str(name).map_err(|_| {
+                        anyhow!(
+                            "EE222: Unknown aggregate function '{name}'. Supported functions \
+                             are: sum, count, min, max, first, last"
+                        )
+                    })?)
+                }
+                ("arg", Value::String(name)) => arg = Some(name.clone()),
+                (arg_name, _) => Err(anyhow!(
+                    "EE222: Invalid argument '{arg_name}' in @aggregate directive. Eg. \
+                     @aggregate(fn: \"sum\", arg: \"amount\")"
+                ))?,
+            }
+        }
+
+        let function = function
+            .ok_or_else(|| anyhow!("EE222: Missing 'fn' argument in @aggregate directive"))?;
+
+        match (function, &arg) {
+            (AggregateFn::Count, Some(_)) => Err(anyhow!(
+                "EE222: The count aggregate does not take an 'arg' argument"
+            ))?,
+            (AggregateFn::Count, None) | (_, Some(_)) => (),
+            (function, None) => Err(anyhow!(
+                "EE222: The {function} aggregate requires an 'arg' argument with the name of a \
+                 field on the source entity"
+            ))?,
+        }
+
+        Ok(Self { function, arg })
+    }
+
+    fn validate(&self, field: &Field, source: &Entity) -> anyhow::Result<()> {
+        let field_type = match &field.field_type {
+            FieldType::RegularField { field_type, .. } if !field_type.is_array() => field_type,
+            _ => Err(anyhow!(
+                "EE222: Aggregated fields must be a single non derived value"
+            ))?,
+        };
+
+        let Some(arg) = &self.arg else {
+            //Only count has no arg
+            return match field_type.get_underlying_scalar() {
+                GqlScalar::Int | GqlScalar::BigInt(_) => Ok(()),
+                _ => Err(anyhow!(
+                    "EE222: The count aggregate must be stored in an Int or BigInt field"
+                )),
+            };
+        };
+
+        let source_field = source
+            .fields
+            .get(arg)
+            .filter(|f| !f.field_type.is_derived_from() && !f.field_type.is_array())
+            .ok_or_else(|| {
+                anyhow!(
+                    "EE222: The aggregate arg '{arg}' must be a single value field on the source \
+                     entity '{}'",
+                    source.name
+                )
+            })?;
+
+        let source_scalar = source_field.field_type.get_underlying_scalar();
+        let is_numeric = matches!(
+            source_scalar,
+            GqlScalar::Int | GqlScalar::Float | GqlScalar::BigInt(_) | GqlScalar::BigDecimal(_)
+        );
+        if matches!(
+            self.function,
+            AggregateFn::Sum | AggregateFn::Min | AggregateFn::Max
+        ) && !is_numeric
+        {
+            Err(anyhow!(
+                "EE222: The {} aggregate requires a numeric arg. '{arg}' on '{}' has type '{}'",
+                self.function,
+                source.name,
+                source_field.field_type
+            ))?
+        }
+
+        if std::mem::discriminant(&source_scalar)
+            != std::mem::discriminant(&field_type.get_underlying_scalar())
+        {
+            Err(anyhow!(
+                "EE222: The aggregated field must have the same scalar type as '{arg}' on '{}', \
+                 which is '{}'",
+                source.name,
+                source_field.field_type
+            ))?
+        }
+
+        Ok(())
+    }
+}
+
+/// A `@fulltext(name: "search", language: "english", fields: ["name", "symbol"])` directive on an
+/// entity. Generates a tsvector column with a GIN index on the entity table and a search function
+/// with the same name which is exposed through Hasura.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullTextIndex {
+    pub name: String,
+    pub language: String,
+    pub fields: Vec<String>,
+}
+
+impl FullTextIndex {
+    const DEFAULT_LANGUAGE: &'static str = "english";
+    ///Text search configurations that ship with Postgres
+    const SUPPORTED_LANGUAGES: [&'static str; 29] = [
+        "simple",
+        "arabic",
+        "armenian",
+        "basque",
+        "catalan",
+        "danish",
+        "dutch",
+        "english",
+        "finnish",
+        "french",
+        "german",
+        "greek",
+        "hindi",
+        "hungarian",
+        "indonesian",
+        "irish",
+        "italian",
+        "lithuanian",
+        "nepali",
+        "norwegian",
+        "portuguese",
+        "romanian",
+        "russian",
+        "serbian",
+        "spanish",
+        "swedish",
+        "tamil",
+        "turkish",
+        "yiddish",
+    ];
+
+    fn from_directive(directive: &Directive<'_, String>) -> anyhow::Result<Self> {
+        let mut name = None;
+        let mut language = None;
+        let mut fields = None;
+        for (arg_name, arg_value) in &directive.arguments {
+            match (arg_name.as_str(), arg_value) {
+                ("name", Value::String(v)) => name = Some(v.clone()),
+                ("language", Value::String(v)) => language = Some(v.clone()),
+                ("fields", Value::List(values)) => {
+                    fields = Some(
+                        values
+                            .iter()
+                            .map(|v| match v {
+                                Value::String(field_name) => Ok(field_name.clone()),
+                                _ => {
+                                    Err(anyhow!("EE224: Listed fulltext fields should be strings"))
+                                }
+                            })
+                            .collect::<anyhow::Result<Vec<_>>>()?,
+                    )
+                }
+                _ => Err(anyhow!(
+                    "EE224: Invalid argument '{arg_name}' in @fulltext directive. Eg. \
+                     @fulltext(name: \"search\", language: \"english\", fields: [\"name\"])"
+                ))?,
+            }
+        }
+
+        let name =
+            name.ok_or_else(|| anyhow!("EE224: Missing 'name' argument in @fulltext directive"))?;
+        let fields = fields.filter(|fields| !fields.is_empty()).ok_or_else(|| {
+            anyhow!("EE224: The @fulltext directive requires a non empty list of 'fields'")
+        })?;
+        let language = language.unwrap_or_else(|| Self::DEFAULT_LANGUAGE.to_string());
+
+        if !Self::SUPPORTED_LANGUAGES.contains(&language.as_str()) {
+            Err(anyhow!(
+                "EE224: Unsupported fulltext language '{language}'. Supported languages are: {}",
+                Self::SUPPORTED_LANGUAGES.join(", ")
+            ))?
+        }
+
+        if !is_valid_postgres_db_name(&name) {
+            Err(anyhow!(
+                "EE224: Invalid fulltext name '{name}'. The name must start with a letter or \
+                 underscore, contain only letters, numbers and underscores and be at most 63 \
+                 characters long"
+            ))?
+        }
+
+        Ok(Self {
+            name,
+            language,
+            fields,
+        })
+    }
+
+    fn validate(&self, entity: &Entity) -> anyhow::Result<()> {
+        if entity.fields.contains_key(&self.name) {
+            Err(anyhow!(
+                "EE224: The fulltext name '{}' clashes with a field on entity '{}'",
+                self.name,
+                entity.name
+            ))?
+        }
+
+        let duplicate_fields = self.fields.iter().duplicates().collect::<Vec<_>>();
+        if !duplicate_fields.is_empty() {
+            Err(anyhow!(
+                "EE224: Fulltext '{}' lists the following fields more than once: {}",
+                self.name,
+                duplicate_fields.iter().join(", ")
+            ))?
+        }
+
+        for field_name in &self.fields {
+            let is_text_field = entity.fields.get(field_name).is_some_and(|field| {
+                !field.field_type.is_derived_from()
+                    && !field.field_type.is_array()
+                    && matches!(
+                        field.field_type.get_underlying_scalar(),
+                        GqlScalar::String | GqlScalar::ID
+                    )
+            });
+            if !is_text_field {
+                Err(anyhow!(
+                    "EE224: Fulltext '{}' field '{field_name}' must be a String or ID field on \
+                     entity '{}'",
+                    self.name,
+                    entity.name
+                ))?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Normalizes a GraphQL description, block strings keep a trailing newline when
+/// parsed and blank descriptions are treated as missing.
+fn parse_description(description: &Option<String>) -> Option<String> {
+    description
+        .as_ref()
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty())
+}
+
+/// Gets the custom db name from an `@entity(name: "...")` or `@field(name: "...")` directive.
+/// Returns None when the directive has no arguments, since @entity is commonly used as a marker.
+fn get_db_name_from_directive(directive: &Directive<'_, String>) -> anyhow::Result<Option<String>> {
+    match directive.arguments.as_slice() {
+        [] => Ok(None),
+        [(arg_name, Value::String(db_name))] if arg_name == "name" => {
+            if !is_valid_postgres_db_name(db_name) {
+                Err(anyhow!(
+                    "EE225: Invalid db name '{db_name}' in @{} directive. The name must start \
+                     with a letter or underscore, contain only letters, numbers and underscores \
+                     and be at most 63 characters long",
+                    directive.name
+                ))?
+            }
+            Ok(Some(db_name.clone()))
+        }
+        _ => Err(anyhow!(
+            "EE225: The @{} directive only takes a single string argument called 'name'. Eg. \
+             @{}(name: \"my_name\")",
+            directive.name,
+            directive.name
+        )),
+    }
+}
+
+///  used to get the positive integers in the directives from the GraphQL schema.
+fn get_positive_integer(arg_value: &Value<String>) -> anyhow::Result<u32> {
+    match arg_value {
+        Value::Int(i) => {
+            let val = i
+                .as_i64()
+                .context("EE217: Failed to convert value to i64")?;
+            if val < 0 {
+                return Err(anyhow!("EE217: Value must be a positive integer"));
+            }
+            Ok(val as u32)
+        }
+        _ => Err(anyhow!("EE217: Value must be an integer")),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Field {
+    pub name: String,
+    ///Custom column name set with @field(name: "...")
+    pub db_name: Option<String>,
+    pub field_type: FieldType,
+    ///The GraphQL description of the field, if any
+    pub description: Option<String>,
+}
+
+impl Field {
+    ///The companion field storing the entity type when this field references an
+    ///interface or union, keyed by name in the given implementors
+    fn get_entity_type_field(
+        &self,
+        implementors_by_type: &HashMap<&String, Vec<String>>,
+    ) -> Option<Field> {
+        let FieldType::RegularField { field_type, .. } = &self.field_type else {
+            return None;
+        };
+        let GqlScalar::Custom(type_name) = field_type.get_underlying_scalar() else {
+            return None;
+        };
+        if !implementors_by_type.contains_key(&type_name) {
+            return None;
+        }
+
+        let enum_type =
+            UserDefinedFieldType::Single(GqlScalar::Custom(get_entity_type_enum_name(&type_name)));
+        let field_type = if field_type.is_optional() {
+            enum_type
+        } else {
+            UserDefinedFieldType::NonNullType(Box::new(enum_type))
+        };
+        Some(Field {
+            name: get_entity_type_field_name(&self.name),
+            db_name: self
+                .db_name
+                .as_ref()
+                .map(|db_name| get_entity_type_field_name(db_name)),
+            field_type: FieldType::RegularField {
+                field_type,
+                has_indexed_directive: false,
+            },
+            description: Some(format!("Entity type of the {} reference", self.name)),
+        })
+    }
+
+    fn from_obj_field(field: &ObjField<String>) -> anyhow::Result<Self> {
+        // Collect directives
+        let derived_from_directives = field
+            .directives
+            .iter()
+            .filter(|&directive| directive.name == "derivedFrom")
+            .collect::<Vec<&Directive<'_, String>>>();
+
+        let indexed_directives = field
+            .directives
+            .iter()
+            .filter(|&directive| directive.name == "index")
+            .collect::<Vec<&Directive<'_, String>>>();
+
+        let config_directives = field
+            .directives
+            .iter()
+            .filter(|&directive| directive.name == "config")
+            .collect::<Vec<&Directive<'_, String>>>();
+
+        let field_directives = field
+            .directives
+            .iter()
+            .filter(|&directive| directive.name == "field")
+            .collect::<Vec<&Directive<'_, String>>>();
+
+        // Validate directive usage
+        let derived_from_count = derived_from_directives.len();
+        let indexed_count = indexed_directives.len();
+        let config_count = config_directives.len();
+        let field_count = field_directives.len();
+
+        if derived_from_count > 1 || indexed_count > 1 || config_count > 1 || field_count > 1 {
             return Err(anyhow!(
                 "EE202: Cannot use more than one of the same directive on field {}",
                 field.name
@@ -617,6 +1908,23 @@ impl Field {
             }
         };
 
+        let db_name = match field_directives.first() {
+            None => None,
+            Some(directive) => get_db_name_from_directive(directive).context(format!(
+                "Failed parsing @field directive on field {}",
+                field.name
+            ))?,
+        };
+
+        if db_name.is_some() && (field.name == "id" || derived_from_count > 0) {
+            return Err(anyhow!(
+                "EE225: A custom db name can't be set on the 'id' field or on @derivedFrom \
+                 fields, since they don't map to a regular column. Please remove the @field \
+                 directive from field {}",
+                field.name
+            ));
+        }
+
         let is_indexed = indexed_count > 0;
 
         // Parse the field type into UserDefinedFieldType
@@ -726,7 +2034,9 @@ impl Field {
 
         Ok(Field {
             name: field.name.clone(),
+            db_name,
             field_type,
+            description: parse_description(&field.description),
         })
     }
 
@@ -760,11 +2070,18 @@ impl Field {
                         )
                     })?;
 
+                if let Some(db_name) = &entity_field.db_name {
+                    return Ok(db_name.clone());
+                }
+
                 match entity_field.field_type.get_underlying_scalar() {
                     //In the case where there is a recipracol lookup, the actual
                     //underlying field contains _id at the end
                     GqlScalar::Custom(name)
-                        if matches!(schema.try_get_type_def(&name)?, TypeDef::Entity(_)) =>
+                        if matches!(
+                            schema.try_get_type_def(&name)?,
+                            TypeDef::Entity(_) | TypeDef::Interface | TypeDef::Union
+                        ) =>
                     {
                         Ok(format!("{derived_from_field}_id"))
                     }
@@ -779,7 +2096,28 @@ impl Field {
                 }
             }
 
-            FieldType::RegularField { .. } => Ok(self.name.clone()),
+            FieldType::RegularField { .. } => Ok(self.get_db_column_name(schema)?),
+        }
+    }
+
+    /// Returns the name of the column of this [`Field`] in the db. Entity references are
+    /// suffixed with _id unless a custom name is set with @field(name: "...")
+    pub fn get_db_column_name(&self, schema: &Schema) -> anyhow::Result<String> {
+        if let Some(db_name) = &self.db_name {
+            return Ok(db_name.clone());
+        }
+        match &self.field_type {
+            FieldType::DerivedFromField { .. } => Err(anyhow!(
+                "Unexpected, derived field {} has no db column",
+                self.name
+            )),
+            FieldType::RegularField { field_type, .. } => {
+                if field_type.is_entity_field(schema)? {
+                    Ok(format!("{}_id", self.name))
+                } else {
+                    Ok(self.name.clone())
+                }
+            }
         }
     }
 
@@ -828,19 +2166,38 @@ impl Field {
             FieldType::RegularField {
                 field_type: gql_field_type,
                 ..
-            } => Ok(Some(PGField {
-                field_name: self.name.clone(),
-                field_type: gql_field_type.to_underlying_postgres_primitive(schema)?,
-                is_array: gql_field_type.is_array(),
-                is_index: self.is_indexed_field(entity),
-                linked_entity: gql_field_type.get_linked_entity(schema)?,
-                is_primary_key: self.is_primary_key(),
-                is_nullable: gql_field_type.is_optional(),
-            })),
+            } => {
+                //Linked entities are referenced by the table they are stored in
+                let linked_entity = gql_field_type.get_linked_entity(schema)?.map(|name| {
+                    match schema.entities.get(&name) {
+                        Some(linked_entity) => linked_entity.get_table_name(),
+                        None => name,
+                    }
+                });
+                //Interface and union references have no single linked table, so the
+                //id column is named directly instead of being derived from the linked entity
+                let field_name =
+                    if linked_entity.is_none() && gql_field_type.is_entity_field(schema)? {
+                        format!("{}_id", self.name)
+                    } else {
+                        self.name.clone()
+                    };
+                Ok(Some(PGField {
+                    field_name,
+                    db_field_name: self.db_name.clone(),
+                    field_type: gql_field_type.to_underlying_postgres_primitive(schema)?,
+                    is_array: gql_field_type.is_array(),
+                    is_index: self.is_indexed_field(entity),
+                    linked_entity,
+                    is_primary_key: self.is_primary_key(),
+                    is_nullable: gql_field_type.is_optional(),
+                    description: self.description.clone(),
+                }))
+            }
         }
     }
 
-    pub fn get_derived_from_field(&self) -> Option<DerivedFieldTemplate> {
+    pub fn get_derived_from_field(&self, schema: &Schema) -> Option<DerivedFieldTemplate> {
         match &self.field_type {
             FieldType::DerivedFromField {
                 entity_name,
@@ -848,7 +2205,11 @@ impl Field {
             } => Some(DerivedFieldTemplate {
                 field_name: self.name.clone(),
                 derived_from_field: derived_from_field.clone(),
-                derived_from_entity: entity_name.clone(),
+                //Derived fields are looked up by the table name of the entity in the db schema
+                derived_from_entity: match schema.entities.get(entity_name) {
+                    Some(entity) => entity.get_table_name(),
+                    None => entity_name.clone(),
+                },
             }),
             FieldType::RegularField { .. } => None,
         }
@@ -993,7 +2354,10 @@ impl UserDefinedFieldType {
                 Self::NonNullType(inner_field_type) => match inner_field_type.as_ref() {
                     //Don't allow non derived from enity relationships inside arrays
                     Self::Single(GqlScalar::Custom(name))
-                        if matches!(schema.try_get_type_def(name)?, TypeDef::Entity(_)) =>
+                        if matches!(
+                            schema.try_get_type_def(name)?,
+                            TypeDef::Entity(_) | TypeDef::Interface | TypeDef::Union
+                        ) =>
                     {
                         Err(anyhow!(
                             "EE211: Arrays of entities is unsupported. Please use one of the \
@@ -1033,19 +2397,31 @@ impl UserDefinedFieldType {
         }
     }
 
+    /// Returns the primitive of the column, or the primitive of its elements for array columns.
+    /// Since the outer list is represented by the is_array flag, only inner lists become array
+    /// primitives. Eg. [[Int!]!]! is an array column of INTEGER[] (an INTEGER[][] column).
     pub fn to_underlying_postgres_primitive(&self, schema: &Schema) -> anyhow::Result<PGPrimitive> {
         match self {
             Self::Single(gql_scalar) => gql_scalar.to_underlying_postgres_primitive(schema),
-            Self::ListType(field_type) | Self::NonNullType(field_type) => {
-                field_type.to_underlying_postgres_primitive(schema)
-            }
+            Self::NonNullType(field_type) => field_type.to_underlying_postgres_primitive(schema),
+            Self::ListType(field_type) => field_type.to_postgres_element_primitive(schema),
         }
     }
 
-    pub fn is_optional(&self) -> bool {
-        !matches!(self, Self::NonNullType(_))
-    }
-
+    fn to_postgres_element_primitive(&self, schema: &Schema) -> anyhow::Result<PGPrimitive> {
+        match self {
+            Self::Single(gql_scalar) => gql_scalar.to_underlying_postgres_primitive(schema),
+            Self::NonNullType(field_type) => field_type.to_postgres_element_primitive(schema),
+            Self::ListType(field_type) => Ok(PGPrimitive::Array(Box::new(
+                field_type.to_postgres_element_primitive(schema)?,
+            ))),
+        }
+    }
+
+    pub fn is_optional(&self) -> bool {
+        !matches!(self, Self::NonNullType(_))
+    }
+
     pub fn is_array(&self) -> bool {
         match self {
             Self::ListType(_) => true,
@@ -1098,6 +2474,8 @@ impl UserDefinedFieldType {
 
     fn to_string(&self) -> String {
         match &self {
+            //The derived Display of the scalar would print Custom instead of the type name
+            Self::Single(GqlScalar::Custom(name)) => name.clone(),
             Self::Single(gql_scalar) => gql_scalar.to_string(),
             Self::ListType(field_type) => format!("[{}]", field_type.to_string()),
             Self::NonNullType(field_type) => format!("{}!", field_type.to_string()),
@@ -1267,6 +2645,13 @@ impl FieldType {
         matches!(self, Self::DerivedFromField { .. })
     }
 
+    /// Compares the declared types of two fields, ignoring directives like @index
+    /// that don't change the type itself.
+    pub fn is_same_type(&self, other: &Self) -> bool {
+        self.is_derived_from() == other.is_derived_from()
+            && self.to_user_defined_field_type() == other.to_user_defined_field_type()
+    }
+
     fn has_indexed_directive(&self) -> bool {
         match self {
             Self::DerivedFromField { .. } => false,
@@ -1372,11 +2757,14 @@ pub enum Relationship {
 }
 
 impl GqlScalar {
+    ///Interfaces and unions are also entity references, stored as the id of
+    ///the implementing entity
     fn is_entity(&self, schema: &Schema) -> anyhow::Result<bool> {
         match self {
-            GqlScalar::Custom(name) => {
-                Ok(matches!(schema.try_get_type_def(name)?, TypeDef::Entity(_)))
-            }
+            GqlScalar::Custom(name) => Ok(matches!(
+                schema.try_get_type_def(name)?,
+                TypeDef::Entity(_) | TypeDef::Interface | TypeDef::Union
+            )),
             _ => Ok(false),
         }
     }
@@ -1414,7 +2802,7 @@ impl GqlScalar {
             }
             GqlScalar::Timestamp => PGPrimitive::Timestamp,
             GqlScalar::Custom(name) => match schema.try_get_type_def(name)? {
-                TypeDef::Entity(_) => PGPrimitive::Text,
+                TypeDef::Entity(_) | TypeDef::Interface | TypeDef::Union => PGPrimitive::Text,
                 TypeDef::Enum => PGPrimitive::Enum(name.clone()),
             },
         };
@@ -1433,7 +2821,7 @@ impl GqlScalar {
             GqlScalar::Boolean => RescriptTypeIdent::Bool,
             GqlScalar::Timestamp => RescriptTypeIdent::Timestamp,
             GqlScalar::Custom(name) => match schema.try_get_type_def(name)? {
-                TypeDef::Entity(_) => RescriptTypeIdent::ID,
+                TypeDef::Entity(_) | TypeDef::Interface | TypeDef::Union => RescriptTypeIdent::ID,
                 TypeDef::Enum => RescriptTypeIdent::SchemaEnum(name.to_capitalized_options()),
             },
         };
@@ -1444,7 +2832,7 @@ impl GqlScalar {
         let opt_entity_name = match self {
             Self::Custom(name) => match schema.try_get_type_def(name)? {
                 TypeDef::Entity(entity) => Some(entity.name.clone()),
-                TypeDef::Enum => None,
+                TypeDef::Enum | TypeDef::Interface | TypeDef::Union => None,
             },
             _ => None,
         };
@@ -1456,7 +2844,8 @@ impl GqlScalar {
 #[cfg(test)]
 mod tests {
     use super::{
-        anyhow, Entity, Field, FieldType, GqlScalar, GraphQLEnum, Schema, UserDefinedFieldType,
+        anyhow, AggregationInterval, Entity, Field, FieldType, FullTextIndex, GqlScalar,
+        GraphQLEnum, Schema, UserDefinedFieldType,
     };
     use crate::config_parsing::postgres_types::Primitive as PGPrimitive;
     use graphql_parser::schema::{parse_schema, Definition, Document, ObjectType, TypeDefinition};
@@ -1721,7 +3110,7 @@ type TestEntity {
     fn gql_type_to_rescript_type_entity() {
         let test_entity_string = String::from("TestEntity");
         let test_entity = Entity::new(&test_entity_string, vec![], vec![]).unwrap();
-        let schema = Schema::new(vec![test_entity], vec![]).unwrap();
+        let schema = Schema::new(vec![test_entity], vec![], vec![], vec![]).unwrap();
         let rescript_type = UserDefinedFieldType::Single(GqlScalar::Custom(test_entity_string))
             .to_rescript_type(&schema)
             .expect("expected rescript type string");
@@ -1733,7 +3122,7 @@ type TestEntity {
     fn gql_type_to_rescript_type_enum() {
         let name = String::from("TestEnum");
         let test_enum = GraphQLEnum::new(name.clone(), vec![]).unwrap();
-        let schema = Schema::new(vec![], vec![test_enum]).unwrap();
+        let schema = Schema::new(vec![], vec![test_enum], vec![], vec![]).unwrap();
         let rescript_type = UserDefinedFieldType::Single(GqlScalar::Custom(name))
             .to_rescript_type(&schema)
             .expect("expected rescript type string");
@@ -1813,7 +3202,7 @@ type TestEntity {
         let test_enum = GraphQLEnum::new(name.clone(), vec!["TEST_VALUE".to_string()]).unwrap();
         let field_type =
             get_field_type_helper_with_additional("TestEnum!", vec![test_enum.clone()]);
-        let schema = Schema::new(vec![], vec![test_enum]).unwrap();
+        let schema = Schema::new(vec![], vec![test_enum], vec![], vec![]).unwrap();
         let pg_primitive = field_type
             .to_user_defined_field_type()
             .to_underlying_postgres_primitive(&schema)
@@ -1843,7 +3232,14 @@ type TestEntity {
             .to_user_defined_field_type()
             .to_underlying_postgres_primitive(&empty_schema)
             .expect("unable to get postgres primitive");
-        assert_eq!(pg_primitive, PGPrimitive::Integer);
+        assert_eq!(
+            pg_primitive,
+            PGPrimitive::Array(Box::new(PGPrimitive::Integer))
+        );
+        assert_eq!(
+            pg_primitive.get_res_field_type_variant(),
+            "Custom(\"INTEGER\" ++ \"[]\")"
+        );
         assert!(field_type.to_user_defined_field_type().is_array());
     }
 
@@ -2012,6 +3408,95 @@ type TestEntity {
         assert_eq!(pg_field.linked_entity, None);
     }
 
+    #[test]
+    fn test_get_postgres_field_enum_array_types() {
+        let schema_str = r#"
+enum Status {
+  ACTIVE
+  INACTIVE
+}
+
+type TestEntity {
+  id: ID!
+  statuses: [Status!]!
+  optStatuses: [Status!]
+  statusGrid: [[Status!]!]!
+}
+        "#;
+        let gql_doc = setup_document(schema_str).unwrap();
+        let schema = Schema::from_document(gql_doc).unwrap();
+        let entity = schema.entities.get("TestEntity").unwrap();
+        let get_pg_field = |name: &str| {
+            entity
+                .fields
+                .get(name)
+                .unwrap()
+                .get_postgres_field(&schema, entity)
+                .expect("Failed to get postgres field")
+                .unwrap()
+        };
+
+        let statuses = get_pg_field("statuses");
+        assert_eq!(statuses.field_type, PGPrimitive::Enum("Status".to_string()));
+        assert!(statuses.is_array);
+        assert!(!statuses.is_nullable);
+
+        let opt_statuses = get_pg_field("optStatuses");
+        assert_eq!(
+            opt_statuses.field_type,
+            PGPrimitive::Enum("Status".to_string())
+        );
+        assert!(opt_statuses.is_array);
+        assert!(opt_statuses.is_nullable);
+
+        let status_grid = get_pg_field("statusGrid");
+        assert_eq!(
+            status_grid.field_type,
+            PGPrimitive::Array(Box::new(PGPrimitive::Enum("Status".to_string())))
+        );
+        assert_eq!(
+            status_grid.field_type.get_res_field_type_variant(),
+            "Custom(Enums.Status.enum.name ++ \"[]\")"
+        );
+        assert!(status_grid.is_array);
+        assert!(!status_grid.is_nullable);
+    }
+
+    #[test]
+    fn test_nested_array_rescript_types() {
+        let schema_str = r#"
+enum Status {
+  ACTIVE
+  INACTIVE
+}
+
+type TestEntity {
+  id: ID!
+  statusGrid: [[Status!]!]!
+  intGrid: [[Int!]!]
+}
+        "#;
+        let gql_doc = setup_document(schema_str).unwrap();
+        let schema = Schema::from_document(gql_doc).unwrap();
+        let entity = schema.entities.get("TestEntity").unwrap();
+        let get_rescript_type = |name: &str| {
+            entity
+                .fields
+                .get(name)
+                .unwrap()
+                .field_type
+                .to_rescript_type(&schema)
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(
+            get_rescript_type("statusGrid"),
+            "array<array<Enums.Status.t>>"
+        );
+        assert_eq!(get_rescript_type("intGrid"), "option<array<array<int>>>");
+    }
+
     #[test]
     fn test_decimal_precision_config_happy_path() {
         let schema_str = r#"
@@ -2354,4 +3839,660 @@ type TestEntity {
             vec!["b".to_string(), "a".to_string()]
         );
     }
+
+    #[test]
+    fn test_interface_and_union_relationships() {
+        let schema_str = r#"
+interface Trade {
+  id: ID!
+  amount: BigInt!
+}
+
+type Swap implements Trade {
+  id: ID!
+  amount: BigInt!
+  pool: String!
+}
+
+type LimitFill implements Trade {
+  id: ID!
+  amount: BigInt! @index
+}
+
+union Fill = LimitFill
+
+type Account {
+  id: ID!
+  lastTrade: Trade
+  lastFill: Fill!
+}
+        "#;
+        let gql_doc = setup_document(schema_str).unwrap();
+        let schema = Schema::from_document(gql_doc).expect("Failed to parse schema from doc");
+
+        let implementors = schema
+            .get_implementors(&"Trade".to_string())
+            .iter()
+            .map(|e| e.name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            implementors,
+            vec!["LimitFill".to_string(), "Swap".to_string()]
+        );
+
+        let entity = schema.entities.get("Account").unwrap();
+        let field = entity.fields.get("lastTrade").unwrap();
+        assert!(field.field_type.is_entity_field(&schema).unwrap());
+        let pg_field = field
+            .get_postgres_field(&schema, entity)
+            .expect("Failed to get postgres field")
+            .unwrap();
+
+        assert_eq!(pg_field.field_name, "lastTrade_id");
+        assert_eq!(pg_field.field_type, PGPrimitive::Text);
+        assert!(pg_field.is_nullable);
+        assert_eq!(pg_field.linked_entity, None);
+
+        let field = entity.fields.get("lastFill").unwrap();
+        let pg_field = field
+            .get_postgres_field(&schema, entity)
+            .expect("Failed to get postgres field")
+            .unwrap();
+        assert_eq!(pg_field.field_name, "lastFill_id");
+        assert!(!pg_field.is_nullable);
+
+        //The type of the referenced entity is stored next to its id
+        assert_eq!(
+            schema.enums.get("TradeEntityType").unwrap().values,
+            vec!["LimitFill".to_string(), "Swap".to_string()]
+        );
+        let pg_field = entity
+            .fields
+            .get("lastTrade_type")
+            .unwrap()
+            .get_postgres_field(&schema, entity)
+            .expect("Failed to get postgres field")
+            .unwrap();
+        assert_eq!(pg_field.field_name, "lastTrade_type");
+        assert_eq!(
+            pg_field.field_type,
+            PGPrimitive::Enum("TradeEntityType".to_string())
+        );
+        assert!(pg_field.is_nullable);
+        let pg_field = entity
+            .fields
+            .get("lastFill_type")
+            .unwrap()
+            .get_postgres_field(&schema, entity)
+            .expect("Failed to get postgres field")
+            .unwrap();
+        assert!(!pg_field.is_nullable);
+    }
+
+    #[test]
+    fn test_error_case_entity_type_field_clash() {
+        let schema_str = r#"
+        interface Trade {
+            id: ID!
+        }
+        type Swap implements Trade {
+            id: ID!
+        }
+        type Account {
+            id: ID!
+            lastTrade: Trade
+            lastTrade_type: String
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let err_message = format!("{:?}", Schema::from_document(gql_doc).unwrap_err());
+        assert!(err_message.contains("EE226"));
+    }
+
+    #[test]
+    fn test_error_case_implementor_missing_interface_field() {
+        let schema_str = r#"
+        interface Trade {
+            id: ID!
+            amount: BigInt!
+        }
+        type Swap implements Trade {
+            id: ID!
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE219"));
+    }
+
+    #[test]
+    fn test_error_case_implementor_with_mismatched_field_type() {
+        let schema_str = r#"
+        interface Trade {
+            id: ID!
+            amount: BigInt!
+        }
+        type Swap implements Trade {
+            id: ID!
+            amount: BigInt
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE219"));
+    }
+
+    #[test]
+    fn test_error_case_unknown_interface() {
+        let schema_str = r#"
+        type Swap implements Trade {
+            id: ID!
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE218"));
+    }
+
+    #[test]
+    fn test_error_case_union_of_non_entity() {
+        let schema_str = r#"
+        enum Side {
+            BUY
+            SELL
+        }
+        type Swap {
+            id: ID!
+        }
+        union Trade = Swap | Side
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE220"));
+    }
+
+    #[test]
+    fn test_aggregation_entity() {
+        let schema_str = r#"
+type Swap {
+  id: ID!
+  pool: String!
+  amount: BigInt!
+  timestamp: Timestamp!
+}
+
+type SwapCandle @aggregation(intervals: ["hour", "day"], source: "Swap") {
+  id: ID!
+  timestamp: Timestamp!
+  pool: String!
+  volume: BigInt! @aggregate(fn: "sum", arg: "amount")
+  count: Int! @aggregate(fn: "count")
+}
+        "#;
+        let gql_doc = setup_document(schema_str).unwrap();
+        let schema = Schema::from_document(gql_doc).expect("Failed to parse schema from doc");
+        let entity = schema.entities.get("SwapCandle").unwrap();
+        let aggregation = entity.aggregation.as_ref().expect("Expected aggregation");
+
+        assert_eq!(
+            aggregation.intervals,
+            vec![AggregationInterval::Hour, AggregationInterval::Day]
+        );
+        assert!(entity.fields.contains_key("interval"));
+        assert_eq!(
+            aggregation
+                .get_dimension_fields(entity)
+                .iter()
+                .map(|f| f.name.clone())
+                .collect::<Vec<_>>(),
+            vec!["pool".to_string()]
+        );
+
+        let buckets_query = aggregation
+            .get_buckets_query(&AggregationInterval::Hour, entity, &schema)
+            .unwrap();
+        assert_eq!(
+            buckets_query,
+            "SELECT \"id\" AS \"source_id\", concat('hour-', extract(epoch from \
+             date_trunc('hour', \"timestamp\"))::bigint, '-', \
+             jsonb_build_array(\"pool\")::text) AS \"id\", extract(epoch from \
+             date_trunc('hour', \"timestamp\"))::float8 AS \"bucket_start\" FROM \
+             \"public\".\"Swap\" WHERE \"id\" = ANY($1);"
+        );
+
+        let rollup_query = aggregation
+            .get_rollup_query(&AggregationInterval::Hour, entity, &schema)
+            .unwrap();
+        assert_eq!(
+            rollup_query,
+            "SELECT concat('hour-', extract(epoch from date_trunc('hour', \
+             \"timestamp\"))::bigint, '-', jsonb_build_array(\"pool\")::text) AS \"id\", \
+             'hour' AS \"interval\", date_trunc('hour', \"timestamp\") AS \"timestamp\", \
+             \"pool\" AS \"pool\", CAST(COUNT(*) AS INTEGER) AS \"count\", CAST(SUM(\"amount\") \
+             AS NUMERIC) AS \"volume\" FROM \"public\".\"Swap\" WHERE \"timestamp\" >= \
+             to_timestamp($2) AND \"timestamp\" < to_timestamp($3) + interval '1 hour' AND \
+             concat('hour-', extract(epoch from date_trunc('hour', \"timestamp\"))::bigint, \
+             '-', jsonb_build_array(\"pool\")::text) = ANY($1) GROUP BY date_trunc('hour', \
+             \"timestamp\"), \"pool\";"
+        );
+
+        let backfill_query = aggregation
+            .get_backfill_query(&AggregationInterval::Day, entity, &schema)
+            .unwrap();
+        assert_eq!(
+            backfill_query,
+            "INSERT INTO \"public\".\"SwapCandle\" (\"id\", \"interval\", \"timestamp\", \
+             \"pool\", \"count\", \"volume\") SELECT concat('day-', extract(epoch from \
+             date_trunc('day', \"timestamp\"))::bigint, '-', jsonb_build_array(\"pool\")::text), \
+             'day', date_trunc('day', \"timestamp\"), \"pool\", CAST(COUNT(*) AS INTEGER), \
+             CAST(SUM(\"amount\") AS NUMERIC) FROM \"public\".\"Swap\" WHERE NOT EXISTS (SELECT \
+             1 FROM \"public\".\"SwapCandle\" WHERE \"interval\" = 'day') GROUP BY \
+             date_trunc('day', \"timestamp\"), \"pool\";"
+        );
+    }
+
+    #[test]
+    fn test_aggregation_bucket_id_keeps_null_dimensions() {
+        let schema_str = r#"
+type Swap {
+  id: ID!
+  pool: String
+  token: String
+  amount: BigInt!
+  timestamp: Timestamp!
+}
+
+type SwapCandle @aggregation(intervals: ["day"], source: "Swap") {
+  id: ID!
+  timestamp: Timestamp!
+  pool: String
+  token: String
+  volume: BigInt! @aggregate(fn: "sum", arg: "amount")
+}
+        "#;
+        let gql_doc = setup_document(schema_str).unwrap();
+        let schema = Schema::from_document(gql_doc).expect("Failed to parse schema from doc");
+        let entity = schema.entities.get("SwapCandle").unwrap();
+        let aggregation = entity.aggregation.as_ref().expect("Expected aggregation");
+
+        //concat_ws would skip a null pool or token, so ("a", null) and (null, "a") would share
+        //the same id
+        let buckets_query = aggregation
+            .get_buckets_query(&AggregationInterval::Day, entity, &schema)
+            .unwrap();
+        assert!(buckets_query.contains("jsonb_build_array(\"pool\", \"token\")::text"));
+        assert!(!buckets_query.contains("concat_ws"));
+    }
+
+    #[test]
+    fn test_error_case_aggregation_source_without_timestamp() {
+        let schema_str = r#"
+        type Swap {
+            id: ID!
+            amount: BigInt!
+        }
+        type SwapCandle @aggregation(intervals: ["hour"], source: "Swap") {
+            id: ID!
+            timestamp: Timestamp!
+            volume: BigInt! @aggregate(fn: "sum", arg: "amount")
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE221"));
+    }
+
+    #[test]
+    fn test_error_case_sum_of_non_numeric_field() {
+        let schema_str = r#"
+        type Swap {
+            id: ID!
+            pool: String!
+            timestamp: Timestamp!
+        }
+        type SwapCandle @aggregation(intervals: ["day"], source: "Swap") {
+            id: ID!
+            timestamp: Timestamp!
+            volume: String! @aggregate(fn: "sum", arg: "pool")
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE222"));
+    }
+
+    #[test]
+    fn test_error_case_aggregation_dimension_missing_on_source() {
+        let schema_str = r#"
+        type Swap {
+            id: ID!
+            amount: BigInt!
+            timestamp: Timestamp!
+        }
+        type SwapCandle @aggregation(intervals: ["day"], source: "Swap") {
+            id: ID!
+            timestamp: Timestamp!
+            pool: String!
+            volume: BigInt! @aggregate(fn: "sum", arg: "amount")
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE223"));
+    }
+
+    #[test]
+    fn test_fulltext_directive() {
+        let schema_str = r#"
+        type Token
+          @fulltext(name: "tokenSearch", language: "simple", fields: ["name", "symbol"])
+          @fulltext(name: "descriptionSearch", fields: ["description"]) {
+            id: ID!
+            name: String!
+            symbol: String!
+            description: String
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let schema = Schema::from_document(gql_doc).expect("Failed to build schema");
+        let token = schema.entities.get("Token").unwrap();
+
+        assert_eq!(
+            token.full_text_indexes,
+            vec![
+                FullTextIndex {
+                    name: "tokenSearch".to_string(),
+                    language: "simple".to_string(),
+                    fields: vec!["name".to_string(), "symbol".to_string()],
+                },
+                FullTextIndex {
+                    name: "descriptionSearch".to_string(),
+                    language: "english".to_string(),
+                    fields: vec!["description".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_error_case_fulltext_on_non_string_field() {
+        let schema_str = r#"
+        type Token @fulltext(name: "search", fields: ["name", "supply"]) {
+            id: ID!
+            name: String!
+            supply: BigInt!
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE224"));
+    }
+
+    #[test]
+    fn test_error_case_fulltext_unsupported_language() {
+        let schema_str = r#"
+        type Token @fulltext(name: "search", language: "klingon", fields: ["name"]) {
+            id: ID!
+            name: String!
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE224"));
+    }
+
+    #[test]
+    fn test_error_case_duplicate_fulltext_names() {
+        let schema_str = r#"
+        type Token @fulltext(name: "search", fields: ["name"]) {
+            id: ID!
+            name: String!
+        }
+        type Collection @fulltext(name: "search", fields: ["name"]) {
+            id: ID!
+            name: String!
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE224"));
+    }
+
+    #[test]
+    fn test_error_case_fulltext_name_clashes_with_table_root_fields() {
+        for fulltext_name in ["tokens", "tokens_by_pk", "tokens_aggregate"] {
+            let schema_str = format!(
+                r#"
+        type Token @entity(name: "tokens") @fulltext(name: "{fulltext_name}", fields: ["name"]) {{
+            id: ID!
+            name: String!
+        }}
+        "#
+            );
+
+            let gql_doc = setup_document(&schema_str).expect("Failed to parse schema");
+            let result = Schema::from_document(gql_doc);
+
+            let err_message = format!("{:?}", result.unwrap_err());
+            assert!(
+                err_message.contains("EE224") && err_message.contains(fulltext_name),
+                "Expected a clash error for {fulltext_name}, got: {err_message}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_descriptions() {
+        let schema_str = r#"
+"""
+A user's vault
+"""
+type Vault {
+  id: ID!
+  "Owner of the vault"
+  owner: String!
+  """
+  Balance of the vault
+
+    In wei
+  """
+  balance: BigInt!
+  createdAt: Int!
+}
+        "#;
+        let schema = Schema::from_document(setup_document(schema_str).unwrap()).unwrap();
+        let vault = schema.entities.get("Vault").unwrap();
+        assert_eq!(vault.description, Some("A user's vault".to_string()));
+
+        let owner = vault.fields.get("owner").unwrap();
+        assert_eq!(owner.description, Some("Owner of the vault".to_string()));
+        let pg_field = owner.get_postgres_field(&schema, vault).unwrap().unwrap();
+        assert_eq!(pg_field.description, Some("Owner of the vault".to_string()));
+
+        assert_eq!(
+            vault.fields.get("balance").unwrap().description,
+            Some("Balance of the vault\n\n  In wei".to_string())
+        );
+        assert_eq!(vault.fields.get("createdAt").unwrap().description, None);
+    }
+
+    #[test]
+    fn test_custom_db_names() {
+        let schema_str = r#"
+        type User @entity(name: "users") {
+            id: ID!
+            displayName: String! @field(name: "display_name")
+            gravatar: Gravatar @field(name: "gravatar_ref")
+            tokens: [Token!]! @derivedFrom(field: "owner")
+        }
+        type Gravatar @entity {
+            id: ID!
+        }
+        type Token @entity(name: "tokens") {
+            id: ID!
+            owner: User! @field(name: "owner_ref")
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let schema = Schema::from_document(gql_doc).expect("Failed to build schema");
+        let user = schema.entities.get("User").unwrap();
+        let gravatar = schema.entities.get("Gravatar").unwrap();
+        let token = schema.entities.get("Token").unwrap();
+
+        assert_eq!(user.get_table_name(), "users");
+        assert_eq!(gravatar.get_table_name(), "Gravatar");
+
+        let display_name = user.fields.get("displayName").unwrap();
+        assert_eq!(
+            display_name.get_db_column_name(&schema).unwrap(),
+            "display_name"
+        );
+
+        let pg_field = user
+            .fields
+            .get("gravatar")
+            .unwrap()
+            .get_postgres_field(&schema, user)
+            .unwrap()
+            .unwrap();
+        assert_eq!(pg_field.field_name, "gravatar");
+        assert_eq!(pg_field.db_field_name, Some("gravatar_ref".to_string()));
+        assert_eq!(pg_field.linked_entity, Some("Gravatar".to_string()));
+
+        let owner_pg_field = token
+            .fields
+            .get("owner")
+            .unwrap()
+            .get_postgres_field(&schema, token)
+            .unwrap()
+            .unwrap();
+        assert_eq!(owner_pg_field.linked_entity, Some("users".to_string()));
+
+        let tokens_field = user.fields.get("tokens").unwrap();
+        assert_eq!(
+            tokens_field.get_relational_key(&schema).unwrap(),
+            "owner_ref"
+        );
+        assert_eq!(
+            tokens_field
+                .get_derived_from_field(&schema)
+                .unwrap()
+                .derived_from_entity,
+            "tokens"
+        );
+    }
+
+    #[test]
+    fn test_error_case_invalid_custom_db_name() {
+        let schema_str = r#"
+        type User {
+            id: ID!
+            displayName: String! @field(name: "display name")
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE225"));
+    }
+
+    #[test]
+    fn test_error_case_custom_db_name_on_id() {
+        let schema_str = r#"
+        type User {
+            id: ID! @field(name: "user_id")
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE225"));
+    }
+
+    #[test]
+    fn test_error_case_duplicate_table_names() {
+        let schema_str = r#"
+        type User @entity(name: "Account") {
+            id: ID!
+        }
+        type Account {
+            id: ID!
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE225"));
+    }
+
+    #[test]
+    fn test_error_case_table_name_clashes_with_internal_table() {
+        let schema_str = r#"
+        type RawEvent @entity(name: "raw_events") {
+            id: ID!
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE225"));
+    }
+
+    #[test]
+    fn test_error_case_duplicate_column_names() {
+        let schema_str = r#"
+        type User {
+            id: ID!
+            name: String!
+            displayName: String! @field(name: "name")
+        }
+        "#;
+
+        let gql_doc = setup_document(schema_str).expect("Failed to parse schema");
+        let result = Schema::from_document(gql_doc);
+
+        let err_message = format!("{:?}", result.unwrap_err());
+        assert!(err_message.contains("EE225"));
+    }
 }
@@ -283,6 +283,11 @@ pub async fn generate_config_from_subgraph_id(
         save_full_history: None,
         field_selection: None,
         raw_events: None,
+        hasura: None,
+        graphql_client: None,
+        validators: None,
+        hooks: None,
+        local_services: None,
     };
     let mut networks: Vec<Network> = vec![];
 
@@ -2,6 +2,7 @@ use crate::utils::normalized_list::{NormalizedList, SingleOrList};
 use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use strum::EnumIter;
 
 impl<T: Clone + JsonSchema> JsonSchema for SingleOrList<T> {
     fn schema_name() -> Cow<'static, str> {
@@ -88,13 +89,76 @@ pub struct NetworkContract<T> {
     pub config: Option<T>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LocalServices {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Name of the docker compose project running the local services. Envio only \
+                       starts and stops the containers of this project (default: derived from \
+                       the project name). Local services started by previous envio versions run \
+                       under the project named after the generated directory, remove them with \
+                       `docker compose --project-name generated down -v` in the generated \
+                       directory"
+    )]
+    pub compose_project_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Host port of the local Postgres. The ENVIO_PG_PORT env var takes \
+                       precedence (default: 5433)"
+    )]
+    pub postgres_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Host port of the local Hasura. The HASURA_EXTERNAL_PORT env var takes \
+                       precedence (default: 8080)"
+    )]
+    pub hasura_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "If true, the ports that aren't set are derived from the project name, so \
+                       that the local services of several indexers can run on the same machine \
+                       (default: false)"
+    )]
+    pub derive_ports: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash, JsonSchema, EnumIter)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub enum ValidatorFormat {
+    JsonSchema,
+    Zod,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Hooks {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Shell commands run in order in the project root before codegen, eg. to \
+                       build the contracts the abis come from. Files they write are read when \
+                       hashing the project for changes. envio dev only runs them when codegen \
+                       needs to rerun. A failing command aborts codegen."
+    )]
+    pub pre_codegen: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Shell commands run in order in the project root after the generated \
+                       files are written and before the packages are installed. A failing \
+                       command aborts codegen."
+    )]
+    pub post_codegen: Option<Vec<String>>,
+}
+
 #[derive(Deserialize)]
 pub struct ConfigDiscriminant {
     pub ecosystem: Option<String>,
 }
 
 pub mod evm {
-    use super::{GlobalContract, NetworkContract, NetworkId};
+    use super::{
+        GlobalContract, Hooks, LocalServices, NetworkContract, NetworkId, ValidatorFormat,
+    };
     use crate::utils::normalized_list::SingleOrList;
     use schemars::JsonSchema;
     use serde::{Deserialize, Serialize};
@@ -172,6 +236,38 @@ pub mod evm {
                            false)"
         )]
         pub raw_events: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "If false, the indexer only uses Postgres. Hasura isn't started with \
+                           docker compose and the tables aren't tracked in it, for deployments \
+                           that query the tables through their own api (default: true)"
+        )]
+        pub hasura: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "If true, codegen also emits a TypeScript client package to \
+                           generated/graphql-client with typed entities, filters, ordering and \
+                           query helpers for the Hasura api of the indexer. Requires Hasura \
+                           (default: false)"
+        )]
+        pub graphql_client: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Runtime validators codegen emits to generated/validators for the \
+                           params of every event and for every entity. json-schema emits \
+                           schema.json and zod emits zod.ts, which imports zod from the project. \
+                           BigInt and BigDecimal values are validated as strings (default: none)"
+        )]
+        pub validators: Option<Vec<ValidatorFormat>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "Shell commands to run before and after codegen")]
+        pub hooks: Option<Hooks>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Ports and docker compose project name of the Postgres and Hasura \
+                           that envio dev starts locally"
+        )]
+        pub local_services: Option<LocalServices>,
     }
 
     impl Display for HumanConfig {
@@ -421,7 +517,9 @@ pub mod evm {
 pub mod fuel {
     use std::fmt::Display;
 
-    use super::{GlobalContract, NetworkContract, NetworkId};
+    use super::{
+        GlobalContract, Hooks, LocalServices, NetworkContract, NetworkId, ValidatorFormat,
+    };
     use schemars::JsonSchema;
     use serde::{Deserialize, Serialize};
     use strum::Display;
@@ -463,6 +561,38 @@ pub mod fuel {
                            false)"
         )]
         pub raw_events: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "If false, the indexer only uses Postgres. Hasura isn't started with \
+                           docker compose and the tables aren't tracked in it, for deployments \
+                           that query the tables through their own api (default: true)"
+        )]
+        pub hasura: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "If true, codegen also emits a TypeScript client package to \
+                           generated/graphql-client with typed entities, filters, ordering and \
+                           query helpers for the Hasura api of the indexer. Requires Hasura \
+                           (default: false)"
+        )]
+        pub graphql_client: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Runtime validators codegen emits to generated/validators for the \
+                           params of every event and for every entity. json-schema emits \
+                           schema.json and zod emits zod.ts, which imports zod from the project. \
+                           BigInt and BigDecimal values are validated as strings (default: none)"
+        )]
+        pub validators: Option<Vec<ValidatorFormat>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "Shell commands to run before and after codegen")]
+        pub hooks: Option<Hooks>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Ports and docker compose project name of the Postgres and Hasura \
+                           that envio dev starts locally"
+        )]
+        pub local_services: Option<LocalServices>,
     }
 
     impl Display for HumanConfig {
@@ -752,6 +882,11 @@ address: ["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"]
             ecosystem: fuel::EcosystemTag::Fuel,
             contracts: None,
             raw_events: None,
+            hasura: None,
+            graphql_client: None,
+            validators: None,
+            hooks: None,
+            local_services: None,
             networks: vec![fuel::Network {
                 id: 0,
                 start_block: 0,
@@ -798,6 +933,11 @@ address: ["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"]
             description: None,
             schema: None,
             raw_events: None,
+            hasura: None,
+            graphql_client: None,
+            validators: None,
+            hooks: None,
+            local_services: None,
             ecosystem: fuel::EcosystemTag::Fuel,
             contracts: None,
             networks: vec![],
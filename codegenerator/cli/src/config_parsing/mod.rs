@@ -6,5 +6,7 @@ pub mod graph_migration;
 pub mod human_config;
 pub mod hypersync_endpoints;
 pub mod postgres_types;
+pub mod schema_diagram;
+pub mod sql_schema;
 pub mod system_config;
 pub mod validation;
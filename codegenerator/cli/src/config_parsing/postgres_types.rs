@@ -1,3 +1,4 @@
+use crate::utils::text::Capitalize;
 use core::fmt;
 use serde::Serialize;
 use std::fmt::Display;
@@ -13,6 +14,8 @@ pub enum Primitive {
     Json,
     Timestamp,
     Enum(String),
+    ///An inner array of a multidimensional array column, eg. the INTEGER[] in INTEGER[][]
+    Array(Box<Primitive>),
 }
 
 impl Primitive {
@@ -30,6 +33,46 @@ impl Primitive {
             Self::Timestamp => "Timestamp".to_string(),
             Self::DoublePrecision => "DoublePrecision".to_string(),
             Self::Enum(enum_name) => format!("Custom(Enums.{enum_name}.enum.name)"),
+            Self::Array(_) => format!("Custom({})", self.get_res_sql_type_code()),
+        }
+    }
+
+    ///Rescript code evaluating to the sql type, enum type names are only known at runtime
+    fn get_res_sql_type_code(&self) -> String {
+        match &self {
+            Self::Boolean => "\"BOOLEAN\"".to_string(),
+            Self::Text => "\"TEXT\"".to_string(),
+            Self::Integer => "\"INTEGER\"".to_string(),
+            Self::Numeric(None) => "\"NUMERIC\"".to_string(),
+            Self::Numeric(Some((precision, scale))) => {
+                format!("\"NUMERIC({}, {})\"", precision, scale)
+            }
+            Self::Serial => "\"SERIAL\"".to_string(),
+            Self::Json => "\"JSONB\"".to_string(),
+            Self::Timestamp => "\"TIMESTAMP WITH TIME ZONE\"".to_string(),
+            Self::DoublePrecision => "\"DOUBLE PRECISION\"".to_string(),
+            Self::Enum(enum_name) => format!("Enums.{enum_name}.enum.name"),
+            Self::Array(primitive) => format!("{} ++ \"[]\"", primitive.get_res_sql_type_code()),
+        }
+    }
+
+    ///The sql type as the db migrations create it, enum types are named by the
+    ///capitalized enum name
+    pub fn get_sql_type(&self) -> String {
+        match &self {
+            Self::Boolean => "BOOLEAN".to_string(),
+            Self::Text => "TEXT".to_string(),
+            Self::Integer => "INTEGER".to_string(),
+            Self::Numeric(None) => "NUMERIC".to_string(),
+            Self::Numeric(Some((precision, scale))) => {
+                format!("NUMERIC({}, {})", precision, scale)
+            }
+            Self::Serial => "SERIAL".to_string(),
+            Self::Json => "JSONB".to_string(),
+            Self::Timestamp => "TIMESTAMP WITH TIME ZONE".to_string(),
+            Self::DoublePrecision => "DOUBLE PRECISION".to_string(),
+            Self::Enum(enum_name) => enum_name.capitalize(),
+            Self::Array(primitive) => format!("{}[]", primitive.get_sql_type()),
         }
     }
 }
@@ -52,10 +95,14 @@ impl Display for Primitive {
 #[derive(Serialize, Debug, PartialEq, Clone)]
 pub struct Field {
     pub field_name: String,
+    ///Custom column name, defaults to the field name (suffixed with _id for linked entities)
+    pub db_field_name: Option<String>,
     pub linked_entity: Option<String>,
     pub is_index: bool,
     pub is_primary_key: bool,
     pub is_nullable: bool,
     pub is_array: bool,
     pub field_type: Primitive,
+    ///Written as a column comment so that it shows in the Hasura api
+    pub description: Option<String>,
 }
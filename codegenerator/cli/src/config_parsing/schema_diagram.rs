@@ -0,0 +1,317 @@
+use super::entity_parsing::{Entity, Field, FieldType, Schema};
+use anyhow::{Context, Result};
+use itertools::Itertools;
+
+#[derive(Debug, Clone, PartialEq)]
+enum DiagramKey {
+    Primary,
+    Foreign,
+}
+
+impl DiagramKey {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Primary => "PK",
+            Self::Foreign => "FK",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DiagramField {
+    name: String,
+    ///The GraphQL type as declared in the schema, eg. [String!]!
+    gql_type: String,
+    key: Option<DiagramKey>,
+    ///Notes on the indices and derived lookup of the field
+    notes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DiagramEntity {
+    name: String,
+    fields: Vec<DiagramField>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum RelationKind {
+    ///A field storing the id of the related entity
+    Link { is_optional: bool },
+    ///A @derivedFrom field looking up the entities that link back to this one
+    DerivedFrom,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DiagramRelation {
+    from_entity: String,
+    to_entity: String,
+    field_name: String,
+    kind: RelationKind,
+}
+
+///Entities of the schema and the relationships between them, for rendering as an
+///entity relationship diagram
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaDiagram {
+    entities: Vec<DiagramEntity>,
+    relations: Vec<DiagramRelation>,
+}
+
+impl DiagramField {
+    fn from_field(field: &Field, entity: &Entity, schema: &Schema) -> Result<Self> {
+        let mut notes = vec![];
+        let gql_type = match &field.field_type {
+            FieldType::DerivedFromField {
+                entity_name,
+                derived_from_field,
+            } => {
+                notes.push(format!("derived from {entity_name}.{derived_from_field}"));
+                format!("[{entity_name}!]!")
+            }
+            FieldType::RegularField { field_type, .. } => field_type.to_string(),
+        };
+
+        if field.is_indexed_field(entity) {
+            notes.push("indexed".to_string());
+        }
+        for index in entity.get_composite_indices() {
+            if index.contains(&field.name) {
+                notes.push(format!("index({})", index.join(", ")));
+            }
+        }
+
+        //Interface and union fields reference an entity too, without a relationship to
+        //a single entity
+        let key = if field.name == "id" {
+            Some(DiagramKey::Primary)
+        } else if !field.field_type.is_derived_from() && field.field_type.is_entity_field(schema)? {
+            Some(DiagramKey::Foreign)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            name: field.name.clone(),
+            gql_type,
+            key,
+            notes,
+        })
+    }
+
+    ///Mermaid types can't contain ! and read better with array suffixes, eg. String[]
+    fn get_mermaid_type(&self) -> String {
+        let gql_type = self.gql_type.replace('!', "");
+        let array_depth = gql_type.matches('[').count();
+        format!(
+            "{}{}",
+            gql_type.replace(['[', ']'], ""),
+            "[]".repeat(array_depth)
+        )
+    }
+}
+
+impl SchemaDiagram {
+    pub fn from_schema(schema: &Schema) -> Result<Self> {
+        let mut entities = vec![];
+        let mut relations = vec![];
+
+        for entity in schema
+            .entities
+            .values()
+            .sorted_by_key(|entity| &entity.name)
+        {
+            let related_entities = entity.get_related_entities(schema).context(format!(
+                "Failed getting related entities of entity {}",
+                entity.name
+            ))?;
+            entities.push(DiagramEntity {
+                name: entity.name.clone(),
+                fields: entity
+                    .get_fields()
+                    .into_iter()
+                    .map(|field| DiagramField::from_field(field, entity, schema))
+                    .collect::<Result<_>>()?,
+            });
+
+            for (field, related_entity) in related_entities {
+                let kind = match &field.field_type {
+                    FieldType::DerivedFromField { .. } => RelationKind::DerivedFrom,
+                    FieldType::RegularField { .. } => RelationKind::Link {
+                        is_optional: field.field_type.is_optional(),
+                    },
+                };
+                relations.push(DiagramRelation {
+                    from_entity: entity.name.clone(),
+                    to_entity: related_entity.name.clone(),
+                    field_name: field.name.clone(),
+                    kind,
+                });
+            }
+        }
+
+        Ok(Self {
+            entities,
+            relations,
+        })
+    }
+
+    ///Renders a Mermaid erDiagram, which GitHub renders in markdown
+    pub fn to_mermaid(&self) -> String {
+        let mut lines = vec!["erDiagram".to_string()];
+
+        for entity in &self.entities {
+            lines.push(format!("  {} {{", entity.name));
+            for field in &entity.fields {
+                let key = match &field.key {
+                    Some(key) => format!(" {}", key.as_str()),
+                    None => String::new(),
+                };
+                let comment = if field.notes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" \"{}\"", field.notes.join(", ").replace('"', "'"))
+                };
+                lines.push(format!(
+                    "    {} {}{key}{comment}",
+                    field.get_mermaid_type(),
+                    field.name
+                ));
+            }
+            lines.push("  }".to_string());
+        }
+
+        for relation in &self.relations {
+            //Cardinality reads from the entity with the field to the related entity
+            let cardinality = match &relation.kind {
+                RelationKind::Link { is_optional: true } => "}o--o|",
+                RelationKind::Link { is_optional: false } => "}o--||",
+                //Dotted since the relationship isn't stored on this entity
+                RelationKind::DerivedFrom => "||..o{",
+            };
+            lines.push(format!(
+                "  {} {cardinality} {} : \"{}\"",
+                relation.from_entity, relation.to_entity, relation.field_name
+            ));
+        }
+
+        format!("{}\n", lines.join("\n"))
+    }
+
+    ///Renders a Graphviz digraph with a table node per entity
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec![
+            "digraph schema {".to_string(),
+            "  rankdir=LR;".to_string(),
+            "  node [shape=plaintext];".to_string(),
+        ];
+
+        for entity in &self.entities {
+            let mut rows = vec![format!(
+                "<tr><td colspan=\"3\" bgcolor=\"lightgrey\"><b>{}</b></td></tr>",
+                escape_html(&entity.name)
+            )];
+            for field in &entity.fields {
+                let notes = field
+                    .key
+                    .iter()
+                    .map(|key| key.as_str().to_string())
+                    .chain(field.notes.iter().cloned())
+                    .join(", ");
+                rows.push(format!(
+                    "<tr><td port=\"{name}\" align=\"left\">{name}</td><td \
+                     align=\"left\">{}</td><td align=\"left\">{}</td></tr>",
+                    escape_html(&field.gql_type),
+                    escape_html(&notes),
+                    name = escape_html(&field.name),
+                ));
+            }
+            lines.push(format!(
+                "  \"{}\" [label=<<table border=\"0\" cellborder=\"1\" cellspacing=\"0\">{}</table>>];",
+                entity.name,
+                rows.join("")
+            ));
+        }
+
+        for relation in &self.relations {
+            let style = match relation.kind {
+                RelationKind::Link { .. } => "solid",
+                RelationKind::DerivedFrom => "dashed",
+            };
+            lines.push(format!(
+                "  \"{}\":\"{}\" -> \"{}\" [label=\"{}\", style={style}];",
+                relation.from_entity, relation.field_name, relation.to_entity, relation.field_name
+            ));
+        }
+
+        lines.push("}".to_string());
+        format!("{}\n", lines.join("\n"))
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::SchemaDiagram;
+    use crate::config_parsing::entity_parsing::Schema;
+
+    const SCHEMA: &str = r#"
+type User {
+  id: ID!
+  name: String
+  gravatars: [Gravatar!]! @derivedFrom(field: "owner")
+}
+
+type Gravatar @index(fields: ["owner", "size"]) {
+  id: ID!
+  owner: User!
+  size: BigInt! @index
+  previousOwner: User
+}
+"#;
+
+    fn diagram() -> SchemaDiagram {
+        let schema_doc = graphql_parser::schema::parse_schema::<String>(SCHEMA).unwrap();
+        let schema = Schema::from_document(schema_doc).unwrap();
+        SchemaDiagram::from_schema(&schema).unwrap()
+    }
+
+    #[test]
+    fn renders_mermaid() {
+        assert_eq!(
+            diagram().to_mermaid(),
+            r#"erDiagram
+  Gravatar {
+    ID id PK
+    User owner FK "index(owner, size)"
+    User previousOwner FK
+    BigInt size "indexed, index(owner, size)"
+  }
+  User {
+    Gravatar[] gravatars "derived from Gravatar.owner"
+    ID id PK
+    String name
+  }
+  Gravatar }o--|| User : "owner"
+  Gravatar }o--o| User : "previousOwner"
+  User ||..o{ Gravatar : "gravatars"
+"#
+        );
+    }
+
+    #[test]
+    fn renders_dot_edges() {
+        let dot = diagram().to_dot();
+        assert!(dot.starts_with("digraph schema {"));
+        assert!(dot.contains(r#"  "Gravatar":"owner" -> "User" [label="owner", style=solid];"#));
+        assert!(dot
+            .contains(r#"  "User":"gravatars" -> "Gravatar" [label="gravatars", style=dashed];"#));
+        assert!(dot.contains("<td align=\"left\">[Gravatar!]!</td>"));
+    }
+}
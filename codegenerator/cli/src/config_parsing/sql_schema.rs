@@ -0,0 +1,709 @@
+use super::{
+    entity_parsing::{Entity, Schema},
+    system_config::SystemConfig,
+};
+use crate::utils::text::Capitalize;
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+
+const HISTORY_ROW_ACTION_ENUM: &str = "ENTITY_HISTORY_ROW_ACTION";
+const CONTRACT_TYPE_ENUM: &str = "CONTRACT_TYPE";
+const ENTITY_TYPE_ENUM: &str = "ENTITY_TYPE";
+const DYNAMIC_CONTRACT_REGISTRY_TABLE: &str = "dynamic_contract_registry";
+
+///A column as the db migrations of the indexer create it
+#[derive(Debug, Clone, PartialEq)]
+struct SqlColumn {
+    name: String,
+    sql_type: String,
+    is_array: bool,
+    is_nullable: bool,
+    is_primary_key: bool,
+    is_index: bool,
+    default: Option<&'static str>,
+    description: Option<String>,
+}
+
+impl SqlColumn {
+    fn new(name: &str, sql_type: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            sql_type: sql_type.to_string(),
+            is_array: false,
+            is_nullable: false,
+            is_primary_key: false,
+            is_index: false,
+            default: None,
+            description: None,
+        }
+    }
+
+    fn nullable(self) -> Self {
+        Self {
+            is_nullable: true,
+            ..self
+        }
+    }
+
+    fn primary_key(self) -> Self {
+        Self {
+            is_primary_key: true,
+            ..self
+        }
+    }
+
+    fn to_sql(&self) -> String {
+        let array_suffix = if self.is_array { "[]" } else { "" };
+        let constraint = match self.default {
+            Some(default) => format!(" DEFAULT {default}"),
+            None if self.is_nullable => String::new(),
+            None => " NOT NULL".to_string(),
+        };
+        format!(
+            "\"{}\" {}{array_suffix}{constraint}",
+            self.name, self.sql_type
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SqlFullTextIndex {
+    name: String,
+    language: String,
+    columns: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SqlTable {
+    name: String,
+    columns: Vec<SqlColumn>,
+    ///Indices over more than one column
+    composite_indices: Vec<Vec<String>>,
+    full_text_indices: Vec<SqlFullTextIndex>,
+    description: Option<String>,
+}
+
+impl SqlTable {
+    fn new(name: &str, columns: Vec<SqlColumn>) -> Self {
+        Self {
+            name: name.to_string(),
+            columns,
+            composite_indices: vec![],
+            full_text_indices: vec![],
+            description: None,
+        }
+    }
+
+    fn from_entity(entity: &Entity, schema: &Schema) -> Result<Self> {
+        let mut columns = vec![];
+        for field in entity.get_fields() {
+            let Some(pg_field) = field.get_postgres_field(schema, entity)? else {
+                continue;
+            };
+            columns.push(SqlColumn {
+                name: field.get_db_column_name(schema)?,
+                sql_type: pg_field.field_type.get_sql_type(),
+                is_array: pg_field.is_array,
+                is_nullable: pg_field.is_nullable,
+                is_primary_key: pg_field.is_primary_key,
+                is_index: pg_field.is_index,
+                default: None,
+                description: pg_field.description,
+            });
+        }
+        columns.push(SqlColumn {
+            default: Some("CURRENT_TIMESTAMP"),
+            ..SqlColumn::new("db_write_timestamp", "TIMESTAMP")
+        });
+
+        //Indices are declared with the field names, which differ from the column
+        //names of linked entities and fields with a custom name
+        let get_column_name = |field_name: &String| match entity.fields.get(field_name) {
+            Some(field) => field.get_db_column_name(schema),
+            None => Ok(field_name.clone()),
+        };
+
+        let composite_indices = entity
+            .get_composite_indices()
+            .iter()
+            .map(|index| index.iter().map(get_column_name).collect())
+            .collect::<Result<_>>()?;
+
+        let full_text_indices = entity
+            .full_text_indexes
+            .iter()
+            .map(|index| {
+                Ok(SqlFullTextIndex {
+                    name: index.name.clone(),
+                    language: index.language.clone(),
+                    columns: index
+                        .fields
+                        .iter()
+                        .map(get_column_name)
+                        .collect::<Result<_>>()?,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            name: entity.get_table_name(),
+            columns,
+            composite_indices,
+            full_text_indices,
+            description: entity.description.clone(),
+        })
+    }
+
+    ///The table the indexer records every change of an entity in, so that it can
+    ///roll back reorgs. Matches EntityHistory.fromTable in the envio package.
+    fn to_history_table(&self) -> Self {
+        let current_fields = [
+            "entity_history_block_timestamp",
+            "entity_history_chain_id",
+            "entity_history_block_number",
+            "entity_history_log_index",
+        ];
+
+        let mut columns: Vec<SqlColumn> = current_fields
+            .iter()
+            .map(|name| SqlColumn::new(name, "INTEGER").primary_key())
+            .collect();
+        columns.extend(
+            current_fields
+                .iter()
+                .map(|name| SqlColumn::new(&format!("previous_{name}"), "INTEGER").nullable()),
+        );
+        columns.extend(
+            self.columns
+                .iter()
+                .filter(|column| column.name != "db_write_timestamp")
+                .map(|column| {
+                    if column.name == "id" {
+                        column.clone().primary_key()
+                    } else {
+                        SqlColumn {
+                            is_nullable: true,
+                            is_index: false,
+                            ..column.clone()
+                        }
+                    }
+                }),
+        );
+        columns.push(SqlColumn::new("action", HISTORY_ROW_ACTION_ENUM));
+        columns.push(SqlColumn {
+            is_index: true,
+            ..SqlColumn::new("serial", "SERIAL").nullable()
+        });
+
+        Self::new(&format!("{}_history", self.name), columns)
+    }
+
+    fn get_index_sql(&self, columns: &[String]) -> String {
+        get_index_sql(&self.name, columns)
+    }
+
+    fn to_sql(&self) -> String {
+        let mut definitions: Vec<String> = self.columns.iter().map(SqlColumn::to_sql).collect();
+        let primary_key: Vec<String> = self
+            .columns
+            .iter()
+            .filter(|column| column.is_primary_key)
+            .map(|column| format!("\"{}\"", column.name))
+            .collect();
+        if !primary_key.is_empty() {
+            definitions.push(format!("PRIMARY KEY ({})", primary_key.join(", ")));
+        }
+
+        let mut statements = vec![format!(
+            "CREATE TABLE \"public\".\"{}\" (\n  {}\n);",
+            self.name,
+            definitions.join(",\n  ")
+        )];
+
+        for column in self.columns.iter().filter(|column| column.is_index) {
+            statements.push(self.get_index_sql(std::slice::from_ref(&column.name)));
+        }
+        for columns in &self.composite_indices {
+            statements.push(self.get_index_sql(columns));
+        }
+
+        for index in &self.full_text_indices {
+            let document = index
+                .columns
+                .iter()
+                .map(|column| format!("coalesce(\"{column}\", '')"))
+                .collect::<Vec<_>>()
+                .join(" || ' ' || ");
+            let (table, name, language) = (&self.name, &index.name, &index.language);
+            statements.push(format!(
+                "ALTER TABLE \"public\".\"{table}\" ADD COLUMN \"{name}\" tsvector GENERATED \
+                 ALWAYS AS (to_tsvector('{language}', {document})) STORED;\n\
+                 CREATE INDEX \"{table}_{name}\" ON \"public\".\"{table}\" USING GIN \
+                 (\"{name}\");\n\
+                 CREATE OR REPLACE FUNCTION \"public\".\"{name}\"(search text) RETURNS SETOF \
+                 \"public\".\"{table}\" AS $$\n  SELECT * FROM \"public\".\"{table}\" WHERE \
+                 \"{name}\" @@ websearch_to_tsquery('{language}', search);\n$$ LANGUAGE sql \
+                 STABLE;"
+            ));
+        }
+
+        if let Some(description) = &self.description {
+            statements.push(format!(
+                "COMMENT ON TABLE \"public\".\"{}\" IS '{}';",
+                self.name,
+                escape_sql_string(description)
+            ));
+        }
+        for column in &self.columns {
+            if let Some(description) = &column.description {
+                statements.push(format!(
+                    "COMMENT ON COLUMN \"public\".\"{}\".\"{}\" IS '{}';",
+                    self.name,
+                    column.name,
+                    escape_sql_string(description)
+                ));
+            }
+        }
+
+        statements.join("\n")
+    }
+}
+
+fn escape_sql_string(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+fn get_index_sql(table_name: &str, columns: &[String]) -> String {
+    let quoted_columns = columns
+        .iter()
+        .map(|column| format!("\"{column}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "CREATE INDEX \"{table_name}_{}\" ON \"public\".\"{table_name}\"({quoted_columns});",
+        columns.join("_")
+    )
+}
+
+///The internal tables of the indexer, matching TablesStatic.res in the static
+///codegen templates
+fn get_internal_tables() -> Vec<SqlTable> {
+    vec![
+        SqlTable::new(
+            "event_sync_state",
+            vec![
+                SqlColumn::new("chain_id", "INTEGER").primary_key(),
+                SqlColumn::new("block_number", "INTEGER"),
+                SqlColumn::new("log_index", "INTEGER"),
+                SqlColumn::new("block_timestamp", "INTEGER"),
+                SqlColumn::new("is_pre_registering_dynamic_contracts", "BOOLEAN"),
+            ],
+        ),
+        SqlTable::new(
+            "chain_metadata",
+            vec![
+                SqlColumn::new("chain_id", "INTEGER").primary_key(),
+                SqlColumn::new("start_block", "INTEGER"),
+                SqlColumn::new("end_block", "INTEGER").nullable(),
+                SqlColumn::new("block_height", "INTEGER"),
+                SqlColumn::new("first_event_block_number", "INTEGER").nullable(),
+                SqlColumn::new("latest_processed_block", "INTEGER").nullable(),
+                SqlColumn::new("num_events_processed", "INTEGER").nullable(),
+                SqlColumn::new("is_hyper_sync", "BOOLEAN"),
+                SqlColumn::new("num_batches_fetched", "INTEGER"),
+                SqlColumn::new("latest_fetched_block_number", "INTEGER"),
+                SqlColumn::new(
+                    "timestamp_caught_up_to_head_or_endblock",
+                    "TIMESTAMP WITH TIME ZONE NULL",
+                )
+                .nullable(),
+            ],
+        ),
+        SqlTable::new(
+            "persisted_state",
+            vec![
+                SqlColumn::new("id", "SERIAL").primary_key(),
+                SqlColumn::new("envio_version", "TEXT"),
+                SqlColumn::new("config_hash", "TEXT"),
+                SqlColumn::new("schema_hash", "TEXT"),
+                SqlColumn::new("handler_files_hash", "TEXT"),
+                SqlColumn::new("abi_files_hash", "TEXT"),
+                SqlColumn::new("templates_hash", "TEXT"),
+                SqlColumn::new("db_templates_hash", "TEXT"),
+                SqlColumn::new("hooks_hash", "TEXT"),
+                SqlColumn::new("schema_snapshot", "TEXT"),
+                SqlColumn::new("project_snapshot", "TEXT"),
+            ],
+        ),
+        SqlTable::new(
+            "end_of_block_range_scanned_data",
+            vec![
+                SqlColumn::new("chain_id", "INTEGER").primary_key(),
+                SqlColumn::new("block_timestamp", "INTEGER"),
+                SqlColumn::new("block_number", "INTEGER").primary_key(),
+                SqlColumn::new("block_hash", "TEXT"),
+            ],
+        ),
+        SqlTable::new(
+            "raw_events",
+            vec![
+                SqlColumn::new("chain_id", "INTEGER"),
+                SqlColumn::new("event_id", "NUMERIC"),
+                SqlColumn::new("event_name", "TEXT"),
+                SqlColumn::new("contract_name", "TEXT"),
+                SqlColumn::new("block_number", "INTEGER"),
+                SqlColumn::new("log_index", "INTEGER"),
+                SqlColumn::new("src_address", "TEXT"),
+                SqlColumn::new("block_hash", "TEXT"),
+                SqlColumn::new("block_timestamp", "INTEGER"),
+                SqlColumn::new("block_fields", "JSONB"),
+                SqlColumn::new("transaction_fields", "JSONB"),
+                SqlColumn::new("params", "JSONB"),
+                SqlColumn {
+                    default: Some("CURRENT_TIMESTAMP"),
+                    ..SqlColumn::new("db_write_timestamp", "TIMESTAMP")
+                },
+                SqlColumn::new("serial", "SERIAL").nullable().primary_key(),
+            ],
+        ),
+        SqlTable::new(
+            "raw_events_coverage",
+            vec![
+                SqlColumn::new("chain_id", "INTEGER").primary_key(),
+                SqlColumn::new("start_block", "INTEGER"),
+                SqlColumn::new("end_block", "INTEGER"),
+            ],
+        ),
+        SqlTable::new(
+            DYNAMIC_CONTRACT_REGISTRY_TABLE,
+            vec![
+                SqlColumn::new("id", "TEXT").primary_key(),
+                SqlColumn::new("chain_id", "INTEGER"),
+                SqlColumn::new("registering_event_block_number", "INTEGER"),
+                SqlColumn::new("registering_event_log_index", "INTEGER"),
+                SqlColumn::new("registering_event_block_timestamp", "INTEGER"),
+                SqlColumn::new("registering_event_contract_name", "TEXT"),
+                SqlColumn::new("registering_event_name", "TEXT"),
+                SqlColumn::new("registering_event_src_address", "TEXT"),
+                SqlColumn::new("contract_address", "TEXT"),
+                SqlColumn::new("contract_type", CONTRACT_TYPE_ENUM),
+            ],
+        ),
+    ]
+}
+
+///The db structure the migrations of the indexer create, as plain SQL DDL for
+///reviewing schema changes and applying them with other tooling
+pub struct SqlSchema {
+    enums: Vec<(String, Vec<String>)>,
+    tables: Vec<SqlTable>,
+    ///Indices on the columns derived from fields look up, by table and column
+    derived_from_indices: BTreeSet<(String, String)>,
+}
+
+impl SqlSchema {
+    pub fn from_config(config: &SystemConfig) -> Result<Self> {
+        let schema = &config.schema;
+        let entities = config.get_entities();
+
+        let mut entity_type_values: Vec<String> =
+            entities.iter().map(|entity| entity.name.clone()).collect();
+        entity_type_values.push(DYNAMIC_CONTRACT_REGISTRY_TABLE.to_string());
+
+        let mut enums = vec![
+            (
+                HISTORY_ROW_ACTION_ENUM.to_string(),
+                vec!["SET".to_string(), "DELETE".to_string()],
+            ),
+            (
+                CONTRACT_TYPE_ENUM.to_string(),
+                config
+                    .get_contracts()
+                    .iter()
+                    .map(|contract| contract.name.capitalize())
+                    .collect(),
+            ),
+            (ENTITY_TYPE_ENUM.to_string(), entity_type_values),
+        ];
+        enums.extend(
+            config
+                .get_gql_enums()
+                .iter()
+                .map(|gql_enum| (gql_enum.name.capitalize(), gql_enum.values.clone())),
+        );
+
+        let internal_tables = get_internal_tables();
+        let entity_tables = entities
+            .iter()
+            .map(|entity| {
+                SqlTable::from_entity(entity, schema)
+                    .context(format!("Failed creating table of entity {}", entity.name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        //Dynamic contracts are stored as an entity, so they have a history table too
+        let history_tables: Vec<SqlTable> = entity_tables
+            .iter()
+            .chain(
+                internal_tables
+                    .iter()
+                    .filter(|table| table.name == DYNAMIC_CONTRACT_REGISTRY_TABLE),
+            )
+            .map(SqlTable::to_history_table)
+            .collect();
+
+        let mut derived_from_indices = BTreeSet::new();
+        for entity in &entities {
+            for field in entity.get_fields() {
+                let Some(derived_field) = field.get_derived_from_field(schema) else {
+                    continue;
+                };
+                let column = field.get_relational_key(schema)?;
+                derived_from_indices.insert((derived_field.derived_from_entity, column));
+            }
+        }
+
+        Ok(Self {
+            enums,
+            tables: internal_tables
+                .into_iter()
+                .chain(entity_tables)
+                .chain(history_tables)
+                .collect(),
+            derived_from_indices,
+        })
+    }
+
+    pub fn to_sql(&self) -> String {
+        let mut sections = vec![
+            "-- Generated by envio codegen --emit-sql from the config and schema, do not edit.\n\
+             -- The db migrations of the indexer create the same tables, and also the insert\n\
+             -- functions of the history tables and the Hasura metadata."
+                .to_string(),
+        ];
+
+        let enums = self
+            .enums
+            .iter()
+            .map(|(name, values)| {
+                let values = values
+                    .iter()
+                    .map(|value| format!("'{}'", escape_sql_string(value)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("CREATE TYPE {name} AS ENUM({values});")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        sections.push(enums);
+
+        sections.extend(self.tables.iter().map(SqlTable::to_sql));
+
+        //Indices that already exist on a table aren't created twice
+        let existing_indices: BTreeSet<String> = self.tables.iter().map(SqlTable::to_sql).collect();
+        let derived_from_indices: Vec<String> = self
+            .derived_from_indices
+            .iter()
+            .map(|(table, column)| get_index_sql(table, std::slice::from_ref(column)))
+            .filter(|index| {
+                !existing_indices
+                    .iter()
+                    .any(|table_sql| table_sql.contains(index))
+            })
+            .collect();
+        if !derived_from_indices.is_empty() {
+            sections.push(derived_from_indices.join("\n"));
+        }
+
+        format!("{}\n", sections.join("\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        get_internal_tables, SqlColumn, SqlTable, CONTRACT_TYPE_ENUM, HISTORY_ROW_ACTION_ENUM,
+    };
+
+    const TABLE_RES: &str = include_str!("../../npm/envio/src/db/Table.res");
+    const ENTITY_HISTORY_RES: &str = include_str!("../../npm/envio/src/db/EntityHistory.res");
+    const TABLES_STATIC_RES: &str =
+        include_str!("../../templates/static/codegen/src/db/TablesStatic.res");
+
+    ///Gets the sql type of a Table.fieldType variant from its @as attribute
+    fn get_res_sql_type(res_type: &str) -> String {
+        match res_type.strip_prefix("Custom(") {
+            Some("Enums.ContractType.enum.name)") => CONTRACT_TYPE_ENUM.to_string(),
+            Some("RowAction.enum.name)") => HISTORY_ROW_ACTION_ENUM.to_string(),
+            Some(enum_name) => panic!("Unknown enum {enum_name}"),
+            None => TABLE_RES
+                .lines()
+                .find_map(|line| {
+                    line.trim()
+                        .strip_prefix("| @as(\"")?
+                        .strip_suffix(&format!("\") {res_type}"))
+                })
+                .unwrap_or_else(|| panic!("Unknown field type {res_type}"))
+                .to_string(),
+        }
+    }
+
+    ///Parses a single line mkField call. Names bound with let in the source are
+    ///resolved, other variables are kept as they are.
+    fn parse_res_field(source: &'static str, line: &'static str) -> Option<SqlColumn> {
+        let (_, args) = line.split_once("mkField(")?;
+        let (args, _) = args.rsplit_once(')')?;
+        let mut args = args.split(", ");
+
+        let name = args.next()?;
+        let name = match name.strip_prefix('"') {
+            Some(name) => name.trim_end_matches('"'),
+            None => source
+                .lines()
+                .find_map(|line| line.trim().strip_prefix(&format!("let {name} = \"")))
+                .map_or(name, |value| value.trim_end_matches('"')),
+        };
+        let mut column = SqlColumn::new(name, &get_res_sql_type(args.next()?));
+
+        for arg in args {
+            let arg = arg.trim_start_matches('~');
+            let (flag, value) = arg.split_once('=').unwrap_or((arg, "true"));
+            match flag {
+                "isNullable" => column.is_nullable = value == "true",
+                "isPrimaryKey" => column.is_primary_key = value == "true",
+                "isIndex" => column.is_index = value == "true",
+                "default" => column.default = Some(value.trim_matches('"')),
+                flag => panic!("Unknown mkField argument {flag}"),
+            }
+        }
+
+        Some(column)
+    }
+
+    #[test]
+    fn internal_tables_match_tables_static() {
+        let mut tables_static = vec![];
+        let mut lines = TABLES_STATIC_RES.lines();
+        while let Some(line) = lines.next() {
+            if !line.contains("mkTable(") {
+                continue;
+            }
+            let name = lines.next().unwrap().trim().trim_end_matches(',');
+            let columns = lines
+                .by_ref()
+                .take_while(|line| line.trim() != "],")
+                .filter_map(|line| parse_res_field(TABLES_STATIC_RES, line))
+                .collect();
+            tables_static.push(SqlTable::new(name.trim_matches('"'), columns));
+        }
+
+        assert_eq!(get_internal_tables(), tables_static);
+    }
+
+    #[test]
+    fn history_table_matches_entity_history() {
+        let (_, from_table) = ENTITY_HISTORY_RES
+            .split_once("let fromTable = ")
+            .expect("EntityHistory.fromTable should exist");
+        let current_field_names: Vec<_> = from_table
+            .lines()
+            .take_while(|line| !line.contains("let currentChangeFieldNames"))
+            .filter_map(|line| line.trim().strip_prefix("let ")?.split_once(" = \""))
+            .map(|(_, name)| name.trim_end_matches('"'))
+            .collect();
+        let fields: Vec<_> = from_table
+            .lines()
+            .filter_map(|line| parse_res_field(ENTITY_HISTORY_RES, line))
+            .collect();
+        let [current_field, previous_field, action_field, serial_field] = fields.as_slice() else {
+            panic!("Unexpected fields of EntityHistory.fromTable: {fields:?}");
+        };
+        assert!(from_table.contains("\"previous_\" ++ fieldName"));
+        assert!(ENTITY_HISTORY_RES.contains(&format!("let name = \"{HISTORY_ROW_ACTION_ENUM}\"")));
+
+        let table = SqlTable::new(
+            "Gravatar",
+            vec![
+                SqlColumn::new("id", "TEXT").primary_key(),
+                SqlColumn {
+                    is_index: true,
+                    ..SqlColumn::new("owner_id", "TEXT")
+                },
+                SqlColumn {
+                    default: Some("CURRENT_TIMESTAMP"),
+                    ..SqlColumn::new("db_write_timestamp", "TIMESTAMP")
+                },
+            ],
+        );
+        let mut expected_columns: Vec<_> = current_field_names
+            .iter()
+            .map(|name| SqlColumn::new(name, &current_field.sql_type).primary_key())
+            .collect();
+        expected_columns.extend(current_field_names.iter().map(|name| SqlColumn {
+            name: format!("previous_{name}"),
+            ..previous_field.clone()
+        }));
+        //Entity data fields are nullable and unindexed, apart from the id in the primary key
+        expected_columns.push(SqlColumn::new("id", "TEXT").primary_key());
+        expected_columns.push(SqlColumn::new("owner_id", "TEXT").nullable());
+        expected_columns.push(action_field.clone());
+        expected_columns.push(serial_field.clone());
+
+        assert!(current_field.is_primary_key);
+        assert_eq!(
+            table.to_history_table(),
+            SqlTable::new("Gravatar_history", expected_columns)
+        );
+    }
+
+    #[test]
+    fn table_to_sql() {
+        let mut table = SqlTable::new(
+            "Gravatar",
+            vec![
+                SqlColumn::new("id", "TEXT").primary_key(),
+                SqlColumn {
+                    is_index: true,
+                    description: Some("The owner's address".to_string()),
+                    ..SqlColumn::new("owner_id", "TEXT")
+                },
+                SqlColumn {
+                    is_array: true,
+                    ..SqlColumn::new("sizes", "INTEGER").nullable()
+                },
+            ],
+        );
+        table.composite_indices = vec![vec!["owner_id".to_string(), "sizes".to_string()]];
+
+        assert_eq!(
+            table.to_sql(),
+            r#"CREATE TABLE "public"."Gravatar" (
+  "id" TEXT NOT NULL,
+  "owner_id" TEXT NOT NULL,
+  "sizes" INTEGER[],
+  PRIMARY KEY ("id")
+);
+CREATE INDEX "Gravatar_owner_id" ON "public"."Gravatar"("owner_id");
+CREATE INDEX "Gravatar_owner_id_sizes" ON "public"."Gravatar"("owner_id", "sizes");
+COMMENT ON COLUMN "public"."Gravatar"."owner_id" IS 'The owner''s address';"#
+        );
+
+        let history_table = table.to_history_table();
+        assert_eq!(history_table.name, "Gravatar_history");
+        assert_eq!(
+            history_table
+                .columns
+                .iter()
+                .filter(|column| column.is_primary_key)
+                .map(|column| column.name.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "entity_history_block_timestamp",
+                "entity_history_chain_id",
+                "entity_history_block_number",
+                "entity_history_log_index",
+                "id"
+            ]
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use super::{
     chain_helpers::get_confirmed_block_threshold_from_id,
-    entity_parsing::{Entity, GraphQLEnum, Schema},
+    entity_parsing::{Entity, GraphQLEnum, Interface, Schema, Union},
+    event_parsing::abi_to_rescript_type,
     human_config::{
         self,
         evm::{
@@ -8,6 +9,7 @@ use super::{
             Network as EvmNetwork,
         },
         fuel::{EventConfig as FuelEventConfig, HumanConfig as FuelConfig},
+        ValidatorFormat,
     },
     hypersync_endpoints,
     validation::{self, validate_names_valid_rescript},
@@ -17,7 +19,7 @@ use crate::{
     constants::{links, project_paths::DEFAULT_SCHEMA_PATH},
     fuel::abi::{FuelAbi, BURN_EVENT_NAME, CALL_EVENT_NAME, MINT_EVENT_NAME, TRANSFER_EVENT_NAME},
     project_paths::{path_utils, ParsedProjectPaths},
-    rescript_types::RescriptTypeIdent,
+    rescript_types::{RescriptRecordField, RescriptTypeExpr, RescriptTypeIdent},
     utils::unique_hashmap,
 };
 use anyhow::{anyhow, Context, Result};
@@ -59,6 +61,24 @@ pub struct SystemConfig {
     pub schema: Schema,
     pub field_selection: FieldSelection,
     pub enable_raw_events: bool,
+    pub enable_hasura: bool,
+    ///Whether to emit the typed TypeScript client of the Hasura api
+    pub enable_graphql_client: bool,
+    ///Formats of the event params and entity validators to emit
+    pub validator_formats: Vec<ValidatorFormat>,
+    pub local_services: LocalServices,
+    pub hooks: CodegenHooks,
+}
+
+///The client only queries the Hasura api, so it can't be generated without Hasura
+fn get_enable_graphql_client(graphql_client: Option<bool>, enable_hasura: bool) -> Result<bool> {
+    match graphql_client {
+        Some(true) if !enable_hasura => Err(anyhow!(
+            "EE115: graphql_client can't be enabled with hasura: false since the client \
+             queries the Hasura api"
+        )),
+        graphql_client => Ok(graphql_client.unwrap_or(false)),
+    }
 }
 
 //Getter methods for system config
@@ -115,6 +135,20 @@ impl SystemConfig {
         enums
     }
 
+    pub fn get_gql_interfaces(&self) -> Vec<&Interface> {
+        let mut interfaces: Vec<&Interface> = self.schema.interfaces.values().collect();
+        //For consistent templating in alphabetical order
+        interfaces.sort_by_key(|i| i.name.clone());
+        interfaces
+    }
+
+    pub fn get_gql_unions(&self) -> Vec<&Union> {
+        let mut unions: Vec<&Union> = self.schema.unions.values().collect();
+        //For consistent templating in alphabetical order
+        unions.sort_by_key(|u| u.name.clone());
+        unions
+    }
+
     pub fn get_gql_enum_names_set(&self) -> HashSet<EntityKey> {
         self.schema.enums.keys().cloned().collect()
     }
@@ -309,6 +343,22 @@ impl SystemConfig {
             schema,
             field_selection,
             enable_raw_events: evm_config.raw_events.unwrap_or(false),
+            enable_hasura: evm_config.hasura.unwrap_or(true),
+            enable_graphql_client: get_enable_graphql_client(
+                evm_config.graphql_client,
+                evm_config.hasura.unwrap_or(true),
+            )?,
+            validator_formats: evm_config
+                .validators
+                .unwrap_or_default()
+                .into_iter()
+                .unique()
+                .collect(),
+            local_services: LocalServices::from_config(
+                &evm_config.name,
+                evm_config.local_services,
+            )?,
+            hooks: CodegenHooks::from_config(evm_config.hooks),
         })
     }
 
@@ -444,6 +494,22 @@ impl SystemConfig {
             schema,
             field_selection: FieldSelection::fuel(),
             enable_raw_events: fuel_config.raw_events.unwrap_or(false),
+            enable_hasura: fuel_config.hasura.unwrap_or(true),
+            enable_graphql_client: get_enable_graphql_client(
+                fuel_config.graphql_client,
+                fuel_config.hasura.unwrap_or(true),
+            )?,
+            validator_formats: fuel_config
+                .validators
+                .unwrap_or_default()
+                .into_iter()
+                .unique()
+                .collect(),
+            local_services: LocalServices::from_config(
+                &fuel_config.name,
+                fuel_config.local_services,
+            )?,
+            hooks: CodegenHooks::from_config(fuel_config.hooks),
         })
     }
 
@@ -771,7 +837,7 @@ pub enum Abi {
 }
 
 impl Abi {
-    fn get_path(&self) -> Option<PathBuf> {
+    pub fn get_path(&self) -> Option<PathBuf> {
         match self {
             Abi::Evm(abi) => abi.path.clone(),
             Abi::Fuel(abi) => Some(abi.path_buf.clone()),
@@ -935,6 +1001,12 @@ impl Event {
             })
         }
 
+        //Events are keyed by name in a sorted map, overloads of the same name are sorted
+        //too so that the serialized abi doesn't depend on the order of the config
+        for overloaded_events in events_abi.events.values_mut() {
+            overloaded_events.sort_by_key(|event| event.abi_signature());
+        }
+
         let events_abi_raw = serde_json::to_string(&events_abi)
             .context("Failed serializing ABI from filtered events")?;
 
@@ -1040,6 +1112,41 @@ impl Event {
         // TODO: Clean up fuel_abi to include only relevant events
         Ok((events, fuel_abi))
     }
+
+    ///Gets the type of the event params passed to handlers. None for logged data, whose
+    ///types are only declared in the ReScript code generated from the Fuel abi.
+    pub fn get_params_type_expr(&self) -> Option<RescriptTypeExpr> {
+        match &self.kind {
+            EventKind::Params(params) if params.is_empty() => {
+                Some(RescriptTypeIdent::Unit.to_expr())
+            }
+            EventKind::Params(params) => Some(RescriptTypeExpr::Record(
+                params
+                    .iter()
+                    .map(|param| {
+                        RescriptRecordField::new(
+                            param.name.clone(),
+                            abi_to_rescript_type(&param.into()),
+                        )
+                    })
+                    .collect(),
+            )),
+            EventKind::Fuel(FuelEventKind::Mint | FuelEventKind::Burn) => {
+                Some(RescriptTypeExpr::Record(vec![
+                    RescriptRecordField::new("subId".to_string(), RescriptTypeIdent::String),
+                    RescriptRecordField::new("amount".to_string(), RescriptTypeIdent::BigInt),
+                ]))
+            }
+            EventKind::Fuel(FuelEventKind::Transfer | FuelEventKind::Call) => {
+                Some(RescriptTypeExpr::Record(vec![
+                    RescriptRecordField::new("to".to_string(), RescriptTypeIdent::Address),
+                    RescriptRecordField::new("assetId".to_string(), RescriptTypeIdent::String),
+                    RescriptRecordField::new("amount".to_string(), RescriptTypeIdent::BigInt),
+                ]))
+            }
+            EventKind::Fuel(FuelEventKind::LogData(_)) => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1268,6 +1375,124 @@ impl FieldSelection {
     }
 }
 
+///Ports and docker compose project of the Postgres and Hasura started by envio dev
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalServices {
+    pub compose_project_name: String,
+    pub postgres_port: u16,
+    pub hasura_port: u16,
+}
+
+impl LocalServices {
+    const DEFAULT_POSTGRES_PORT: u16 = 5433;
+    const DEFAULT_HASURA_PORT: u16 = 8080;
+    ///Derived ports start from these and stay clear of the ports of common dev tools
+    const DERIVED_POSTGRES_PORT_BASE: u16 = 15433;
+    const DERIVED_HASURA_PORT_BASE: u16 = 18080;
+    const NUM_DERIVED_PORTS: u16 = 1000;
+
+    ///Docker compose only accepts lowercase letters, digits, dashes and underscores,
+    ///starting with a letter or digit
+    fn is_valid_compose_project_name(name: &str) -> bool {
+        name.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    }
+
+    fn derive_compose_project_name(project_name: &str) -> String {
+        let name = project_name
+            .to_lowercase()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect::<String>();
+        let name = name.trim_start_matches(['-', '_']);
+        if name.is_empty() {
+            "envio".to_string()
+        } else {
+            name.to_string()
+        }
+    }
+
+    ///Offset of the derived ports, which is stable across envio versions unlike the
+    ///std hasher
+    fn derive_port_offset(project_name: &str) -> u16 {
+        use sha2::{Digest, Sha256};
+        let hash = Sha256::digest(project_name.as_bytes());
+        u16::from_be_bytes([hash[0], hash[1]]) % Self::NUM_DERIVED_PORTS
+    }
+
+    pub fn from_config(
+        project_name: &str,
+        local_services: Option<human_config::LocalServices>,
+    ) -> Result<Self> {
+        let human_config::LocalServices {
+            compose_project_name,
+            postgres_port,
+            hasura_port,
+            derive_ports,
+        } = local_services.unwrap_or(human_config::LocalServices {
+            compose_project_name: None,
+            postgres_port: None,
+            hasura_port: None,
+            derive_ports: None,
+        });
+
+        let compose_project_name = match compose_project_name {
+            Some(name) if !Self::is_valid_compose_project_name(&name) => {
+                return Err(anyhow!(
+                    "EE113: Invalid compose_project_name '{name}'. It can only contain lowercase \
+                     letters, digits, dashes and underscores, and has to start with a letter or \
+                     digit"
+                ))
+            }
+            Some(name) => name,
+            None => Self::derive_compose_project_name(project_name),
+        };
+
+        let (default_postgres_port, default_hasura_port) = if derive_ports.unwrap_or(false) {
+            let offset = Self::derive_port_offset(project_name);
+            (
+                Self::DERIVED_POSTGRES_PORT_BASE + offset,
+                Self::DERIVED_HASURA_PORT_BASE + offset,
+            )
+        } else {
+            (Self::DEFAULT_POSTGRES_PORT, Self::DEFAULT_HASURA_PORT)
+        };
+
+        Ok(Self {
+            compose_project_name,
+            postgres_port: postgres_port.unwrap_or(default_postgres_port),
+            hasura_port: hasura_port.unwrap_or(default_hasura_port),
+        })
+    }
+}
+
+///Shell commands run in the project root around codegen
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CodegenHooks {
+    pub pre_codegen: Vec<String>,
+    pub post_codegen: Vec<String>,
+}
+
+impl CodegenHooks {
+    fn from_config(hooks: Option<human_config::Hooks>) -> Self {
+        match hooks {
+            None => Self::default(),
+            Some(hooks) => Self {
+                pre_codegen: hooks.pre_codegen.unwrap_or_default(),
+                post_codegen: hooks.post_codegen.unwrap_or_default(),
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::path::PathBuf;
@@ -1275,7 +1500,7 @@ mod test {
     use super::SystemConfig;
     use crate::{
         config_parsing::{
-            human_config::evm::HumanConfig as EvmConfig,
+            human_config::evm::{EventConfig as EvmEventConfig, HumanConfig as EvmConfig},
             system_config::{Event, SyncConfig, SyncSource},
         },
         project_paths::ParsedProjectPaths,
@@ -1535,4 +1760,77 @@ mod test {
             _ => panic!("Expected HypersyncConfig"),
         }
     }
+
+    #[test]
+    fn local_services_defaults_and_derived_ports() {
+        use super::LocalServices;
+        use crate::config_parsing::human_config;
+
+        assert_eq!(
+            LocalServices::from_config("My Indexer", None).unwrap(),
+            LocalServices {
+                compose_project_name: "my-indexer".to_string(),
+                postgres_port: 5433,
+                hasura_port: 8080,
+            }
+        );
+
+        let derive_ports = |project_name| {
+            LocalServices::from_config(
+                project_name,
+                Some(human_config::LocalServices {
+                    compose_project_name: None,
+                    postgres_port: None,
+                    hasura_port: Some(9000),
+                    derive_ports: Some(true),
+                }),
+            )
+            .unwrap()
+        };
+        let local_services = derive_ports("indexer_a");
+        assert_eq!(local_services, derive_ports("indexer_a"));
+        assert_ne!(
+            local_services.postgres_port,
+            derive_ports("indexer_b").postgres_port
+        );
+        assert!((15433..16433).contains(&local_services.postgres_port));
+        assert_eq!(local_services.hasura_port, 9000);
+
+        let invalid_name = LocalServices::from_config(
+            "indexer",
+            Some(human_config::LocalServices {
+                compose_project_name: Some("My Stack".to_string()),
+                postgres_port: None,
+                hasura_port: None,
+                derive_ports: None,
+            }),
+        );
+        assert!(invalid_name.is_err());
+    }
+
+    #[test]
+    fn evm_events_abi_is_independent_of_config_order() {
+        let get_abi_raw = |events: Vec<&str>| {
+            let events_config = events
+                .into_iter()
+                .map(|event| EvmEventConfig {
+                    event: event.to_string(),
+                    name: None,
+                })
+                .collect();
+            let (_, evm_abi) =
+                Event::from_evm_events_config(events_config, &None, &ParsedProjectPaths::default())
+                    .expect("Failed parsing events");
+            evm_abi.raw
+        };
+
+        let events = vec![
+            "Transfer(address indexed from, address indexed to, uint256 value)",
+            "Transfer(address indexed from, address indexed to, uint256 indexed tokenId, bool)",
+            "Approval(address indexed owner, address indexed spender, uint256 value)",
+        ];
+        let reversed_events = events.iter().rev().cloned().collect();
+
+        assert_eq!(get_abi_raw(events), get_abi_raw(reversed_events));
+    }
 }
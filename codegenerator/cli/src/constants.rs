@@ -5,6 +5,10 @@ pub mod project_paths {
     pub const DEFAULT_GENERATED_PATH: &str = "generated";
     pub const DEFAULT_CONFIG_PATH: &str = "config.yaml";
     pub const DEFAULT_SCHEMA_PATH: &str = "schema.graphql";
+    ///Project local templates that shadow the embedded codegen templates
+    pub const TEMPLATE_OVERRIDES_PATH: &str = "templates";
+    ///Dir within the template overrides with handlebars partials
+    pub const TEMPLATE_PARTIALS_PATH: &str = "partials";
 }
 
 pub mod links {
@@ -171,4 +175,13 @@ pub mod reserved_keywords {
     ];
 
     pub const ENVIO_INTERNAL_RESERVED_POSTGRES_TYPES: &[&str] = &["EVENT_TYPE", "CONTRACT_TYPE"];
+
+    pub const ENVIO_INTERNAL_RESERVED_POSTGRES_TABLES: &[&str] = &[
+        "event_sync_state",
+        "chain_metadata",
+        "persisted_state",
+        "end_of_block_range_scanned_data",
+        "raw_events",
+        "dynamic_contract_registry",
+    ];
 }
@@ -1,12 +1,57 @@
 use crate::{
+    cli_args::clap_definitions::{PackageManager, PostCodegenArgs},
     commands,
-    config_parsing::system_config::SystemConfig,
-    persisted_state::{PersistedStateExists, CURRENT_CRATE_VERSION},
+    config_parsing::{sql_schema::SqlSchema, system_config::SystemConfig},
+    hbs_templating::codegen_templates::ProjectTemplate,
+    hbs_templating::{
+        graphql_client_templates::GRAPHQL_CLIENT_DIR,
+        validators_templates::get_disabled_output_paths,
+    },
+    persisted_state::{
+        CodegenScope, PersistedState, PersistedStateExists, StateField, CURRENT_CRATE_VERSION,
+        PERSISTED_STATE_FILE_NAME,
+    },
     project_paths::ParsedProjectPaths,
+    template_dirs::{TemplateDirs, TemplateOverrides},
 };
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+fn get_sql_schema(config: &SystemConfig) -> Result<String> {
+    Ok(SqlSchema::from_config(config)
+        .context("Failed creating sql schema")?
+        .to_sql())
+}
 
-pub async fn run_codegen(project_paths: &ParsedProjectPaths) -> Result<()> {
+///Runs the pre codegen hooks of the config and parses it again, since the hooks can
+///write the abis and schema it reads. Hashing the state of the returned config
+///includes the hook outputs.
+pub async fn run_pre_codegen_hooks(
+    config: SystemConfig,
+    project_paths: &ParsedProjectPaths,
+) -> Result<SystemConfig> {
+    if config.hooks.pre_codegen.is_empty() {
+        return Ok(config);
+    }
+
+    commands::hooks::run_hook_commands("pre_codegen", &config.hooks.pre_codegen, project_paths)
+        .await?;
+    SystemConfig::parse_from_project_files(project_paths)
+        .context("Failed parsing config after running the pre_codegen hooks")
+}
+
+pub async fn run_codegen(
+    project_paths: &ParsedProjectPaths,
+    post_codegen: &PostCodegenArgs,
+    package_manager: PackageManager,
+    emit_sql_path: Option<&str>,
+) -> Result<()> {
     //Manage purging of gengerated folder
     match PersistedStateExists::get_persisted_state_file(&project_paths) {
         PersistedStateExists::Exists(ps) if &ps.envio_version != CURRENT_CRATE_VERSION => {
@@ -25,8 +70,229 @@ pub async fn run_codegen(project_paths: &ParsedProjectPaths) -> Result<()> {
 
     let config =
         SystemConfig::parse_from_project_files(project_paths).context("Failed parsing config")?;
+    let config = run_pre_codegen_hooks(config, project_paths).await?;
+
+    if let Some(emit_sql_path) = emit_sql_path {
+        let path = project_paths.project_root.join(emit_sql_path);
+        fs::write(&path, get_sql_schema(&config)?)
+            .context(format!("Failed writing sql schema to {}", path.display()))?;
+        println!("Wrote sql schema to {}", path.display());
+    }
 
-    commands::codegen::run_codegen(&config, project_paths).await?;
+    commands::codegen::run_codegen(
+        &config,
+        project_paths,
+        &CodegenScope::Full,
+        post_codegen,
+        package_manager,
+    )
+    .await?;
 
     Ok(())
 }
+
+///A file in the generated dir that doesn't match what codegen would write
+#[derive(Debug, PartialEq)]
+enum DriftedFile {
+    Missing(PathBuf),
+    Changed(PathBuf),
+    ///Left from a disabled output, codegen removes it
+    Stale(PathBuf),
+    ///The persisted state file is of other inputs than the current ones
+    Outdated(PathBuf, Vec<StateField>),
+}
+
+impl Display for DriftedFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing(path) => write!(f, "missing: {}", path.display()),
+            Self::Changed(path) => write!(f, "changed: {}", path.display()),
+            Self::Stale(path) => write!(f, "stale: {}", path.display()),
+            Self::Outdated(path, fields) => write!(
+                f,
+                "outdated: {} ({})",
+                path.display(),
+                fields.iter().map(|field| field.to_string()).join(", ")
+            ),
+        }
+    }
+}
+
+///Compares the expected files, keyed by their path relative to the generated dir,
+///with the files in the generated dir. Files that codegen doesn't write, like the
+///build output and installed packages, are ignored.
+fn get_drifted_files(
+    expected_files: &BTreeMap<PathBuf, Vec<u8>>,
+    generated_dir: &Path,
+) -> Vec<DriftedFile> {
+    expected_files
+        .iter()
+        .filter_map(
+            |(path, expected_contents)| match fs::read(generated_dir.join(path)) {
+                Err(_) => Some(DriftedFile::Missing(path.clone())),
+                Ok(contents) if &contents != expected_contents => {
+                    Some(DriftedFile::Changed(path.clone()))
+                }
+                Ok(_) => None,
+            },
+        )
+        .collect()
+}
+
+///Compares the persisted state file with the current state. Only the inputs of the
+///generated code count, the file also holds hashes of the handlers and the
+///individual abi and handler files that don't change what is generated.
+fn get_drifted_persisted_state(
+    current_state: &PersistedState,
+    project_paths: &ParsedProjectPaths,
+) -> Option<DriftedFile> {
+    let path = PathBuf::from(PERSISTED_STATE_FILE_NAME);
+    match PersistedStateExists::get_persisted_state_file(project_paths) {
+        PersistedStateExists::NotExists => Some(DriftedFile::Missing(path)),
+        PersistedStateExists::Corrupted(_) => Some(DriftedFile::Changed(path)),
+        PersistedStateExists::Exists(persisted_state) => {
+            match current_state.get_changed_codegen_inputs(&persisted_state) {
+                changed_fields if changed_fields.is_empty() => None,
+                changed_fields => Some(DriftedFile::Outdated(path, changed_fields)),
+            }
+        }
+    }
+}
+
+///Gets the files of disabled outputs that are still in the generated dir
+fn get_stale_files(config: &SystemConfig, generated_dir: &Path) -> Vec<DriftedFile> {
+    let mut disabled_paths = get_disabled_output_paths(&config.validator_formats);
+    if !config.enable_graphql_client {
+        disabled_paths.push(PathBuf::from(GRAPHQL_CLIENT_DIR));
+    }
+    disabled_paths
+        .into_iter()
+        .filter(|path| generated_dir.join(path).exists())
+        .map(DriftedFile::Stale)
+        .collect()
+}
+
+///Renders the generated code in memory and fails if it differs from the generated
+///dir. Never writes files or runs the package install and build.
+pub fn run_codegen_check(
+    project_paths: &ParsedProjectPaths,
+    emit_sql_path: Option<&str>,
+) -> Result<()> {
+    let config =
+        SystemConfig::parse_from_project_files(project_paths).context("Failed parsing config")?;
+    //The check never writes files, so the hooks aren't run
+    if !config.hooks.post_codegen.is_empty() {
+        println!(
+            "WARNING: The post_codegen hooks aren't run by the check, generated files they \
+             change are reported as drifted"
+        );
+    }
+    let template = ProjectTemplate::from_config(&config, project_paths)
+        .context("Failed creating project template")?;
+
+    let mut expected_files = BTreeMap::new();
+    let overrides = TemplateOverrides::new(&project_paths.project_root);
+    for (path, contents) in TemplateDirs::new()
+        .get_codegen_static_dir()?
+        .get_files_with_overrides(&overrides)?
+    {
+        expected_files.insert(path, contents);
+    }
+    //Dynamic files are written after the static ones, so they take precedence
+    for (path, contents) in template
+        .render_templates(project_paths)
+        .context("Failed rendering dynamic codegen files")?
+    {
+        expected_files.insert(path, contents.into_bytes());
+    }
+    expected_files.remove(Path::new(PERSISTED_STATE_FILE_NAME));
+
+    let current_state =
+        PersistedState::get_current_state(&config).context("Failed getting current state")?;
+    let mut drifted_files = get_drifted_files(&expected_files, &project_paths.generated);
+    drifted_files.extend(get_drifted_persisted_state(&current_state, project_paths));
+    drifted_files.extend(get_stale_files(&config, &project_paths.generated));
+    //The sql schema is written relative to the project root rather than the generated dir
+    if let Some(emit_sql_path) = emit_sql_path {
+        let expected_sql = BTreeMap::from([(
+            PathBuf::from(emit_sql_path),
+            get_sql_schema(&config)?.into_bytes(),
+        )]);
+        drifted_files.extend(get_drifted_files(
+            &expected_sql,
+            &project_paths.project_root,
+        ));
+    }
+    if drifted_files.is_empty() {
+        println!("Generated code is up to date");
+        return Ok(());
+    }
+
+    println!("Generated code doesn't match the config, schema and abis:");
+    for drifted_file in &drifted_files {
+        println!("  {drifted_file}");
+    }
+    Err(anyhow!(
+        "Generated code is out of date with {} drifted files, run envio codegen to update it",
+        drifted_files.len()
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{get_drifted_files, get_stale_files, DriftedFile};
+    use crate::{
+        config_parsing::{human_config::ValidatorFormat, system_config::SystemConfig},
+        project_paths::ParsedProjectPaths,
+    };
+    use std::{collections::BTreeMap, fs, path::PathBuf};
+    use tempdir::TempDir;
+
+    #[test]
+    fn detects_outputs_left_from_disabled_options() {
+        let project_root = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let project_paths =
+            ParsedProjectPaths::new(&project_root, "generated", "configs/config1.yaml").unwrap();
+        let mut config = SystemConfig::parse_from_project_files(&project_paths).unwrap();
+        config.enable_graphql_client = false;
+        config.validator_formats = vec![ValidatorFormat::JsonSchema];
+
+        let dir = TempDir::new("codegen_check").unwrap();
+        fs::create_dir_all(dir.path().join("graphql-client")).unwrap();
+        fs::create_dir_all(dir.path().join("validators")).unwrap();
+        fs::write(dir.path().join("validators/schema.json"), "{}").unwrap();
+        fs::write(dir.path().join("validators/zod.ts"), "").unwrap();
+
+        assert_eq!(
+            get_stale_files(&config, dir.path()),
+            vec![
+                DriftedFile::Stale(PathBuf::from("validators/zod.ts")),
+                DriftedFile::Stale(PathBuf::from("graphql-client")),
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_missing_and_changed_files() {
+        let dir = TempDir::new("codegen_check").unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/Types.res"), "type t = int").unwrap();
+        fs::write(dir.path().join("persisted_state.envio.json"), "{}").unwrap();
+        //Build output isn't written by codegen and shouldn't count as drift
+        fs::write(dir.path().join("src/Types.bs.js"), "").unwrap();
+
+        let expected_files = BTreeMap::from([
+            (PathBuf::from("persisted_state.envio.json"), b"{}".to_vec()),
+            (PathBuf::from("src/Handlers.res"), b"".to_vec()),
+            (PathBuf::from("src/Types.res"), b"type t = string".to_vec()),
+        ]);
+
+        assert_eq!(
+            get_drifted_files(&expected_files, dir.path()),
+            vec![
+                DriftedFile::Missing(PathBuf::from("src/Handlers.res")),
+                DriftedFile::Changed(PathBuf::from("src/Types.res")),
+            ]
+        );
+    }
+}
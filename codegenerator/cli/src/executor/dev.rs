@@ -1,161 +1,669 @@
+use super::codegen;
 use crate::{
+    cli_args::clap_definitions::{PackageManager, PostCodegenArgs},
     commands,
     config_parsing::system_config::SystemConfig,
-    persisted_state::{self, PersistedState, PersistedStateExists, CURRENT_CRATE_VERSION},
+    dev_services::{DevServices, PostgresSettings},
+    persisted_state::{
+        self, CodegenScope, PersistedState, PersistedStateExists, RawEventsCoverage,
+        CURRENT_CRATE_VERSION,
+    },
     project_paths::ParsedProjectPaths,
     service_health::{self, EndpointHealth},
+    template_dirs::TemplateOverrides,
+    utils::file_watcher::FileWatcher,
 };
 use anyhow::{anyhow, Context, Result};
+use inquire::Confirm;
+use std::{path::PathBuf, time::Duration};
 
-pub async fn run_dev(project_paths: ParsedProjectPaths) -> Result<()> {
+///How often the watched files are checked for changes
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(300);
+///How long the watched files need to stay unchanged before rerunning
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+pub async fn run_dev(
+    project_paths: ParsedProjectPaths,
+    should_watch: bool,
+    should_replay_raw_events: bool,
+    post_codegen: &PostCodegenArgs,
+    package_manager: PackageManager,
+) -> Result<()> {
+    let (config, current_state) = get_current_state(&project_paths).await?;
+
+    run_codegen_if_changed(
+        &project_paths,
+        &config,
+        &current_state,
+        post_codegen,
+        package_manager,
+    )
+    .await?;
+
+    let dev_services = DevServices::from_env(&config)?;
+    let hasura_console_url = start_services(
+        &project_paths,
+        &dev_services,
+        &config.local_services.compose_project_name,
+    )
+    .await?;
+
+    let should_sync_from_raw_events = run_db_migrations_if_changed(
+        &project_paths,
+        &config,
+        &current_state,
+        should_replay_raw_events,
+        package_manager,
+    )
+    .await?;
+
+    if should_sync_from_raw_events {
+        println!("Resyncing from raw_events");
+    }
+
+    if should_watch {
+        return watch(
+            &project_paths,
+            config,
+            should_sync_from_raw_events,
+            hasura_console_url,
+            should_replay_raw_events,
+            post_codegen,
+            package_manager,
+        )
+        .await;
+    }
+
+    println!("Starting indexer");
+
+    commands::start::start_indexer(
+        &project_paths,
+        should_sync_from_raw_events,
+        hasura_console_url.as_deref(),
+    )
+    .await
+    .context("Failed running start on the indexer")?;
+
+    Ok(())
+}
+
+async fn get_current_state(
+    project_paths: &ParsedProjectPaths,
+) -> Result<(SystemConfig, PersistedState)> {
     let config =
-        SystemConfig::parse_from_project_files(&project_paths).context("Failed parsing config")?;
+        SystemConfig::parse_from_project_files(project_paths).context("Failed parsing config")?;
+    let current_state = PersistedState::get_current_state(&config)
+        .context("Failed getting current indexer state")?;
+
+    //The pre_codegen hooks only run along with codegen. The state is hashed again after
+    //they ran so that changes to the files they write are detected.
+    if config.hooks.pre_codegen.is_empty()
+        || !CodegenChanges::new(project_paths, &current_state).should_run_codegen()
+    {
+        return Ok((config, current_state));
+    }
 
+    let config = codegen::run_pre_codegen_hooks(config, project_paths).await?;
     let current_state = PersistedState::get_current_state(&config)
         .context("Failed getting current indexer state")?;
 
-    let persisted_state_file = PersistedStateExists::get_persisted_state_file(&project_paths);
+    Ok((config, current_state))
+}
 
-    let (should_run_codegen, changes_detected) = match &persisted_state_file {
+///What changed since the generated files were written, compared to the persisted
+///state file
+struct CodegenChanges {
+    persisted_state_file: PersistedStateExists,
+    changed_fields: Vec<persisted_state::StateField>,
+    ///Generated files changed since codegen and its post_codegen hooks ran
+    changed_generated_files: Vec<String>,
+}
+
+impl CodegenChanges {
+    fn new(project_paths: &ParsedProjectPaths, current_state: &PersistedState) -> Self {
+        let persisted_state_file = PersistedStateExists::get_persisted_state_file(project_paths);
+        let (changed_fields, changed_generated_files) = match &persisted_state_file {
+            PersistedStateExists::Exists(persisted_state) => (
+                current_state.should_run_codegen(persisted_state).1,
+                persisted_state.get_changed_generated_files(project_paths),
+            ),
+            PersistedStateExists::NotExists | PersistedStateExists::Corrupted(_) => {
+                (vec![], vec![])
+            }
+        };
+        Self {
+            persisted_state_file,
+            changed_fields,
+            changed_generated_files,
+        }
+    }
+
+    fn should_run_codegen(&self) -> bool {
+        match self.persisted_state_file {
+            PersistedStateExists::Exists(_) => {
+                !self.changed_fields.is_empty() || !self.changed_generated_files.is_empty()
+            }
+            PersistedStateExists::NotExists | PersistedStateExists::Corrupted(_) => true,
+        }
+    }
+}
+
+fn print_changes_detected(
+    changes_detected: Vec<persisted_state::StateField>,
+    persisted_state: &PersistedState,
+    current_state: &PersistedState,
+) {
+    println!(
+        "Changes to {} detected",
+        //Changes will "Config" or "Schema" etc.
+        changes_detected
+            .iter()
+            .map(|f| f.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    //Describe what changed in the schema and config since the hashes only tell that
+    //something changed
+    for change in persisted_state
+        .project_snapshot
+        .diff(&current_state.project_snapshot)
+    {
+        println!("  {change}");
+    }
+}
+
+async fn run_codegen_if_changed(
+    project_paths: &ParsedProjectPaths,
+    config: &SystemConfig,
+    current_state: &PersistedState,
+    post_codegen: &PostCodegenArgs,
+    package_manager: PackageManager,
+) -> Result<()> {
+    let CodegenChanges {
+        persisted_state_file,
+        changed_fields,
+        changed_generated_files,
+    } = match CodegenChanges::new(project_paths, current_state) {
+        changes if changes.should_run_codegen() => changes,
+        _ => return Ok(()),
+    };
+
+    match &persisted_state_file {
+        PersistedStateExists::NotExists => println!("No generated files detected"),
+        PersistedStateExists::Corrupted(reason) => {
+            println!("Persisted state is invalid: {reason}")
+        }
         PersistedStateExists::Exists(persisted_state) => {
-            current_state.should_run_codegen(persisted_state)
+            if !changed_fields.is_empty() {
+                print_changes_detected(changed_fields, persisted_state, current_state)
+            }
+            if !changed_generated_files.is_empty() {
+                println!(
+                    "Changes to the generated files {} detected",
+                    changed_generated_files.join(", ")
+                )
+            }
+        }
+    }
+
+    match &persisted_state_file {
+        PersistedStateExists::Exists(ps) if &ps.envio_version != CURRENT_CRATE_VERSION => {
+            println!(
+                "Envio version '{}' does not match the previous version '{}' used in the \
+                 generated directory",
+                CURRENT_CRATE_VERSION, &ps.envio_version
+            );
+            println!("Purging generated directory",);
+            commands::codegen::remove_files_except_git(&project_paths.generated)
+                .await
+                .context("Failed purging generated")?;
+        }
+        _ => (),
+    };
+
+    let codegen_scope = match &persisted_state_file {
+        //Changed generated files need the post_codegen hooks to run on a full codegen
+        PersistedStateExists::Exists(persisted_state) if changed_generated_files.is_empty() => {
+            current_state.get_codegen_scope(persisted_state)
         }
-        PersistedStateExists::NotExists | PersistedStateExists::Corrupted => (true, vec![]),
+        PersistedStateExists::Exists(_)
+        | PersistedStateExists::NotExists
+        | PersistedStateExists::Corrupted(_) => CodegenScope::Full,
     };
 
-    let print_changes_detected = |changes_detected: Vec<persisted_state::StateField>| {
-        println!(
-            "Changes to {} detected",
-            //Changes will "Config" or "Schema" etc.
-            changes_detected
+    match &codegen_scope {
+        CodegenScope::Full => println!("Running codegen"),
+        CodegenScope::Targeted(outputs) => println!(
+            "Running codegen for {}",
+            outputs
                 .iter()
-                .map(|f| f.to_string())
+                .map(|output| output.to_string_lossy())
                 .collect::<Vec<_>>()
                 .join(", ")
-        );
-    };
+        ),
+    }
 
-    if should_run_codegen {
-        match persisted_state_file {
-            PersistedStateExists::NotExists => println!("No generated files detected"),
-            PersistedStateExists::Corrupted => println!("Persisted state is invalid"),
-            PersistedStateExists::Exists(_) => print_changes_detected(changes_detected),
-        }
+    commands::codegen::run_codegen(
+        config,
+        project_paths,
+        &codegen_scope,
+        post_codegen,
+        package_manager,
+    )
+    .await
+    .context("Failed running codegen")
+}
 
-        match persisted_state_file {
-            PersistedStateExists::Exists(ps) if &ps.envio_version != CURRENT_CRATE_VERSION => {
-                println!(
-                    "Envio version '{}' does not match the previous version '{}' used in the \
-                     generated directory",
-                    CURRENT_CRATE_VERSION, &ps.envio_version
-                );
-                println!("Purging generated directory",);
-                commands::codegen::remove_files_except_git(&project_paths.generated)
-                    .await
-                    .context("Failed purging generated")?;
+///Starts the docker containers if the project's services aren't running yet and waits for them
+///to be healthy. Services that envio doesn't manage with docker only get checked.
+///Returns the url of the hasura console to open, which is only the case when the
+///containers were started by this run.
+async fn start_services(
+    project_paths: &ParsedProjectPaths,
+    dev_services: &DevServices,
+    compose_project_name: &str,
+) -> Result<Option<String>> {
+    if !dev_services.use_docker {
+        return match service_health::fetch_services_health(dev_services).await {
+            Ok(true) => Ok(None),
+            Ok(false) => Err(anyhow!("Services health check failed")),
+            Err(err) => Err(anyhow!(err)),
+        }
+        .context(format!(
+            "Failed connecting to the existing services. Docker is disabled with \
+             ENVIO_DOCKER=false, so make sure Postgres at {}:{} is running{}",
+            dev_services.postgres.host,
+            dev_services.postgres.port,
+            match &dev_services.hasura_url {
+                Some(hasura_url) => format!(" and Hasura at {hasura_url}"),
+                None => String::new(),
             }
-            _ => (),
-        };
-
-        println!("Running codegen");
-
-        commands::codegen::run_codegen(&config, &project_paths)
-            .await
-            .context("Failed running codegen")?;
+        ));
     }
-    // if hasura healhz check returns not found assume docker isnt running and start it up {
-    let hasura_health_check_is_error = service_health::fetch_hasura_healthz().await.is_err();
 
-    let should_open_hasura_console = if hasura_health_check_is_error {
+    //Checking the containers of this compose project rather than the health of the
+    //ports avoids connecting to the services of another indexer on the same ports
+    let is_project_running = match commands::docker::is_compose_project_running(project_paths).await
+    {
+        Ok(is_project_running) => is_project_running,
+        //Older docker versions without the compose plugin can't list the containers,
+        //so fall back to checking whether the services respond
+        Err(err) => {
+            println!(
+                "Failed checking the docker containers of the project, falling back to the \
+                 services health check: {err:#}"
+            );
+            matches!(
+                service_health::fetch_services_health(dev_services).await,
+                Ok(true)
+            )
+        }
+    };
+
+    let hasura_console_url = if !is_project_running {
+        let running_legacy_project =
+            commands::docker::get_running_legacy_project(project_paths, compose_project_name)
+                .await?;
+        dev_services.check_docker_ports_available(running_legacy_project.as_deref())?;
         //Run docker commands to spin up container
-        commands::docker::docker_compose_up_d(&project_paths)
+        commands::docker::docker_compose_up_d(project_paths)
             .await
             .context("Failed running docker compose up after server liveness check")?;
-        true
+        dev_services.hasura_url.clone()
     } else {
-        false
+        None
     };
 
-    let hasura_health = service_health::fetch_hasura_healthz_with_retry().await;
-
-    match hasura_health {
+    match service_health::fetch_services_health_with_retry(dev_services).await {
         EndpointHealth::Unhealthy(err_message) => {
-            Err(anyhow!(err_message)).context("Failed to start hasura")?;
+            Err(anyhow!(err_message)).context("Failed to start the docker services")
         }
-        EndpointHealth::Healthy => {
-            //Get the persisted state from the db
-            let persisted_state_db = PersistedStateExists::read_from_db()
-                .await
-                .context("Failed to read persisted state from the DB")?;
-
-            //Temporarily disabled since it is causing issues
-            const TEMPORARY_SHOULD_SYNC_FROM_RAW_EVENTS: bool = false;
-            let should_sync_from_raw_events = TEMPORARY_SHOULD_SYNC_FROM_RAW_EVENTS;
-
-            let (
-                /*DISABLED*/ _should_sync_from_raw_events,
-                should_run_db_migrations,
-                changes_detected,
-            ) = match &persisted_state_db {
-                PersistedStateExists::Exists(persisted_state) =>
-                //In the case where the persisted state exists, compare it to current state
-                //determine whether to run migrations and which changes have occured to
-                //cause that.
-                {
-                    let (should_run_db_migrations, changes_detected) =
-                        current_state.should_run_db_migrations(persisted_state);
-
-                    let should_sync_from_raw_events =
-                        current_state.should_sync_from_raw_events(persisted_state);
-
-                    (
-                        should_sync_from_raw_events,
-                        should_run_db_migrations,
-                        changes_detected,
-                    )
-                }
-                //Otherwise we should run db migrations
-                PersistedStateExists::NotExists | PersistedStateExists::Corrupted => {
-                    (false, true, vec![])
-                }
-            };
+        EndpointHealth::Healthy => Ok(hasura_console_url),
+    }
+}
 
-            if should_run_db_migrations {
-                match persisted_state_db {
-                    PersistedStateExists::NotExists => {
-                        println!("Db Migrations have not been run")
-                    }
-                    PersistedStateExists::Corrupted => println!("Invalid DB persisted state"),
-                    PersistedStateExists::Exists(_) => print_changes_detected(changes_detected),
-                }
-                println!("Running db migrations");
+///Checks whether the raw events stored in the db cover everything that was processed,
+///printing why not otherwise. Returns whether the indexer should resync from raw events.
+pub async fn check_raw_events_replay(config: &SystemConfig) -> Result<bool> {
+    let postgres = PostgresSettings::from_env(&config.local_services);
+    let raw_events_coverage = RawEventsCoverage::read_from_db(&postgres)
+        .await
+        .context("Failed to read raw events coverage from the DB")?;
+
+    let replay_blockers = raw_events_coverage.get_replay_blockers(config);
+    if replay_blockers.is_empty() {
+        return Ok(true);
+    }
 
-                let should_drop_raw_events = !should_sync_from_raw_events;
+    println!("Raw events can't be replayed:");
+    for blocker in &replay_blockers {
+        println!("  {blocker}");
+    }
+    println!("Falling back to a full resync");
+    Ok(false)
+}
+
+///Runs the db migrations if the persisted state on the db doesn't match the current state.
+///Returns whether the indexer should resync from raw events.
+async fn run_db_migrations_if_changed(
+    project_paths: &ParsedProjectPaths,
+    config: &SystemConfig,
+    current_state: &PersistedState,
+    should_replay_raw_events: bool,
+    package_manager: PackageManager,
+) -> Result<bool> {
+    let postgres = PostgresSettings::from_env(&config.local_services);
+    //Get the persisted state from the db
+    let persisted_state_db = PersistedStateExists::read_from_db(&postgres)
+        .await
+        .context("Failed to read persisted state from the DB")?;
+
+    let (should_run_db_migrations, only_handler_files_changed, changes_detected) =
+        match &persisted_state_db {
+            PersistedStateExists::Exists(persisted_state) =>
+            //In the case where the persisted state exists, compare it to current state
+            //determine whether to run migrations and which changes have occured to
+            //cause that.
+            {
+                let (should_run_db_migrations, changes_detected) =
+                    current_state.should_run_db_migrations(persisted_state);
+
+                let only_handler_files_changed =
+                    current_state.should_sync_from_raw_events(persisted_state);
 
-                commands::db_migrate::run_db_setup(
-                    &project_paths,
-                    should_drop_raw_events,
-                    &current_state,
+                (
+                    should_run_db_migrations,
+                    only_handler_files_changed,
+                    changes_detected,
                 )
-                .await
-                .context("Failed running db setup command")?;
             }
+            //Otherwise we should run db migrations
+            PersistedStateExists::NotExists | PersistedStateExists::Corrupted(_) => {
+                (true, false, vec![])
+            }
+        };
+
+    if !should_run_db_migrations {
+        return Ok(false);
+    }
 
-            if should_sync_from_raw_events {
-                println!("Resyncing from raw_events");
+    let schema_only_changes = match &persisted_state_db {
+        PersistedStateExists::NotExists => {
+            println!("Db Migrations have not been run");
+            None
+        }
+        PersistedStateExists::Corrupted(reason) => {
+            println!("Invalid DB persisted state: {reason}");
+            None
+        }
+        PersistedStateExists::Exists(persisted_state) => {
+            print_changes_detected(changes_detected, persisted_state, current_state);
+            current_state.get_schema_only_changes(persisted_state)
+        }
+    };
+
+    match schema_only_changes {
+        Some(schema_changes) if schema_changes.iter().all(|change| !change.is_destructive()) => {
+            for change in &schema_changes {
+                println!("  {change}");
             }
+            println!("Applying schema changes to the existing db");
 
-            println!("Starting indexer");
+            commands::db_migrate::run_up_migrations(
+                project_paths,
+                current_state,
+                &postgres,
+                package_manager,
+            )
+            .await
+            .context("Failed applying schema changes to the db")?;
 
-            commands::start::start_indexer(
-                &project_paths,
-                should_sync_from_raw_events,
-                should_open_hasura_console,
+            Ok(false)
+        }
+        schema_changes => {
+            if let Some(schema_changes) = schema_changes {
+                confirm_db_reset(&schema_changes)?;
+            }
+
+            //Replaying reproduces the previous run with the new handlers, which is
+            //only the same as a full resync when nothing else changed
+            let should_sync_from_raw_events = match should_replay_raw_events {
+                false => false,
+                true if !only_handler_files_changed => {
+                    println!(
+                        "Raw events are only replayed when nothing but the handlers changed, \
+                         falling back to a full resync"
+                    );
+                    false
+                }
+                true => check_raw_events_replay(config).await?,
+            };
+
+            println!("Running db migrations");
+
+            let should_drop_raw_events = !should_sync_from_raw_events;
+
+            commands::db_migrate::run_db_setup(
+                project_paths,
+                should_drop_raw_events,
+                current_state,
+                &postgres,
+                package_manager,
             )
             .await
-            .context("Failed running start on the indexer")?;
+            .context("Failed running db setup command")?;
+
+            Ok(should_sync_from_raw_events)
         }
     }
+}
+
+///Schema changes that can't be applied to the existing db drop all indexed data,
+///so the reset needs to be confirmed
+fn confirm_db_reset(schema_changes: &[persisted_state::SchemaChange]) -> Result<()> {
+    println!("The following schema changes can't be applied to the existing db:");
+    for change in schema_changes
+        .iter()
+        .filter(|change| change.is_destructive())
+    {
+        println!("  {change}");
+    }
 
-    Ok(())
+    let should_reset = Confirm::new(
+        "Reset the db? All indexed data will be dropped and the indexer will resync from the \
+         start blocks",
+    )
+    .with_default(false)
+    .prompt()
+    .context("Failed prompting for db reset")?;
+
+    if should_reset {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Db reset was declined. Revert the destructive schema changes or run 'envio local \
+             db-migrate setup' to reset the db manually"
+        ))
+    }
+}
+
+///All the project files that affect codegen, migrations or the running indexer
+fn get_watched_paths(config: &SystemConfig) -> Result<Vec<PathBuf>> {
+    let mut paths = vec![
+        config.parsed_project_paths.config.clone(),
+        config
+            .get_path_to_schema()
+            .context("Failed getting path to schema")?,
+    ];
+    paths.extend(
+        config
+            .get_all_paths_to_abi_files()
+            .context("Failed getting abi file paths")?,
+    );
+    paths.extend(
+        config
+            .get_all_paths_to_handlers()
+            .context("Failed getting handler paths")?,
+    );
+    //Only existing overrides are watched, new ones are picked up by the next codegen
+    let template_overrides = TemplateOverrides::new(&config.parsed_project_paths.project_root);
+    paths.extend(
+        template_overrides
+            .get_paths()
+            .context("Failed getting template override paths")?
+            .into_iter()
+            .map(|path| template_overrides.get_dir().join(path)),
+    );
+    Ok(paths)
+}
+
+///Reruns codegen and migrations for the changed files, returning the new config
+///and whether the indexer should resync from raw events
+async fn rerun_dev(
+    project_paths: &ParsedProjectPaths,
+    should_replay_raw_events: bool,
+    post_codegen: &PostCodegenArgs,
+    package_manager: PackageManager,
+) -> Result<(SystemConfig, bool)> {
+    let (config, current_state) = get_current_state(project_paths).await?;
+
+    run_codegen_if_changed(
+        project_paths,
+        &config,
+        &current_state,
+        post_codegen,
+        package_manager,
+    )
+    .await?;
+
+    let should_sync_from_raw_events = run_db_migrations_if_changed(
+        project_paths,
+        &config,
+        &current_state,
+        should_replay_raw_events,
+        package_manager,
+    )
+    .await?;
+
+    Ok((config, should_sync_from_raw_events))
+}
+
+enum WatchEvent {
+    IndexerExited,
+    FilesChanged(Vec<PathBuf>),
+    Interrupted,
+}
+
+///Resolves to None if ctrl+c is pressed first. Once ctrl+c is listened to it no longer
+///exits the process, so every wait in watch mode needs to be interruptible.
+async fn until_interrupted<T>(future: impl std::future::Future<Output = T>) -> Option<T> {
+    tokio::select! {
+        output = future => Some(output),
+        _ = tokio::signal::ctrl_c() => None,
+    }
+}
+
+///Runs the indexer and restarts it whenever the config, schema, abi or handler
+///files change. Errors while rerunning are printed instead of exiting, since the
+///files are likely still being edited.
+async fn watch(
+    project_paths: &ParsedProjectPaths,
+    config: SystemConfig,
+    should_sync_from_raw_events: bool,
+    hasura_console_url: Option<String>,
+    should_replay_raw_events: bool,
+    post_codegen: &PostCodegenArgs,
+    package_manager: PackageManager,
+) -> Result<()> {
+    let mut watcher = FileWatcher::new(
+        get_watched_paths(&config)?,
+        WATCH_POLL_INTERVAL,
+        WATCH_DEBOUNCE,
+    );
+    let mut should_sync_from_raw_events = should_sync_from_raw_events;
+    let mut hasura_console_url = hasura_console_url;
+
+    loop {
+        println!("Starting indexer, watching for changes");
+
+        let mut indexer = commands::start::spawn_indexer(
+            project_paths,
+            should_sync_from_raw_events,
+            hasura_console_url.as_deref(),
+        )
+        .context("Failed running start on the indexer")?;
+        //The console only needs to be opened once
+        hasura_console_url = None;
+
+        let watch_event = tokio::select! {
+            exit = indexer.wait() => {
+                let exit = exit.context("Failed to exit the indexer child process")?;
+                if exit.success() {
+                    println!("Indexer finished processing all events");
+                } else {
+                    println!("Indexer crashed, see the error logs above");
+                }
+                WatchEvent::IndexerExited
+            }
+            changed_paths = watcher.wait_for_changes() => WatchEvent::FilesChanged(changed_paths),
+            _ = tokio::signal::ctrl_c() => WatchEvent::Interrupted,
+        };
+
+        let changed_paths = match watch_event {
+            WatchEvent::FilesChanged(changed_paths) => {
+                println!("Stopping indexer");
+                commands::start::stop_indexer(&mut indexer).await?;
+                Some(changed_paths)
+            }
+            WatchEvent::IndexerExited => {
+                println!("Waiting for changes to restart the indexer");
+                until_interrupted(watcher.wait_for_changes()).await
+            }
+            WatchEvent::Interrupted => {
+                commands::start::stop_indexer(&mut indexer).await?;
+                None
+            }
+        };
+        let Some(mut changed_paths) = changed_paths else {
+            return Ok(());
+        };
+
+        //Keep waiting for changes until the project can be rerun
+        loop {
+            for path in &changed_paths {
+                println!("Changed {}", path.display());
+            }
+
+            let Some(rerun) = until_interrupted(rerun_dev(
+                project_paths,
+                should_replay_raw_events,
+                post_codegen,
+                package_manager,
+            ))
+            .await
+            else {
+                return Ok(());
+            };
+
+            match rerun {
+                Ok((config, should_sync)) => {
+                    should_sync_from_raw_events = should_sync;
+                    //The config could add or remove watched files. The modified times from
+                    //before the rerun are kept, so files saved during it trigger another rerun.
+                    if let Ok(paths) = get_watched_paths(&config) {
+                        watcher.set_paths(paths);
+                    }
+                    break;
+                }
+                Err(err) => {
+                    eprintln!("Error: {err:?}");
+                    println!("Waiting for changes to retry");
+                    match until_interrupted(watcher.wait_for_changes()).await {
+                        Some(paths) => changed_paths = paths,
+                        None => return Ok(()),
+                    }
+                }
+            }
+        }
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     cli_args::{
-        clap_definitions::{InitArgs, ProjectPaths},
+        clap_definitions::{InitArgs, PackageManager, PostCodegenArgs, ProjectPaths},
         init_config::{self, Ecosystem, Language},
         interactive_init::prompt_missing_init_args,
     },
@@ -13,6 +13,7 @@ use crate::{
         contract_import_templates, hbs_dir_generator::HandleBarsDirGenerator,
         init_templates::InitTemplates,
     },
+    persisted_state::CodegenScope,
     project_paths::ParsedProjectPaths,
     template_dirs::TemplateDirs,
     utils::file_system,
@@ -30,7 +31,11 @@ fn is_valid_release_version_number(version: &str) -> bool {
     re_version_pattern.is_match(version) || version.contains("-main-")
 }
 
-pub async fn run_init_args(init_args: InitArgs, project_paths: &ProjectPaths) -> Result<()> {
+pub async fn run_init_args(
+    init_args: InitArgs,
+    project_paths: &ProjectPaths,
+    package_manager: PackageManager,
+) -> Result<()> {
     let template_dirs = TemplateDirs::new();
     //get_init_args_interactive opens an interactive cli for required args to be selected
     //if they haven't already been
@@ -270,10 +275,19 @@ pub async fn run_init_args(init_args: InitArgs, project_paths: &ProjectPaths) ->
     let config = SystemConfig::parse_from_project_files(&parsed_project_paths)
         .context("Failed parsing config")?;
 
-    commands::codegen::run_codegen(&config, &parsed_project_paths).await?;
+    let post_codegen = PostCodegenArgs::default();
+    commands::codegen::run_codegen(
+        &config,
+        &parsed_project_paths,
+        &CodegenScope::Full,
+        &post_codegen,
+        package_manager,
+    )
+    .await?;
 
     if init_config.language == Language::ReScript {
-        let res_build_exit = commands::rescript::build(&parsed_project_paths.project_root).await?;
+        let res_build_exit =
+            commands::rescript::build(&parsed_project_paths.project_root, package_manager).await?;
         if !res_build_exit.success() {
             return Err(anyhow!("Failed to build rescript"))?;
         }
@@ -1,7 +1,10 @@
 use crate::{
-    cli_args::clap_definitions::{DbMigrateSubcommands, LocalCommandTypes, LocalDockerSubcommands},
+    cli_args::clap_definitions::{
+        DbMigrateSubcommands, LocalCommandTypes, LocalDockerSubcommands, PackageManager,
+    },
     commands,
     config_parsing::system_config::SystemConfig,
+    dev_services::{DevServices, PostgresSettings},
     persisted_state::PersistedState,
     project_paths::ParsedProjectPaths,
 };
@@ -10,10 +13,22 @@ use anyhow::{Context, Result};
 pub async fn run_local(
     local_commands: &LocalCommandTypes,
     project_paths: &ParsedProjectPaths,
+    package_manager: PackageManager,
 ) -> Result<()> {
     match local_commands {
         LocalCommandTypes::Docker(subcommand) => match subcommand {
             LocalDockerSubcommands::Up => {
+                let config = SystemConfig::parse_from_project_files(project_paths)
+                    .context("Failed parsing config")?;
+                if !commands::docker::is_compose_project_running(project_paths).await? {
+                    let running_legacy_project = commands::docker::get_running_legacy_project(
+                        project_paths,
+                        &config.local_services.compose_project_name,
+                    )
+                    .await?;
+                    DevServices::from_env(&config)?
+                        .check_docker_ports_available(running_legacy_project.as_deref())?;
+                }
                 commands::docker::docker_compose_up_d(&project_paths).await?;
             }
             LocalDockerSubcommands::Down => {
@@ -22,34 +37,44 @@ pub async fn run_local(
         },
         LocalCommandTypes::DbMigrate(subcommand) => {
             //Use a closure just so running local dow doesn't need to construct persisted state
-            let get_persisted_state = || -> Result<PersistedState> {
+            let get_persisted_state = || -> Result<(PersistedState, PostgresSettings)> {
                 let config = SystemConfig::parse_from_project_files(&project_paths)
                     .context("Failed parsing config")?;
 
                 let persisted_state = PersistedState::get_current_state(&config)
                     .context("Failed constructing persisted state")?;
 
-                Ok(persisted_state)
+                Ok((
+                    persisted_state,
+                    PostgresSettings::from_env(&config.local_services),
+                ))
             };
 
             match subcommand {
                 DbMigrateSubcommands::Up => {
-                    let persisted_state = get_persisted_state()?;
-                    commands::db_migrate::run_up_migrations(&project_paths, &persisted_state)
-                        .await?;
+                    let (persisted_state, postgres) = get_persisted_state()?;
+                    commands::db_migrate::run_up_migrations(
+                        &project_paths,
+                        &persisted_state,
+                        &postgres,
+                        package_manager,
+                    )
+                    .await?;
                 }
 
                 DbMigrateSubcommands::Down => {
-                    commands::db_migrate::run_drop_schema(&project_paths).await?;
+                    commands::db_migrate::run_drop_schema(&project_paths, package_manager).await?;
                 }
 
                 DbMigrateSubcommands::Setup => {
-                    let persisted_state = get_persisted_state()?;
+                    let (persisted_state, postgres) = get_persisted_state()?;
                     const SHOULD_DROP_RAW_EVENTS: bool = true;
                     commands::db_migrate::run_db_setup(
                         &project_paths,
                         SHOULD_DROP_RAW_EVENTS,
                         &persisted_state,
+                        &postgres,
+                        package_manager,
                     )
                     .await?;
                 }
@@ -3,6 +3,7 @@ use crate::{
     cli_args::clap_definitions::{CommandLineArgs, CommandType},
     commands,
     config_parsing::{human_config, system_config::SystemConfig},
+    dev_services::{DevServices, PostgresSettings},
     persisted_state::{PersistedState, PersistedStateExists, CURRENT_CRATE_VERSION},
     project_paths::ParsedProjectPaths,
     scripts,
@@ -12,30 +13,83 @@ mod codegen;
 mod dev;
 pub mod init;
 mod local;
+mod schema;
+mod state;
+mod templates;
 
 use anyhow::{Context, Result};
 use schemars::schema_for;
 
 pub async fn execute(command_line_args: CommandLineArgs) -> Result<()> {
     let global_project_paths = command_line_args.project_paths;
+    let package_manager = command_line_args.package_manager;
     let parsed_project_paths = ParsedProjectPaths::try_from(global_project_paths.clone())
         .context("Failed parsing project paths")?;
 
+    //The indexer reads the .env file of the project, load it as well so that the cli
+    //connects to the same services. Vars that are already set take precedence.
+    let _ = dotenvy::from_path(parsed_project_paths.project_root.join(".env"));
+
     match command_line_args.command {
         CommandType::Init(init_args) => {
-            init::run_init_args(init_args, &global_project_paths).await?;
+            init::run_init_args(init_args, &global_project_paths, package_manager).await?;
         }
 
-        CommandType::Codegen => {
-            codegen::run_codegen(&parsed_project_paths).await?;
+        CommandType::Codegen(codegen_args) => {
+            if codegen_args.check {
+                codegen::run_codegen_check(
+                    &parsed_project_paths,
+                    codegen_args.emit_sql.as_deref(),
+                )?;
+            } else {
+                codegen::run_codegen(
+                    &parsed_project_paths,
+                    &codegen_args.post_codegen,
+                    package_manager,
+                    codegen_args.emit_sql.as_deref(),
+                )
+                .await?;
+            }
         }
 
-        CommandType::Dev => {
-            dev::run_dev(parsed_project_paths).await?;
+        CommandType::Dev(dev_args) => {
+            dev::run_dev(
+                parsed_project_paths,
+                dev_args.watch,
+                dev_args.replay_raw_events,
+                &dev_args.post_codegen,
+                package_manager,
+            )
+            .await?;
         }
 
         CommandType::Stop => {
-            commands::docker::docker_compose_down_v(&parsed_project_paths).await?;
+            if DevServices::is_docker_enabled_from_env()? {
+                commands::docker::docker_compose_down_v(&parsed_project_paths).await?;
+                //The services of a previous envio version are only pointed out, since the
+                //indexers of all previous versions share the same compose project name.
+                //Without a parseable config the compose project name is unknown.
+                if let Ok(config) = SystemConfig::parse_from_project_files(&parsed_project_paths) {
+                    if let Some(legacy_project_name) = commands::docker::get_running_legacy_project(
+                        &parsed_project_paths,
+                        &config.local_services.compose_project_name,
+                    )
+                    .await?
+                    {
+                        println!(
+                            "The local services started by a previous envio version are still \
+                             running under the docker compose project '{legacy_project_name}'. \
+                             Remove them and their data with `{}` in the generated directory",
+                            commands::docker::get_legacy_project_down_command(&legacy_project_name)
+                        );
+                    }
+                }
+            } else {
+                println!(
+                    "Docker is disabled with ENVIO_DOCKER=false, leaving the existing services \
+                     and their data untouched"
+                );
+            }
         }
 
         CommandType::Start(start_args) => {
@@ -54,9 +108,9 @@ pub async fn execute(command_line_args: CommandLineArgs) -> Result<()> {
                     "WARNING: Generated directory not detected. Consider running envio codegen \
                      first"
                 ),
-                PersistedStateExists::Corrupted => println!(
-                    "WARNING: Generated directory is corrupted. Consider running envio codegen \
-                     first"
+                PersistedStateExists::Corrupted(reason) => println!(
+                    "WARNING: Generated directory is corrupted ({reason}). Consider running \
+                     envio codegen first or inspecting it with envio state show"
                 ),
                 PersistedStateExists::Exists(_) => (),
             };
@@ -65,38 +119,57 @@ pub async fn execute(command_line_args: CommandLineArgs) -> Result<()> {
                 std::env::set_var("ENVIO_SAVE_BENCHMARK_DATA", "true");
             }
 
-            if start_args.restart {
+            let mut should_sync_from_raw_events = false;
+
+            if start_args.restart || start_args.replay {
                 let config = SystemConfig::parse_from_project_files(&parsed_project_paths)
                     .context("Failed parsing config")?;
 
                 let persisted_state = PersistedState::get_current_state(&config)
                     .context("Failed constructing persisted state")?;
 
-                const SHOULD_DROP_RAW_EVENTS: bool = true;
+                if start_args.replay {
+                    should_sync_from_raw_events = dev::check_raw_events_replay(&config).await?;
+                }
+
+                let should_drop_raw_events = !should_sync_from_raw_events;
 
                 commands::db_migrate::run_db_setup(
                     &parsed_project_paths,
-                    SHOULD_DROP_RAW_EVENTS,
+                    should_drop_raw_events,
                     &persisted_state,
+                    &PostgresSettings::from_env(&config.local_services),
+                    package_manager,
                 )
                 .await?;
             }
-            const SHOULD_SYNC_FROM_RAW_EVENTS: bool = false;
-            const SHOULD_OPEN_HASURA: bool = false;
+            const HASURA_CONSOLE_URL: Option<&str> = None;
             commands::start::start_indexer(
                 &parsed_project_paths,
-                SHOULD_SYNC_FROM_RAW_EVENTS,
-                SHOULD_OPEN_HASURA,
+                should_sync_from_raw_events,
+                HASURA_CONSOLE_URL,
             )
             .await?;
         }
 
+        CommandType::State(state_subcommand) => {
+            state::run_state(&state_subcommand, &parsed_project_paths).await?;
+        }
+
+        CommandType::Templates(templates_subcommand) => {
+            templates::run_templates(&templates_subcommand, &parsed_project_paths)?;
+        }
+
+        CommandType::Schema(schema_subcommand) => {
+            schema::run_schema(&schema_subcommand, &parsed_project_paths)?;
+        }
+
         CommandType::Local(local_commands) => {
-            local::run_local(&local_commands, &parsed_project_paths).await?;
+            local::run_local(&local_commands, &parsed_project_paths, package_manager).await?;
         }
 
         CommandType::BenchmarkSummary => {
-            commands::benchmark::print_summary(&parsed_project_paths).await?
+            commands::benchmark::print_summary(&parsed_project_paths, package_manager).await?
         }
 
         CommandType::Script(Script::PrintCliHelpMd) => {
@@ -0,0 +1,36 @@
+use crate::{
+    cli_args::clap_definitions::{DiagramFormat, SchemaSubcommands},
+    config_parsing::{schema_diagram::SchemaDiagram, system_config::SystemConfig},
+    project_paths::ParsedProjectPaths,
+};
+use anyhow::{Context, Result};
+use std::fs;
+
+pub fn run_schema(
+    subcommand: &SchemaSubcommands,
+    project_paths: &ParsedProjectPaths,
+) -> Result<()> {
+    let config =
+        SystemConfig::parse_from_project_files(project_paths).context("Failed parsing config")?;
+
+    match subcommand {
+        SchemaSubcommands::Diagram(diagram_args) => {
+            let diagram = SchemaDiagram::from_schema(&config.schema)
+                .context("Failed creating schema diagram")?;
+            let rendered = match diagram_args.format {
+                DiagramFormat::Mermaid => diagram.to_mermaid(),
+                DiagramFormat::Dot => diagram.to_dot(),
+            };
+            match &diagram_args.output {
+                Some(output) => {
+                    fs::write(output, rendered)
+                        .context(format!("Failed writing schema diagram to {output}"))?;
+                    println!("Wrote schema diagram to {output}");
+                }
+                None => print!("{rendered}"),
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use crate::{
+    cli_args::clap_definitions::StateSubcommands,
+    config_parsing::system_config::SystemConfig,
+    dev_services::PostgresSettings,
+    persisted_state::{PersistedState, PersistedStateExists, StateField},
+    project_paths::ParsedProjectPaths,
+};
+use anyhow::{Context, Result};
+use strum::IntoEnumIterator;
+
+///Hashes are shortened so that the states fit side by side
+const DISPLAYED_HASH_LENGTH: usize = 12;
+
+pub async fn run_state(
+    subcommand: &StateSubcommands,
+    project_paths: &ParsedProjectPaths,
+) -> Result<()> {
+    let config =
+        SystemConfig::parse_from_project_files(project_paths).context("Failed parsing config")?;
+    let current_state = PersistedState::get_current_state(&config)
+        .context("Failed constructing current persisted state")?;
+    let postgres = PostgresSettings::from_env(&config.local_services);
+
+    match subcommand {
+        StateSubcommands::Show => {
+            let file_state = PersistedStateExists::get_persisted_state_file(project_paths);
+            //The db might not be running, which shouldn't stop showing the other states
+            let db_state = PersistedStateExists::read_from_db(&postgres)
+                .await
+                .unwrap_or_else(|err| PersistedStateExists::Corrupted(err.to_string()));
+
+            print_states(&file_state, &db_state, &current_state);
+        }
+        StateSubcommands::ResetFile => {
+            current_state.write_to_file(project_paths)?;
+            println!("Persisted state file now matches the current state");
+        }
+        StateSubcommands::SetDb => {
+            current_state
+                .upsert_to_db(&postgres)
+                .await
+                .context("Failed to upsert persisted state table")?;
+            println!("Persisted state in the db now matches the current state");
+        }
+    }
+
+    Ok(())
+}
+
+fn format_field_value(state: &PersistedState, field: &StateField) -> String {
+    let value = state.get_field_value(field);
+    match field {
+        StateField::EnvioVersion => value,
+        _ => value.chars().take(DISPLAYED_HASH_LENGTH).collect(),
+    }
+}
+
+fn format_state_cell(state: &PersistedStateExists, field: &StateField) -> String {
+    match state {
+        PersistedStateExists::Exists(state) => format_field_value(state, field),
+        PersistedStateExists::NotExists => "-".to_string(),
+        PersistedStateExists::Corrupted(_) => "?".to_string(),
+    }
+}
+
+fn print_changes(label: &str, state: &PersistedStateExists, current_state: &PersistedState) {
+    match state {
+        PersistedStateExists::Exists(state) => {
+            let changed_fields = current_state.get_changed_fields(state);
+            if changed_fields.is_empty() {
+                println!("{label} matches the current state");
+            } else {
+                println!(
+                    "{label} differs from the current state in {}",
+                    changed_fields
+                        .iter()
+                        .map(|field| field.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }
+        PersistedStateExists::NotExists => println!("{label} doesn't exist"),
+        PersistedStateExists::Corrupted(reason) => println!("{label} can't be read: {reason}"),
+    }
+}
+
+fn print_states(
+    file_state: &PersistedStateExists,
+    db_state: &PersistedStateExists,
+    current_state: &PersistedState,
+) {
+    println!(
+        "{:<14} {:<14} {:<14} {:<14}",
+        "Field", "File", "Db", "Current"
+    );
+    for field in StateField::iter() {
+        println!(
+            "{:<14} {:<14} {:<14} {:<14}",
+            field.to_string(),
+            format_state_cell(file_state, &field),
+            format_state_cell(db_state, &field),
+            format_field_value(current_state, &field),
+        );
+    }
+    println!();
+    print_changes("File", file_state, current_state);
+    print_changes("Db", db_state, current_state);
+}
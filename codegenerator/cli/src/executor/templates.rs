@@ -0,0 +1,112 @@
+use crate::{
+    cli_args::clap_definitions::TemplatesSubcommands,
+    project_paths::ParsedProjectPaths,
+    template_dirs::{TemplateDirs, TemplateOverrides},
+};
+use anyhow::{anyhow, Context, Result};
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+pub fn run_templates(
+    subcommand: &TemplatesSubcommands,
+    project_paths: &ParsedProjectPaths,
+) -> Result<()> {
+    let template_dirs = TemplateDirs::new();
+    let codegen_paths = template_dirs.get_codegen_file_paths()?;
+    let overrides = TemplateOverrides::new(&project_paths.project_root);
+
+    match subcommand {
+        TemplatesSubcommands::List => {
+            for path in codegen_paths {
+                let is_overridden = overrides.get_dir().join(&path).is_file();
+                println!(
+                    "{}{}",
+                    path.display(),
+                    if is_overridden { " (overridden)" } else { "" }
+                );
+            }
+        }
+        TemplatesSubcommands::Dump { paths } => {
+            for path in paths {
+                let matching_paths = get_matching_paths(&codegen_paths, &PathBuf::from(path));
+                if matching_paths.is_empty() {
+                    return Err(anyhow!(
+                        "No codegen template at {path}, run envio templates list to see the \
+                         available templates"
+                    ));
+                }
+                for matching_path in matching_paths {
+                    dump_template(&template_dirs, &overrides, matching_path)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+///Gets the template paths that are the given path or in the dir at the given path
+fn get_matching_paths<'a>(codegen_paths: &'a [PathBuf], path: &Path) -> Vec<&'a PathBuf> {
+    //Leading ./ would never match the template paths
+    let path: PathBuf = path
+        .components()
+        .filter(|component| component != &Component::CurDir)
+        .collect();
+    codegen_paths
+        .iter()
+        .filter(|codegen_path| codegen_path.starts_with(&path))
+        .collect()
+}
+
+fn dump_template(
+    template_dirs: &TemplateDirs,
+    overrides: &TemplateOverrides,
+    path: &Path,
+) -> Result<()> {
+    let output_path = overrides.get_dir().join(path);
+    if output_path.exists() {
+        println!("Skipping {}, it already exists", output_path.display());
+        return Ok(());
+    }
+
+    let contents = template_dirs
+        .get_file_contents(path)
+        .ok_or_else(|| anyhow!("Unexpected, template {} does not exist", path.display()))?;
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent).context(format!("Failed creating dir {}", parent.display()))?;
+    }
+    fs::write(&output_path, contents)
+        .context(format!("Failed writing {}", output_path.display()))?;
+    println!("Copied {}", output_path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::get_matching_paths;
+    use std::path::PathBuf;
+
+    #[test]
+    fn matches_files_and_dirs() {
+        let codegen_paths = vec![
+            PathBuf::from("dynamic/codegen/index.d.ts.hbs"),
+            PathBuf::from("dynamic/codegen/src/Handlers.res.hbs"),
+            PathBuf::from("static/codegen/src/Env.res"),
+        ];
+
+        assert_eq!(
+            get_matching_paths(&codegen_paths, &PathBuf::from("dynamic/codegen/src/")),
+            vec![&codegen_paths[1]]
+        );
+        assert_eq!(
+            get_matching_paths(&codegen_paths, &PathBuf::from("./dynamic")),
+            vec![&codegen_paths[0], &codegen_paths[1]]
+        );
+        assert_eq!(
+            get_matching_paths(&codegen_paths, &PathBuf::from("static/codegen/src/Env")),
+            Vec::<&PathBuf>::new()
+        );
+    }
+}
@@ -1,27 +1,42 @@
-use std::{collections::HashMap, path::PathBuf, vec};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    vec,
+};
 
-use super::hbs_dir_generator::HandleBarsDirGenerator;
+use super::{
+    graphql_client_templates::{GraphqlClientTemplate, GRAPHQL_CLIENT_DIR},
+    hbs_dir_generator::HandleBarsDirGenerator,
+    ts_types_templates::TsTypesTemplate,
+    validators_templates::{ValidatorsTemplate, VALIDATORS_DIR},
+};
 use crate::{
     config_parsing::{
-        entity_parsing::{Entity, Field, GraphQLEnum, MultiFieldIndex, Schema},
+        entity_parsing::{
+            get_entity_type_enum_name, Aggregation, Entity, Field, FullTextIndex, GraphQLEnum,
+            Interface, MultiFieldIndex, Schema, Union,
+        },
         event_parsing::{abi_to_rescript_type, EthereumEventParam},
+        human_config::ValidatorFormat,
         postgres_types,
         system_config::{
             self, Abi, Ecosystem, EventKind, FuelEventKind, HyperfuelConfig, HypersyncConfig,
             RpcConfig, SelectedField, SystemConfig,
         },
     },
-    persisted_state::{PersistedState, PersistedStateJsonString},
+    persisted_state::{CodegenScope, PersistedState, PersistedStateJsonString},
     project_paths::{
         handler_paths::HandlerPathsTemplate, path_utils::add_trailing_relative_dot,
         ParsedProjectPaths,
     },
     rescript_types::{RescriptRecordField, RescriptTypeExpr, RescriptTypeIdent},
-    template_dirs::TemplateDirs,
+    template_dirs::{TemplateDirs, TemplateOverrides},
     utils::text::{Capitalize, CapitalizedOptions, CaseOptions},
 };
 use anyhow::{anyhow, Context, Result};
 use ethers::abi::EventParam;
+use itertools::Itertools;
 use pathdiff::diff_paths;
 use serde::Serialize;
 
@@ -65,6 +80,37 @@ impl GraphQlEnumTypeTemplate {
     }
 }
 
+///Template for an interface or union, templated as a variant of its implementing entities
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct GraphQlPolymorphicTypeTemplate {
+    pub name: CapitalizedOptions,
+    pub implementors: Vec<CapitalizedOptions>,
+    ///Enum of the implementors, stored next to the id of a reference
+    pub entity_type_enum: CapitalizedOptions,
+}
+
+impl GraphQlPolymorphicTypeTemplate {
+    fn new(name: &String, schema: &Schema) -> Self {
+        GraphQlPolymorphicTypeTemplate {
+            name: name.to_capitalized_options(),
+            entity_type_enum: get_entity_type_enum_name(name).to_capitalized_options(),
+            implementors: schema
+                .get_implementors(name)
+                .iter()
+                .map(|entity| entity.name.to_capitalized_options())
+                .collect(),
+        }
+    }
+
+    fn from_config_interface(interface: &Interface, schema: &Schema) -> Self {
+        Self::new(&interface.name, schema)
+    }
+
+    fn from_config_union(union: &Union, schema: &Schema) -> Self {
+        Self::new(&union.name, schema)
+    }
+}
+
 #[derive(Serialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum RelationshipTypeTemplate {
@@ -157,6 +203,9 @@ pub struct EntityParamTypeTemplate {
     ///Used to determine if you can run a where
     ///query on this field.
     pub is_queryable_field: bool,
+    ///Name of the column in the db, None for derived fields
+    pub db_field_name: Option<String>,
+    pub description: Option<String>,
 }
 
 impl HasIsDerivedFrom for EntityParamTypeTemplate {
@@ -166,6 +215,15 @@ impl HasIsDerivedFrom for EntityParamTypeTemplate {
 }
 
 impl EntityParamTypeTemplate {
+    ///Name of the field in the entity record, entity fields hold the id of the linked entity
+    pub fn get_record_field_name(&self) -> String {
+        if self.is_entity_field {
+            format!("{}_id", self.field_name.uncapitalized)
+        } else {
+            self.field_name.uncapitalized.clone()
+        }
+    }
+
     fn from_entity_field(field: &Field, entity: &Entity, config: &SystemConfig) -> Result<Self> {
         let res_type: RescriptTypeIdent = field
             .field_type
@@ -184,6 +242,12 @@ impl EntityParamTypeTemplate {
         //Both of these cases have indexes on them and should exist
         let is_queryable_field = is_indexed_field || is_derived_lookup_field;
 
+        let db_field_name = if is_derived_from {
+            None
+        } else {
+            Some(field.get_db_column_name(schema)?)
+        };
+
         Ok(EntityParamTypeTemplate {
             field_name: field.name.to_capitalized_options(),
             res_schema_code: res_type.to_rescript_schema(),
@@ -192,6 +256,8 @@ impl EntityParamTypeTemplate {
             is_entity_field,
             is_indexed_field,
             is_queryable_field,
+            db_field_name,
+            description: field.description.clone(),
         })
     }
 }
@@ -214,16 +280,53 @@ pub struct DerivedFieldTemplate {
     pub derived_from_field: String,
 }
 
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct FullTextIndexTemplate {
+    pub name: String,
+    pub language: String,
+    pub fields: Vec<String>,
+}
+
+impl FullTextIndexTemplate {
+    fn from_config_full_text_index(
+        full_text_index: &FullTextIndex,
+        entity: &Entity,
+        schema: &Schema,
+    ) -> Result<Self> {
+        //The generated column is built from the db columns of the listed fields
+        let fields = full_text_index
+            .fields
+            .iter()
+            .map(|field_name| {
+                entity
+                    .fields
+                    .get(field_name)
+                    .ok_or_else(|| anyhow!("Unexpected, fulltext field {field_name} is missing"))?
+                    .get_db_column_name(schema)
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(FullTextIndexTemplate {
+            name: full_text_index.name.clone(),
+            language: full_text_index.language.clone(),
+            fields,
+        })
+    }
+}
+
 #[derive(Serialize, Debug, PartialEq, Clone)]
 pub struct EntityRecordTypeTemplate {
     pub name: CapitalizedOptions,
+    pub table_name: String,
     pub postgres_fields: Vec<postgres_types::Field>,
     pub composite_indices: Vec<Vec<String>>,
+    pub full_text_indices: Vec<FullTextIndexTemplate>,
     pub derived_fields: Vec<DerivedFieldTemplate>,
     pub params: Vec<EntityParamTypeTemplate>,
     pub index_groups: Vec<EntityIndexParamGroup>,
     pub relational_params: FilteredTemplateLists<EntityRelationalTypesTemplate>,
     pub filtered_params: FilteredTemplateLists<EntityParamTypeTemplate>,
+    pub description: Option<String>,
 }
 
 impl EntityRecordTypeTemplate {
@@ -309,20 +412,89 @@ impl EntityRecordTypeTemplate {
         let derived_fields = entity
             .get_fields()
             .iter()
-            .filter_map(|gql_field| gql_field.get_derived_from_field())
+            .filter_map(|gql_field| gql_field.get_derived_from_field(&config.schema))
             .collect();
 
         let composite_indices = entity.get_composite_indices();
 
+        let full_text_indices = entity
+            .full_text_indexes
+            .iter()
+            .map(|full_text_index| {
+                FullTextIndexTemplate::from_config_full_text_index(
+                    full_text_index,
+                    entity,
+                    &config.schema,
+                )
+            })
+            .collect::<Result<_>>()?;
+
         Ok(EntityRecordTypeTemplate {
             name: entity.name.to_capitalized_options(),
+            table_name: entity.get_table_name(),
             postgres_fields,
             derived_fields,
             composite_indices,
+            full_text_indices,
             params,
             index_groups,
             relational_params,
             filtered_params,
+            description: entity.description.clone(),
+        })
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct AggregationIntervalTemplate {
+    pub interval: String,
+    pub buckets_query: String,
+    pub rollup_query: String,
+    pub backfill_query: String,
+}
+
+///Template for an @aggregation entity, the buckets touched by its source entity
+///are recomputed with each batch
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct AggregationTemplate {
+    pub name: CapitalizedOptions,
+    pub source: CapitalizedOptions,
+    pub intervals: Vec<AggregationIntervalTemplate>,
+}
+
+impl AggregationTemplate {
+    fn from_config_entity(
+        entity: &Entity,
+        aggregation: &Aggregation,
+        schema: &Schema,
+    ) -> Result<Self> {
+        let intervals = aggregation
+            .intervals
+            .iter()
+            .map(|interval| {
+                Ok(AggregationIntervalTemplate {
+                    interval: interval.to_string(),
+                    buckets_query: aggregation
+                        .get_buckets_query(interval, entity, schema)
+                        .context(format!("Failed building {interval} buckets query"))?,
+                    rollup_query: aggregation
+                        .get_rollup_query(interval, entity, schema)
+                        .context(format!("Failed building {interval} rollup query"))?,
+                    backfill_query: aggregation
+                        .get_backfill_query(interval, entity, schema)
+                        .context(format!("Failed building {interval} backfill query"))?,
+                })
+            })
+            .collect::<Result<_>>()
+            .context(format!(
+                "Failed templating aggregation of entity: {}",
+                entity.name
+            ))?;
+
+        Ok(AggregationTemplate {
+            name: entity.name.to_capitalized_options(),
+            source: aggregation.source.to_capitalized_options(),
+            intervals,
         })
     }
 }
@@ -708,11 +880,17 @@ let eventSignatures = [{}]"#,
                         contract.name
                     ))?;
 
+                //Required relative to the generated module, since the abi path has the
+                //project root as its prefix which can be absolute
+                let abi_path_relative_to_generated_src =
+                    diff_paths(&abi.path_buf, project_paths.generated.join("src"))
+                        .ok_or_else(|| anyhow!("Failed diffing abi path to generated src"))?;
+
                 format!(
-                    "let abi = Fuel.transpileAbi(%raw(`require(\"../../{}\")`))\n{}\n{}",
+                    "let abi = Fuel.transpileAbi(%raw(`require(\"{}\")`))\n{}\n{}",
                     // If we decide to inline the abi, instead of using require
                     // we need to remember that abi might contain ` and we should escape it
-                    abi.path_buf.to_string_lossy(),
+                    abi_path_relative_to_generated_src.to_string_lossy(),
                     all_abi_type_declarations.to_string(),
                     all_abi_type_declarations.to_rescript_schema()
                 )
@@ -919,12 +1097,47 @@ struct SelectedFieldTemplate {
     default_value_rescript: String,
 }
 
+///An entity type re-exported from index.d.ts with its schema description as a doc comment
+#[derive(Serialize, Debug, PartialEq, Clone)]
+struct DocumentedEntityTypeTemplate {
+    name: String,
+    description: String,
+}
+
+impl DocumentedEntityTypeTemplate {
+    ///Entities sharing a name with a contract are skipped since index.d.ts already
+    ///exports the contract under that name
+    fn from_entities(
+        entities: &[EntityRecordTypeTemplate],
+        contracts: &[ContractTemplate],
+    ) -> Vec<Self> {
+        entities
+            .iter()
+            .filter(|entity| {
+                !contracts
+                    .iter()
+                    .any(|contract| contract.name.capitalized == entity.name.original)
+            })
+            .filter_map(|entity| {
+                entity.description.clone().map(|description| Self {
+                    name: entity.name.original.clone(),
+                    description,
+                })
+            })
+            .collect()
+    }
+}
+
 #[derive(Serialize)]
 pub struct ProjectTemplate {
     project_name: String,
     codegen_contracts: Vec<ContractTemplate>,
     entities: Vec<EntityRecordTypeTemplate>,
+    documented_entity_types: Vec<DocumentedEntityTypeTemplate>,
     gql_enums: Vec<GraphQlEnumTypeTemplate>,
+    polymorphic_types: Vec<GraphQlPolymorphicTypeTemplate>,
+    ts_types: TsTypesTemplate,
+    aggregations: Vec<AggregationTemplate>,
     chain_configs: Vec<NetworkConfigTemplate>,
     codegen_out_path: String,
     persisted_state: PersistedStateJsonString,
@@ -932,31 +1145,135 @@ pub struct ProjectTemplate {
     should_rollback_on_reorg: bool,
     should_save_full_history: bool,
     enable_raw_events: bool,
+    enable_hasura: bool,
+    ///Adds the zod dependency imported by the Zod validators
+    enable_zod_validators: bool,
+    compose_project_name: String,
+    postgres_port: u16,
+    hasura_port: u16,
     has_multiple_events: bool,
     field_selection: FieldSelection,
     is_evm_ecosystem: bool,
     is_fuel_ecosystem: bool,
     //Used for the package.json reference to handlers in generated
     relative_path_to_root_from_generated: String,
+    ///Rendered into its own package with its own templates, None when disabled
+    #[serde(skip)]
+    graphql_client: Option<GraphqlClientTemplate>,
+    ///Rendered into the validators dir with its own templates, None when no format is
+    ///enabled
+    #[serde(skip)]
+    validators: Option<ValidatorsTemplate>,
 }
 
 impl ProjectTemplate {
-    pub fn generate_templates(&self, project_paths: &ParsedProjectPaths) -> Result<()> {
+    ///Writes the dynamic templates with an output in the codegen scope
+    pub fn generate_templates(
+        &self,
+        project_paths: &ParsedProjectPaths,
+        codegen_scope: &CodegenScope,
+    ) -> Result<()> {
+        let rendered_files = self.render_templates_filtered(project_paths, |output_path| {
+            codegen_scope.includes_output(output_path)
+        })?;
+        for (path, contents) in rendered_files {
+            let output_path = project_paths.generated.join(&path);
+            if let Some(parent) = output_path.parent() {
+                fs::create_dir_all(parent)
+                    .context(format!("Failed creating dir {}", parent.display()))?;
+            }
+            fs::write(&output_path, contents)
+                .context(format!("Failed writing {}", output_path.display()))?;
+        }
+        Ok(())
+    }
+
+    ///Gets the paths relative to the generated dir of all the static and dynamic files
+    ///that codegen writes
+    pub fn get_output_paths(&self, project_paths: &ParsedProjectPaths) -> Result<Vec<PathBuf>> {
+        let overrides = TemplateOverrides::new(&project_paths.project_root);
+        let static_paths = TemplateDirs::new()
+            .get_codegen_static_dir()?
+            .get_files_with_overrides(&overrides)?
+            .into_iter()
+            .map(|(path, _)| path);
+        let dynamic_paths = self
+            .render_templates(project_paths)?
+            .into_iter()
+            .map(|(path, _)| path);
+        Ok(static_paths.chain(dynamic_paths).unique().collect())
+    }
+
+    ///Renders all dynamic templates without writing them, paired with their path
+    ///relative to the generated dir
+    pub fn render_templates(
+        &self,
+        project_paths: &ParsedProjectPaths,
+    ) -> Result<Vec<(PathBuf, String)>> {
+        self.render_templates_filtered(project_paths, |_| true)
+    }
+
+    ///Renders the dynamic templates whose output path relative to the generated dir
+    ///satisfies the predicate
+    fn render_templates_filtered(
+        &self,
+        project_paths: &ParsedProjectPaths,
+        should_render: impl Fn(&Path) -> bool,
+    ) -> Result<Vec<(PathBuf, String)>> {
         let template_dirs = TemplateDirs::new();
         let dynamic_codegen_dir = template_dirs
             .get_codegen_dynamic_dir()
             .context("Failed getting dynamic codegen dir")?;
 
+        let overrides = TemplateOverrides::new(&project_paths.project_root);
         let hbs =
-            HandleBarsDirGenerator::new(&dynamic_codegen_dir, &self, &project_paths.generated);
-        hbs.generate_hbs_templates()?;
+            HandleBarsDirGenerator::new(&dynamic_codegen_dir, &self, &project_paths.generated)
+                .with_overrides(&overrides)?;
+        let mut rendered_files = hbs.render_hbs_templates_filtered(&should_render)?;
+
+        if let Some(graphql_client) = &self.graphql_client {
+            let graphql_client_dir = template_dirs
+                .get_graphql_client_dynamic_dir()
+                .context("Failed getting dynamic graphql client dir")?;
+            let output_dir = project_paths.generated.join(GRAPHQL_CLIENT_DIR);
+            let hbs = HandleBarsDirGenerator::new(&graphql_client_dir, graphql_client, &output_dir)
+                .with_overrides(&overrides)?;
+            rendered_files.extend(
+                hbs.render_hbs_templates_filtered(|output_path| {
+                    should_render(&Path::new(GRAPHQL_CLIENT_DIR).join(output_path))
+                })?
+                .into_iter()
+                .map(|(path, contents)| (Path::new(GRAPHQL_CLIENT_DIR).join(path), contents)),
+            );
+        }
 
-        Ok(())
+        if let Some(validators) = &self.validators {
+            let validators_dir = template_dirs
+                .get_validators_dynamic_dir()
+                .context("Failed getting dynamic validators dir")?;
+            let output_dir = project_paths.generated.join(VALIDATORS_DIR);
+            let hbs = HandleBarsDirGenerator::new(&validators_dir, validators, &output_dir)
+                .with_overrides(&overrides)?;
+            rendered_files.extend(
+                hbs.render_hbs_templates_filtered(|output_path| {
+                    validators.includes_output(output_path)
+                        && should_render(&Path::new(VALIDATORS_DIR).join(output_path))
+                })?
+                .into_iter()
+                .map(|(path, contents)| (Path::new(VALIDATORS_DIR).join(path), contents)),
+            );
+        }
+
+        Ok(rendered_files)
     }
 
     pub fn from_config(cfg: &SystemConfig, project_paths: &ParsedProjectPaths) -> Result<Self> {
         //TODO: make this a method in path handlers
-        let gitignore_generated_path = project_paths.generated.join("*");
+        //Relative to the project root so that the output is the same on any machine
+        let gitignore_generated_path =
+            diff_paths(&project_paths.generated, &project_paths.project_root)
+                .ok_or_else(|| anyhow!("Failed diffing generated path to project root"))?
+                .join("*");
         let gitignore_path_str = gitignore_generated_path
             .to_str()
             .ok_or_else(|| anyhow!("invalid codegen path"))?
@@ -978,6 +1295,9 @@ impl ProjectTemplate {
             .collect::<Result<_>>()
             .context("Failed generating entity template types")?;
 
+        let documented_entity_types =
+            DocumentedEntityTypeTemplate::from_entities(&entities, &codegen_contracts);
+
         let gql_enums: Vec<GraphQlEnumTypeTemplate> = cfg
             .get_gql_enums()
             .iter()
@@ -985,6 +1305,31 @@ impl ProjectTemplate {
             .collect::<Result<_>>()
             .context("Failed generating enum template types")?;
 
+        let polymorphic_types: Vec<GraphQlPolymorphicTypeTemplate> =
+            cfg.get_gql_interfaces()
+                .iter()
+                .map(|interface| {
+                    GraphQlPolymorphicTypeTemplate::from_config_interface(interface, &cfg.schema)
+                })
+                .chain(cfg.get_gql_unions().iter().map(|union| {
+                    GraphQlPolymorphicTypeTemplate::from_config_union(union, &cfg.schema)
+                }))
+                .sorted_by_key(|template| template.name.original.clone())
+                .collect();
+
+        let ts_types = TsTypesTemplate::new(cfg, &entities, &gql_enums, &polymorphic_types);
+
+        let aggregations: Vec<AggregationTemplate> = cfg
+            .get_entities()
+            .iter()
+            .filter_map(|entity| {
+                entity.aggregation.as_ref().map(|aggregation| {
+                    AggregationTemplate::from_config_entity(entity, aggregation, &cfg.schema)
+                })
+            })
+            .collect::<Result<_>>()
+            .context("Failed generating aggregation template types")?;
+
         let chain_configs: Vec<NetworkConfigTemplate> = cfg
             .get_networks()
             .iter()
@@ -1022,11 +1367,33 @@ impl ProjectTemplate {
 
         let field_selection = FieldSelection::from_config_field_selection(&cfg.field_selection);
 
+        let graphql_client = if cfg.enable_graphql_client {
+            Some(
+                GraphqlClientTemplate::from_config(cfg)
+                    .context("Failed generating graphql client template")?,
+            )
+        } else {
+            None
+        };
+
+        let validators = if cfg.validator_formats.is_empty() {
+            None
+        } else {
+            Some(
+                ValidatorsTemplate::new(cfg, &entities, &gql_enums)
+                    .context("Failed generating validators template")?,
+            )
+        };
+
         Ok(ProjectTemplate {
             project_name: cfg.name.clone(),
             codegen_contracts,
             entities,
+            documented_entity_types,
             gql_enums,
+            polymorphic_types,
+            ts_types,
+            aggregations,
             chain_configs,
             codegen_out_path: gitignore_path_str,
             persisted_state,
@@ -1034,12 +1401,19 @@ impl ProjectTemplate {
             should_rollback_on_reorg: cfg.rollback_on_reorg,
             should_save_full_history: cfg.save_full_history,
             enable_raw_events: cfg.enable_raw_events,
+            enable_hasura: cfg.enable_hasura,
+            enable_zod_validators: cfg.validator_formats.contains(&ValidatorFormat::Zod),
+            compose_project_name: cfg.local_services.compose_project_name.clone(),
+            postgres_port: cfg.local_services.postgres_port,
+            hasura_port: cfg.local_services.hasura_port,
             has_multiple_events,
             field_selection,
             is_evm_ecosystem: cfg.ecosystem == Ecosystem::Evm,
             is_fuel_ecosystem: cfg.ecosystem == Ecosystem::Fuel,
             //Used for the package.json reference to handlers in generated
             relative_path_to_root_from_generated,
+            graphql_client,
+            validators,
         })
     }
 }
@@ -1407,4 +1781,78 @@ let getTopicSelection = (eventFilters) => eventFilters->SingleOrMultiple.normali
 
         assert_eq!(&expected_event_template, new_gavatar_event_template);
     }
+
+    #[test]
+    fn rendered_templates_are_the_same_on_any_machine() {
+        let render_templates = |project_root: &str, configs_file_name: &str| {
+            let config = format!("configs/{}", configs_file_name);
+            let project_paths =
+                ParsedProjectPaths::new(project_root, "generated/", &config).expect("Parsed paths");
+            let config = SystemConfig::parse_from_project_files(&project_paths)
+                .expect("Deserialized yml config should be parseable");
+            let project_template = super::ProjectTemplate::from_config(&config, &project_paths)
+                .expect("should be able to get project template");
+            //The template data is compared too since template overrides can use any of it
+            (
+                serde_json::to_value(&project_template).expect("should serialize template"),
+                project_template
+                    .render_templates(&project_paths)
+                    .expect("should be able to render templates"),
+            )
+        };
+
+        //Parsing again iterates the hash maps of the config in a different order, and the
+        //project root is relative in one and absolute in the other
+        for configs_file_name in ["config2.yaml", "factory-contract-config.yaml"] {
+            assert_eq!(
+                render_templates("test", configs_file_name),
+                render_templates(&get_test_path_string_helper(), configs_file_name),
+                "Rendered templates of {configs_file_name} differ"
+            );
+        }
+    }
+
+    #[test]
+    fn templates_without_hasura() {
+        let project_paths = ParsedProjectPaths::new(
+            &get_test_path_string_helper(),
+            "generated/",
+            "configs/config1.yaml",
+        )
+        .expect("Parsed paths");
+        let mut project_template = get_project_template_helper("config1.yaml");
+        project_template.enable_hasura = false;
+        let rendered_templates = project_template
+            .render_templates(&project_paths)
+            .expect("should be able to render templates");
+        let get_rendered = |path: &str| {
+            rendered_templates
+                .iter()
+                .find(|(output_path, _)| output_path == Path::new(path))
+                .map(|(_, content)| content.as_str())
+                .unwrap_or_else(|| panic!("{path} should be rendered"))
+        };
+
+        let docker_compose: serde_yaml::Value =
+            serde_yaml::from_str(get_rendered("docker-compose.yaml"))
+                .expect("docker-compose.yaml should be valid yaml");
+        let services: Vec<_> = docker_compose["services"]
+            .as_mapping()
+            .expect("docker-compose.yaml should have services")
+            .keys()
+            .filter_map(|service| service.as_str())
+            .collect();
+        assert_eq!(services, vec!["envio-postgres"]);
+
+        let package_json: serde_json::Value = serde_json::from_str(get_rendered("package.json"))
+            .expect("package.json should be valid json");
+        assert_eq!(
+            package_json["scripts"]["db-setup"],
+            "node -e 'require(`./src/db/Migrations.bs.js`).setupDb(true, false)'"
+        );
+        assert_eq!(
+            package_json["scripts"]["db-up"],
+            "node -e 'require(`./src/db/Migrations.bs.js`).runUpMigrations(true, false)'"
+        );
+    }
 }
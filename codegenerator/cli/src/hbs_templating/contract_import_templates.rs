@@ -363,7 +363,9 @@ impl Into<Field> for Param {
     fn into(self) -> Field {
         Field {
             name: self.entity_key.original,
+            db_name: None,
             field_type: self.graphql_type,
+            description: None,
         }
     }
 }
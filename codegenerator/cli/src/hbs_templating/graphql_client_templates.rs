@@ -0,0 +1,243 @@
+use crate::config_parsing::{
+    entity_parsing::{Entity, GraphQLEnum, Schema},
+    postgres_types::{self, Primitive},
+    system_config::SystemConfig,
+};
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+///Dir of the client package in the generated dir
+pub const GRAPHQL_CLIENT_DIR: &str = "graphql-client";
+
+///Gets the TypeScript type of a column value as returned by Hasura. Numeric and double
+///precision values are strings since Hasura is started with stringified numeric types.
+fn to_ts_type(primitive: &Primitive) -> String {
+    match primitive {
+        Primitive::Boolean => "boolean".to_string(),
+        Primitive::Text | Primitive::Timestamp => "string".to_string(),
+        Primitive::Integer | Primitive::Serial => "number".to_string(),
+        Primitive::Numeric(_) | Primitive::DoublePrecision => "string".to_string(),
+        Primitive::Json => "Json".to_string(),
+        Primitive::Enum(enum_name) => enum_name.clone(),
+        Primitive::Array(primitive) => format!("{}[]", to_ts_type(primitive)),
+    }
+}
+
+///Gets the TypeScript type of the filter of a non array column, matching the
+///comparison operators Hasura has for its type
+fn to_ts_comparison_type(primitive: &Primitive) -> String {
+    match primitive {
+        Primitive::Text => "TextComparisonExp".to_string(),
+        Primitive::Json => "JsonComparisonExp".to_string(),
+        primitive => format!("ComparisonExp<{}>", to_ts_type(primitive)),
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct GraphqlClientColumnTemplate {
+    ///Name of the column, which is also the name of the field in the Hasura api
+    name: String,
+    ts_type: String,
+    ts_comparison_type: String,
+    ///Hasura can't order by json or array columns
+    is_orderable: bool,
+    description: Option<String>,
+}
+
+impl GraphqlClientColumnTemplate {
+    fn from_postgres_field(field: &postgres_types::Field) -> Self {
+        let name = match (&field.db_field_name, &field.linked_entity) {
+            (Some(db_field_name), _) => db_field_name.clone(),
+            (None, Some(_)) => format!("{}_id", field.field_name),
+            (None, None) => field.field_name.clone(),
+        };
+
+        let element_type = to_ts_type(&field.field_type);
+        let (ts_type, ts_comparison_type) = if field.is_array {
+            (
+                format!("{element_type}[]"),
+                format!("ArrayComparisonExp<{element_type}>"),
+            )
+        } else {
+            (element_type, to_ts_comparison_type(&field.field_type))
+        };
+
+        Self {
+            name,
+            ts_type: if field.is_nullable {
+                format!("{ts_type} | null")
+            } else {
+                ts_type
+            },
+            ts_comparison_type,
+            is_orderable: !field.is_array && field.field_type != Primitive::Json,
+            description: field.description.clone(),
+        }
+    }
+}
+
+///A Hasura relationship to the table of another entity
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct GraphqlClientRelationshipTemplate {
+    name: String,
+    table_name: String,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct GraphqlClientEntityTemplate {
+    ///Name of the table, which Hasura uses for the query and the names of its types
+    table_name: String,
+    columns: Vec<GraphqlClientColumnTemplate>,
+    ///Linked entity fields, which Hasura can filter and order by
+    object_relationships: Vec<GraphqlClientRelationshipTemplate>,
+    ///Derived from fields, which Hasura can only filter by
+    array_relationships: Vec<GraphqlClientRelationshipTemplate>,
+    description: Option<String>,
+}
+
+impl GraphqlClientEntityTemplate {
+    fn from_config_entity(entity: &Entity, schema: &Schema) -> Result<Self> {
+        let mut columns = vec![];
+        let mut object_relationships = vec![];
+        let mut array_relationships = vec![];
+
+        for field in entity.get_fields() {
+            match field.get_postgres_field(schema, entity)? {
+                Some(postgres_field) => {
+                    columns.push(GraphqlClientColumnTemplate::from_postgres_field(
+                        &postgres_field,
+                    ));
+                    if let Some(table_name) = postgres_field.linked_entity {
+                        object_relationships.push(GraphqlClientRelationshipTemplate {
+                            name: field.name.clone(),
+                            table_name,
+                        });
+                    }
+                }
+                None => {
+                    if let Some(derived_field) = field.get_derived_from_field(schema) {
+                        array_relationships.push(GraphqlClientRelationshipTemplate {
+                            name: derived_field.field_name,
+                            table_name: derived_field.derived_from_entity,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            table_name: entity.get_table_name(),
+            columns,
+            object_relationships,
+            array_relationships,
+            description: entity.description.clone(),
+        })
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct GraphqlClientEnumTemplate {
+    name: String,
+    ///The values as a union of string literals
+    ts_type: String,
+}
+
+impl GraphqlClientEnumTemplate {
+    fn from_config_gql_enum(gql_enum: &GraphQLEnum) -> Self {
+        let ts_type = gql_enum
+            .values
+            .iter()
+            .map(|value| format!("\"{value}\""))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        Self {
+            name: gql_enum.name.clone(),
+            ts_type,
+        }
+    }
+}
+
+///Template of the TypeScript client package for the Hasura api of the indexer
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct GraphqlClientTemplate {
+    entities: Vec<GraphqlClientEntityTemplate>,
+    enums: Vec<GraphqlClientEnumTemplate>,
+    ///Port of the Hasura envio dev starts, used for the default url
+    hasura_port: u16,
+}
+
+impl GraphqlClientTemplate {
+    pub fn from_config(cfg: &SystemConfig) -> Result<Self> {
+        let entities = cfg
+            .get_entities()
+            .into_iter()
+            .map(|entity| {
+                GraphqlClientEntityTemplate::from_config_entity(entity, &cfg.schema).context(
+                    format!("Failed templating graphql client of entity {}", entity.name),
+                )
+            })
+            .collect::<Result<_>>()?;
+
+        let enums = cfg
+            .get_gql_enums()
+            .into_iter()
+            .map(GraphqlClientEnumTemplate::from_config_gql_enum)
+            .collect();
+
+        Ok(Self {
+            entities,
+            enums,
+            hasura_port: cfg.local_services.hasura_port,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GraphqlClientColumnTemplate, Primitive};
+    use crate::config_parsing::postgres_types;
+
+    fn get_postgres_field(field_type: Primitive) -> postgres_types::Field {
+        postgres_types::Field {
+            field_name: "owner".to_string(),
+            db_field_name: None,
+            linked_entity: None,
+            is_index: false,
+            is_primary_key: false,
+            is_nullable: false,
+            is_array: false,
+            field_type,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn columns_match_hasura_types() {
+        let mut field = get_postgres_field(Primitive::Text);
+        field.linked_entity = Some("User".to_string());
+        field.is_nullable = true;
+        assert_eq!(
+            GraphqlClientColumnTemplate::from_postgres_field(&field),
+            GraphqlClientColumnTemplate {
+                name: "owner_id".to_string(),
+                ts_type: "string | null".to_string(),
+                ts_comparison_type: "TextComparisonExp".to_string(),
+                is_orderable: true,
+                description: None,
+            }
+        );
+
+        let mut field = get_postgres_field(Primitive::Numeric(None));
+        field.is_array = true;
+        assert_eq!(
+            GraphqlClientColumnTemplate::from_postgres_field(&field),
+            GraphqlClientColumnTemplate {
+                name: "owner".to_string(),
+                ts_type: "string[]".to_string(),
+                ts_comparison_type: "ArrayComparisonExp<string>".to_string(),
+                is_orderable: false,
+                description: None,
+            }
+        );
+    }
+}
@@ -1,16 +1,65 @@
-use crate::{project_paths::path_utils::normalize_path, template_dirs::RelativeDir};
+use crate::{
+    project_paths::path_utils::normalize_path,
+    template_dirs::{RelativeDir, TemplateOverrides},
+};
 use anyhow::{anyhow, Context};
 use handlebars::{handlebars_helper, Handlebars};
 use include_dir::DirEntry;
 use serde::Serialize;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+///Formats a schema description as a doc comment, continuation lines are
+///indented by the given amount to line up with the commented item
+fn to_doc_comment(description: &str, indent: usize) -> String {
+    //Comment tokens in the description would otherwise open or close comments
+    let lines: Vec<String> = description
+        .trim()
+        .lines()
+        .map(|line| line.trim_end().replace("/*", "/\\*").replace("*/", "*\\/"))
+        .collect();
+
+    match lines.as_slice() {
+        [line] => format!("/** {line} */"),
+        lines => {
+            let padding = " ".repeat(indent);
+            let body = lines
+                .iter()
+                .map(|line| {
+                    format!(
+                        "{padding} *{}{line}",
+                        if line.is_empty() { "" } else { " " }
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("/**\n{body}\n{padding} */")
+        }
+    }
+}
+
+///Formats a value as a double quoted ReScript string literal
+fn to_res_string(value: &str) -> String {
+    let escaped: String = value
+        .chars()
+        .filter(|c| *c != '\r')
+        .map(|c| match c {
+            '\\' => "\\\\".to_string(),
+            '"' => "\\\"".to_string(),
+            '\n' => "\\n".to_string(),
+            '\t' => "\\t".to_string(),
+            c => c.to_string(),
+        })
+        .collect();
+    format!("\"{escaped}\"")
+}
 
 pub struct HandleBarsDirGenerator<'a, T: Serialize> {
     handlebars: handlebars::Handlebars<'a>,
     templates_dir: &'a RelativeDir<'a>,
     rs_template: &'a T,
     output_dir: &'a Path,
+    overrides: Option<&'a TemplateOverrides>,
 }
 
 impl<'a, T: Serialize> HandleBarsDirGenerator<'a, T> {
@@ -29,18 +78,43 @@ impl<'a, T: Serialize> HandleBarsDirGenerator<'a, T> {
         });
         handlebars.register_helper("vec_to_array", Box::new(vec_to_array_helper));
 
+        handlebars_helper!(doc_comment_helper: |description: str, {indent: u64 = 0}| {
+          to_doc_comment(description, indent as usize)
+        });
+        handlebars.register_helper("doc_comment", Box::new(doc_comment_helper));
+
+        handlebars_helper!(res_string_helper: |value: str| to_res_string(value));
+        handlebars.register_helper("res_string", Box::new(res_string_helper));
+
         HandleBarsDirGenerator {
             handlebars,
             templates_dir,
             rs_template,
             output_dir,
+            overrides: None,
         }
     }
 
+    ///Renders the project local overrides instead of the embedded templates they
+    ///shadow, with the partials of the overrides registered
+    pub fn with_overrides(mut self, overrides: &'a TemplateOverrides) -> anyhow::Result<Self> {
+        for (name, partial) in overrides.get_partials()? {
+            self.handlebars
+                .register_partial(&name, partial)
+                .context(format!("Failed registering template partial {name}"))?;
+        }
+        self.overrides = Some(overrides);
+        Ok(self)
+    }
+
     fn generate_hbs_templates_internal_recursive(
         &self,
         //The relative dir in "TemplateDirs" that can be extracted
         hbs_templates_root_dir: &RelativeDir,
+        //Whether to render the template with the given output path relative to the output dir
+        should_render: &dyn Fn(&Path) -> bool,
+        //Receives each rendered file with its output path relative to the output dir
+        on_rendered: &mut dyn FnMut(PathBuf, String) -> anyhow::Result<()>,
     ) -> anyhow::Result<()> {
         for entry in hbs_templates_root_dir.entries() {
             match entry {
@@ -70,10 +144,24 @@ impl<'a, T: Serialize> HandleBarsDirGenerator<'a, T> {
                             .file_stem()
                             .ok_or_else(|| anyhow!("Could not produce filestem of {}", path_str))?;
 
-                        //Read the template file contents
-                        let file_str = file.contents_utf8().ok_or_else(|| {
-                            anyhow!("Could not produce file contents of {}", path_str)
-                        })?;
+                        let output_path = normalize_path(parent.join(file_stem));
+                        if !should_render(&output_path) {
+                            continue;
+                        }
+
+                        //Read the template file contents, preferring the project local override
+                        let override_contents = match self.overrides {
+                            Some(overrides) => overrides.get_file(path)?,
+                            None => None,
+                        };
+                        let file_str = match &override_contents {
+                            Some(contents) => std::str::from_utf8(contents).map_err(|_| {
+                                anyhow!("Template override of {} is not valid utf8", path_str)
+                            })?,
+                            None => file.contents_utf8().ok_or_else(|| {
+                                anyhow!("Could not produce file contents of {}", path_str)
+                            })?,
+                        };
 
                         //Render the template
                         let rendered_file = self
@@ -81,24 +169,7 @@ impl<'a, T: Serialize> HandleBarsDirGenerator<'a, T> {
                             .render_template(file_str, &self.rs_template)
                             .context(format!("Could not render file at {}", path_str))?;
 
-                        //Setup output directory
-                        let output_dir_path = normalize_path(self.output_dir.join(parent));
-                        let output_dir_path_str = output_dir_path.to_str().ok_or({
-                            anyhow!("Could not cast output path to str in generate_hbs_templates")
-                        })?;
-
-                        //ensure the dir exists or is created
-                        fs::create_dir_all(&output_dir_path).context(format!(
-                            "create_dir_all failed at {}",
-                            &output_dir_path_str,
-                        ))?;
-
-                        //append the filename
-                        let output_file_path = output_dir_path.join(file_stem);
-
-                        //Write the file
-                        fs::write(&output_file_path, rendered_file)
-                            .context(format!("file write failed at {}", &output_dir_path_str))?;
+                        on_rendered(output_path, rendered_file)?;
                     }
                 }
                 DirEntry::Dir(dir) => Self::generate_hbs_templates_internal_recursive(
@@ -106,12 +177,100 @@ impl<'a, T: Serialize> HandleBarsDirGenerator<'a, T> {
                     //Create a child when recursing so that there is always a reference
                     //to the relative path this template dir is at
                     &hbs_templates_root_dir.new_child(dir),
+                    should_render,
+                    on_rendered,
                 )?,
             }
         }
         Ok(())
     }
+    fn write_rendered_file(
+        &self,
+        output_path: PathBuf,
+        rendered_file: String,
+    ) -> anyhow::Result<()> {
+        let output_file_path = normalize_path(self.output_dir.join(output_path));
+
+        //Setup output directory
+        let output_dir_path = output_file_path
+            .parent()
+            .ok_or_else(|| anyhow!("Could not produce parent of {:?}", output_file_path))?;
+        let output_dir_path_str = output_dir_path.to_str().ok_or_else(|| {
+            anyhow!("Could not cast output path to str in generate_hbs_templates")
+        })?;
+
+        //ensure the dir exists or is created
+        fs::create_dir_all(output_dir_path)
+            .context(format!("create_dir_all failed at {}", &output_dir_path_str))?;
+
+        //Write the file
+        fs::write(&output_file_path, rendered_file)
+            .context(format!("file write failed at {}", &output_dir_path_str))
+    }
+
     pub fn generate_hbs_templates(&self) -> anyhow::Result<()> {
-        Self::generate_hbs_templates_internal_recursive(self, self.templates_dir)
+        self.generate_hbs_templates_filtered(|_| true)
+    }
+
+    ///Only renders the templates whose output path, relative to the output dir,
+    ///satisfies the given predicate
+    pub fn generate_hbs_templates_filtered(
+        &self,
+        should_render: impl Fn(&Path) -> bool,
+    ) -> anyhow::Result<()> {
+        Self::generate_hbs_templates_internal_recursive(
+            self,
+            self.templates_dir,
+            &should_render,
+            &mut |output_path, rendered_file| self.write_rendered_file(output_path, rendered_file),
+        )
+    }
+
+    ///Renders the templates whose output path, relative to the output dir, satisfies
+    ///the given predicate without writing them, returning each file with its output path
+    pub fn render_hbs_templates_filtered(
+        &self,
+        should_render: impl Fn(&Path) -> bool,
+    ) -> anyhow::Result<Vec<(PathBuf, String)>> {
+        let mut rendered_files = vec![];
+        Self::generate_hbs_templates_internal_recursive(
+            self,
+            self.templates_dir,
+            &should_render,
+            &mut |output_path, rendered_file| {
+                rendered_files.push((output_path, rendered_file));
+                Ok(())
+            },
+        )?;
+        Ok(rendered_files)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{to_doc_comment, to_res_string};
+
+    #[test]
+    fn single_line_doc_comment() {
+        assert_eq!(
+            to_doc_comment("  Owner of the vault\n", 2),
+            "/** Owner of the vault */"
+        );
+    }
+
+    #[test]
+    fn multi_line_doc_comment() {
+        assert_eq!(
+            to_doc_comment("Owner of the vault\n\nSet on creation */", 2),
+            "/**\n   * Owner of the vault\n   *\n   * Set on creation *\\/\n   */"
+        );
+    }
+
+    #[test]
+    fn res_string_is_escaped() {
+        assert_eq!(
+            to_res_string("The \"owner\"\r\nC:\\vault"),
+            "\"The \\\"owner\\\"\\nC:\\\\vault\""
+        );
     }
 }
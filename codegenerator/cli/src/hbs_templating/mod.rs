@@ -1,4 +1,7 @@
 pub mod codegen_templates;
 pub mod contract_import_templates;
+pub mod graphql_client_templates;
 pub mod hbs_dir_generator;
 pub mod init_templates;
+pub mod ts_types_templates;
+pub mod validators_templates;
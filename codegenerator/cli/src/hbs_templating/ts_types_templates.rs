@@ -0,0 +1,276 @@
+use super::codegen_templates::{
+    EntityRecordTypeTemplate, GraphQlEnumTypeTemplate, GraphQlPolymorphicTypeTemplate,
+};
+use crate::{
+    config_parsing::{
+        event_parsing::abi_to_rescript_type,
+        system_config::{self, EventKind, SelectedField, SystemConfig},
+    },
+    rescript_types::{RescriptRecordField, RescriptTypeExpr},
+    utils::text::{Capitalize, CaseOptions},
+};
+use itertools::Itertools;
+use serde::Serialize;
+
+///A record field of a declared type, with its doc comment
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct TsFieldTemplate {
+    name: String,
+    ts_type: String,
+    description: Option<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct TsEnumTemplate {
+    name: String,
+    ///The values as a union of string literals
+    ts_type: String,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct TsEntityTemplate {
+    ///Name of the exported type, which is the name of the entity in the schema
+    name: String,
+    ///Name of the ReScript module, used as the prefix of the other types of the entity
+    module_name: String,
+    fields: Vec<TsFieldTemplate>,
+    ///Fields that can be loaded with getWhere, keyed by their column name
+    indexed_fields: Vec<TsFieldTemplate>,
+    description: Option<String>,
+}
+
+impl TsEntityTemplate {
+    fn from_entity_template(entity: &EntityRecordTypeTemplate) -> Self {
+        let fields = entity
+            .params
+            .iter()
+            .filter(|param| !param.is_derived_from)
+            .map(|param| TsFieldTemplate {
+                name: param.get_record_field_name(),
+                ts_type: param.res_type.to_ts_type(),
+                description: param.description.clone(),
+            })
+            .collect();
+
+        let indexed_fields = entity
+            .params
+            .iter()
+            .filter(|param| param.is_queryable_field)
+            .map(|param| TsFieldTemplate {
+                name: if param.is_entity_field {
+                    format!("{}_id", param.field_name.original)
+                } else {
+                    param.field_name.original.clone()
+                },
+                ts_type: param.res_type.to_ts_type(),
+                description: None,
+            })
+            .collect();
+
+        Self {
+            name: entity.name.original.clone(),
+            module_name: entity.name.capitalized.clone(),
+            fields,
+            indexed_fields,
+            description: entity.description.clone(),
+        }
+    }
+}
+
+///An interface or union, which is a variant of the implementing entities
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct TsPolymorphicTemplate {
+    name: String,
+    ts_type: String,
+    ///Name of the enum type of the implementing entities
+    entity_type: String,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct TsEventTemplate {
+    name: String,
+    params_type: String,
+    filter_type: String,
+}
+
+impl TsEventTemplate {
+    fn from_config_event(event: &system_config::Event) -> Self {
+        let params_type = match event.get_params_type_expr() {
+            Some(type_expr) => type_expr.to_ts_type(),
+            //The types of logged data are declared from the contract abi in the
+            //ReScript code only
+            None => "unknown".to_string(),
+        };
+
+        let filter_fields = match &event.kind {
+            EventKind::Params(params) => params
+                .iter()
+                .filter(|param| param.indexed)
+                .map(|param| {
+                    format!(
+                        "readonly {}?: SingleOrMultiple<{}>",
+                        param.name,
+                        abi_to_rescript_type(&param.into()).to_ts_type()
+                    )
+                })
+                .join("; "),
+            EventKind::Fuel(_) => String::new(),
+        };
+        let filter_type = if filter_fields.is_empty() {
+            "{}".to_string()
+        } else {
+            format!("{{ {filter_fields} }}")
+        };
+
+        Self {
+            name: event.name.capitalize(),
+            params_type,
+            filter_type,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct TsContractTemplate {
+    name: String,
+    events: Vec<TsEventTemplate>,
+}
+
+///Template of the TypeScript declarations of the handler api, rendered from the config
+///and schema so that they don't depend on the types genType outputs when the ReScript
+///code compiles. The names match the genType exports for backwards compatibility.
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct TsTypesTemplate {
+    enums: Vec<TsEnumTemplate>,
+    entities: Vec<TsEntityTemplate>,
+    polymorphic_types: Vec<TsPolymorphicTemplate>,
+    contracts: Vec<TsContractTemplate>,
+    transaction_type: String,
+    block_type: String,
+}
+
+fn get_selected_fields_ts_type(fields: &[SelectedField]) -> String {
+    RescriptTypeExpr::Record(
+        fields
+            .iter()
+            .map(|field| {
+                let name: CaseOptions = field.name.clone().into();
+                RescriptRecordField::new(name.camel, field.data_type.clone())
+            })
+            .collect(),
+    )
+    .to_ts_type()
+}
+
+impl TsTypesTemplate {
+    pub fn new(
+        cfg: &SystemConfig,
+        entities: &[EntityRecordTypeTemplate],
+        gql_enums: &[GraphQlEnumTypeTemplate],
+        polymorphic_types: &[GraphQlPolymorphicTypeTemplate],
+    ) -> Self {
+        let enums = gql_enums
+            .iter()
+            .map(|gql_enum| TsEnumTemplate {
+                name: gql_enum.name.capitalized.clone(),
+                ts_type: gql_enum
+                    .params
+                    .iter()
+                    .map(|param| format!("\"{}\"", param.original))
+                    .join(" | "),
+            })
+            .collect();
+
+        let polymorphic_types = polymorphic_types
+            .iter()
+            .map(|polymorphic_type| TsPolymorphicTemplate {
+                name: polymorphic_type.name.original.clone(),
+                entity_type: polymorphic_type.entity_type_enum.capitalized.clone(),
+                ts_type: polymorphic_type
+                    .implementors
+                    .iter()
+                    .map(|implementor| {
+                        format!(
+                            "{{ readonly TAG: \"{}\"; readonly _0: {} }}",
+                            implementor.capitalized, implementor.original
+                        )
+                    })
+                    .join(" | "),
+            })
+            .collect();
+
+        let contracts = cfg
+            .get_contracts()
+            .into_iter()
+            .map(|contract| TsContractTemplate {
+                name: contract.name.capitalize(),
+                events: contract
+                    .events
+                    .iter()
+                    .map(TsEventTemplate::from_config_event)
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            enums,
+            entities: entities
+                .iter()
+                .map(TsEntityTemplate::from_entity_template)
+                .collect(),
+            polymorphic_types,
+            contracts,
+            transaction_type: get_selected_fields_ts_type(&cfg.field_selection.transaction_fields),
+            block_type: get_selected_fields_ts_type(&cfg.field_selection.block_fields),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TsEventTemplate;
+    use crate::config_parsing::system_config::{Event, EventKind, FuelEventKind};
+    use ethers::abi::{EventParam, ParamType};
+
+    #[test]
+    fn event_ts_types_match_params() {
+        let event = Event {
+            kind: EventKind::Params(vec![
+                EventParam {
+                    name: "owner".to_string(),
+                    kind: ParamType::Address,
+                    indexed: true,
+                },
+                EventParam {
+                    name: "amounts".to_string(),
+                    kind: ParamType::Array(Box::new(ParamType::Uint(256))),
+                    indexed: false,
+                },
+            ]),
+            name: "transfer".to_string(),
+            sighash: "0x".to_string(),
+        };
+        assert_eq!(
+            TsEventTemplate::from_config_event(&event),
+            TsEventTemplate {
+                name: "Transfer".to_string(),
+                params_type: "{ readonly owner: Address; readonly amounts: bigint[] }".to_string(),
+                filter_type: "{ readonly owner?: SingleOrMultiple<Address> }".to_string(),
+            }
+        );
+
+        let event = Event {
+            kind: EventKind::Fuel(FuelEventKind::Mint),
+            name: "Mint".to_string(),
+            sighash: "0x".to_string(),
+        };
+        assert_eq!(
+            TsEventTemplate::from_config_event(&event),
+            TsEventTemplate {
+                name: "Mint".to_string(),
+                params_type: "{ readonly subId: string; readonly amount: bigint }".to_string(),
+                filter_type: "{}".to_string(),
+            }
+        );
+    }
+}
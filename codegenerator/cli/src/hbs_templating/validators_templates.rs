@@ -0,0 +1,278 @@
+use super::codegen_templates::{EntityRecordTypeTemplate, GraphQlEnumTypeTemplate};
+use crate::{
+    config_parsing::{
+        human_config::ValidatorFormat,
+        system_config::{self, SystemConfig},
+    },
+    rescript_types::{RescriptRecordField, RescriptTypeExpr},
+    utils::text::Capitalize,
+};
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use strum::IntoEnumIterator;
+
+///Dir of the validators in the generated dir
+pub const VALIDATORS_DIR: &str = "validators";
+
+impl ValidatorFormat {
+    ///Name of the file the validators of the format are rendered to, relative to the
+    ///validators dir
+    pub fn get_output_file_name(&self) -> &'static str {
+        match self {
+            Self::JsonSchema => "schema.json",
+            Self::Zod => "zod.ts",
+        }
+    }
+}
+
+///Gets the paths relative to the generated dir of the validators that aren't enabled,
+///which is the whole validators dir when no format is
+pub fn get_disabled_output_paths(formats: &[ValidatorFormat]) -> Vec<PathBuf> {
+    if formats.is_empty() {
+        return vec![PathBuf::from(VALIDATORS_DIR)];
+    }
+    ValidatorFormat::iter()
+        .filter(|format| !formats.contains(format))
+        .map(|format| Path::new(VALIDATORS_DIR).join(format.get_output_file_name()))
+        .collect()
+}
+
+///A named record validated by the output. Event params are named like their types in
+///types.d.ts, eg. Gravatar_NewGravatar_eventArgs
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct ValidatorDefTemplate {
+    name: String,
+    #[serde(skip)]
+    json_schema: Value,
+    zod_schema: String,
+}
+
+impl ValidatorDefTemplate {
+    fn new(name: String, type_expr: &RescriptTypeExpr) -> Self {
+        Self {
+            name,
+            json_schema: type_expr.to_json_schema(),
+            zod_schema: type_expr.to_zod_schema(),
+        }
+    }
+
+    fn from_entity_template(entity: &EntityRecordTypeTemplate) -> Self {
+        let type_expr = RescriptTypeExpr::Record(
+            entity
+                .params
+                .iter()
+                .filter(|param| !param.is_derived_from)
+                .map(|param| {
+                    RescriptRecordField::new(param.get_record_field_name(), param.res_type.clone())
+                })
+                .collect(),
+        );
+        Self::new(entity.name.original.clone(), &type_expr)
+    }
+
+    fn from_config_event(contract_name: &str, event: &system_config::Event) -> Option<Self> {
+        event.get_params_type_expr().map(|type_expr| {
+            Self::new(
+                format!("{contract_name}_{}_eventArgs", event.name.capitalize()),
+                &type_expr,
+            )
+        })
+    }
+}
+
+///All the definitions share the $defs of the JSON Schema and the exports of the Zod
+///module, so their names can't clash
+fn check_names_unique<'a>(defs: impl Iterator<Item = &'a ValidatorDefTemplate>) -> Result<()> {
+    let clashing_names: Vec<&String> = defs.map(|def| &def.name).duplicates().collect();
+    if !clashing_names.is_empty() {
+        return Err(anyhow!(
+            "EE116: The validator names {} are used by more than one enum, entity or event \
+             params type. Rename the clashing types or disable the validators.",
+            clashing_names.into_iter().join(", ")
+        ));
+    }
+    Ok(())
+}
+
+///Template of the runtime validators of event params and entities
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct ValidatorsTemplate {
+    ///Formats of the rendered outputs
+    #[serde(skip)]
+    formats: Vec<ValidatorFormat>,
+    enums: Vec<ValidatorDefTemplate>,
+    entities: Vec<ValidatorDefTemplate>,
+    events: Vec<ValidatorDefTemplate>,
+    ///The JSON Schema document with all the definitions, pretty printed
+    json_schema: String,
+}
+
+impl ValidatorsTemplate {
+    pub fn new(
+        cfg: &SystemConfig,
+        entities: &[EntityRecordTypeTemplate],
+        gql_enums: &[GraphQlEnumTypeTemplate],
+    ) -> Result<Self> {
+        let enums: Vec<ValidatorDefTemplate> = gql_enums
+            .iter()
+            .map(|gql_enum| {
+                let values: Vec<&String> = gql_enum
+                    .params
+                    .iter()
+                    .map(|param| &param.original)
+                    .collect();
+                //Enum values are GraphQL names, so they don't need escaping
+                ValidatorDefTemplate {
+                    name: gql_enum.name.capitalized.clone(),
+                    zod_schema: format!(
+                        "z.enum([{}])",
+                        values.iter().map(|value| format!("\"{value}\"")).join(", ")
+                    ),
+                    json_schema: json!({ "type": "string", "enum": values }),
+                }
+            })
+            .collect();
+
+        let entities: Vec<ValidatorDefTemplate> = entities
+            .iter()
+            .map(ValidatorDefTemplate::from_entity_template)
+            .collect();
+
+        //Logged data of Fuel contracts is left out since its types are only declared
+        //in the ReScript code generated from the abi
+        let events: Vec<ValidatorDefTemplate> = cfg
+            .get_contracts()
+            .into_iter()
+            .flat_map(|contract| {
+                let contract_name = contract.name.capitalize();
+                contract
+                    .events
+                    .iter()
+                    .filter_map(move |event| {
+                        ValidatorDefTemplate::from_config_event(&contract_name, event)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        check_names_unique(enums.iter().chain(&entities).chain(&events))?;
+
+        let defs: serde_json::Map<String, Value> = enums
+            .iter()
+            .chain(&entities)
+            .chain(&events)
+            .map(|def| (def.name.clone(), def.json_schema.clone()))
+            .collect();
+        let json_schema = serde_json::to_string_pretty(&json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$defs": defs,
+        }))
+        .context("Failed serializing json schema of validators")?;
+
+        Ok(Self {
+            formats: cfg.validator_formats.clone(),
+            enums,
+            entities,
+            events,
+            json_schema,
+        })
+    }
+
+    ///Whether the template at the given path relative to the validators dir is of an
+    ///enabled format
+    pub fn includes_output(&self, output_path: &Path) -> bool {
+        self.formats
+            .iter()
+            .any(|format| output_path == Path::new(format.get_output_file_name()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_names_unique, get_disabled_output_paths, ValidatorDefTemplate};
+    use crate::{
+        config_parsing::{
+            human_config::ValidatorFormat,
+            system_config::{Event, EventKind},
+        },
+        rescript_types::RescriptTypeIdent,
+    };
+    use ethers::abi::{EventParam, ParamType};
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    #[test]
+    fn clashing_validator_names_are_rejected() {
+        let type_expr = RescriptTypeIdent::String.to_expr();
+        let defs = [
+            ValidatorDefTemplate::new("Token_Transfer_eventArgs".to_string(), &type_expr),
+            ValidatorDefTemplate::new("Token".to_string(), &type_expr),
+            ValidatorDefTemplate::new("Token_Transfer_eventArgs".to_string(), &type_expr),
+        ];
+
+        assert!(check_names_unique(defs[..2].iter()).is_ok());
+        assert_eq!(
+            check_names_unique(defs.iter()).unwrap_err().to_string(),
+            "EE116: The validator names Token_Transfer_eventArgs are used by more than one \
+             enum, entity or event params type. Rename the clashing types or disable the \
+             validators."
+        );
+    }
+
+    #[test]
+    fn disabled_validators_are_stale() {
+        assert_eq!(
+            get_disabled_output_paths(&[]),
+            vec![PathBuf::from("validators")]
+        );
+        assert_eq!(
+            get_disabled_output_paths(&[ValidatorFormat::JsonSchema]),
+            vec![PathBuf::from("validators/zod.ts")]
+        );
+        assert_eq!(
+            get_disabled_output_paths(&[ValidatorFormat::Zod, ValidatorFormat::JsonSchema]),
+            Vec::<PathBuf>::new()
+        );
+    }
+
+    #[test]
+    fn event_validators_use_strings_for_big_ints() {
+        let event = Event {
+            kind: EventKind::Params(vec![
+                EventParam {
+                    name: "from".to_string(),
+                    kind: ParamType::Address,
+                    indexed: true,
+                },
+                EventParam {
+                    name: "value".to_string(),
+                    kind: ParamType::Uint(256),
+                    indexed: false,
+                },
+            ]),
+            name: "Transfer".to_string(),
+            sighash: "0x".to_string(),
+        };
+        let def = ValidatorDefTemplate::from_config_event("Token", &event).unwrap();
+
+        assert_eq!(def.name, "Token_Transfer_eventArgs");
+        assert_eq!(
+            def.json_schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "from": { "type": "string", "pattern": "^0x[0-9a-fA-F]+$" },
+                    "value": { "type": "string", "pattern": "^-?[0-9]+$" },
+                },
+                "required": ["from", "value"],
+            })
+        );
+        assert_eq!(
+            def.zod_schema,
+            r#"z.object({ "from": z.string().regex(/^0x[0-9a-fA-F]+$/), "value": z.string().regex(/^-?[0-9]+$/) })"#
+        );
+    }
+}
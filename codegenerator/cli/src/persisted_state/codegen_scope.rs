@@ -0,0 +1,153 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+///Generated files that are rendered from the schema, relative to the generated dir
+const SCHEMA_OUTPUTS: &[&str] = &[
+    "index.d.ts",
+    "src/ContextEnv.res",
+    "src/RegisterHandlers.res",
+    "src/TestHelpers_MockDb.res",
+    "src/Types.res",
+    "src/db/Aggregations.res",
+    "src/db/Entities.res",
+    "src/db/Enums.res",
+    "types.d.ts",
+];
+
+///Files of the graphql client package that are rendered from the schema, relative to
+///the generated dir. Only rendered when the client is enabled.
+const GRAPHQL_CLIENT_SCHEMA_OUTPUTS: &[&str] = &["graphql-client/index.ts"];
+
+///Files of the validators that are rendered from the schema and the abis, relative to the
+///generated dir. Only rendered when their format is enabled.
+const VALIDATORS_OUTPUTS: &[&str] = &["validators/schema.json", "validators/zod.ts"];
+
+///Generated files that contain the event modules of a contract, relative to the generated dir
+const CONTRACT_EVENT_OUTPUTS: &[&str] = &[
+    "index.d.ts",
+    "src/ContextEnv.res",
+    "src/Handlers.res",
+    "src/RegisterHandlers.res",
+    "src/TestHelpers.res",
+    "src/Types.res",
+    "src/db/Enums.res",
+    "types.d.ts",
+];
+
+///Always regenerated so that the new hashes are persisted
+const PERSISTED_STATE_OUTPUT: &str = "persisted_state.envio.json";
+
+///Which part of the generated directory needs to be regenerated after a change
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodegenScope {
+    ///Regenerate all static and dynamic files and rebuild from a clean state
+    Full,
+    ///Only render the listed dynamic templates, relative to the generated dir.
+    ///Static files and installed packages are left untouched.
+    Targeted(BTreeSet<PathBuf>),
+}
+
+impl CodegenScope {
+    ///Gets the scope for a change to the schema and to the abis of the given contracts
+    pub fn from_changes(schema_changed: bool, contracts_with_changed_abi: &[String]) -> Self {
+        let mut outputs = BTreeSet::from([PathBuf::from(PERSISTED_STATE_OUTPUT)]);
+
+        if schema_changed {
+            outputs.extend(SCHEMA_OUTPUTS.iter().map(PathBuf::from));
+            outputs.extend(GRAPHQL_CLIENT_SCHEMA_OUTPUTS.iter().map(PathBuf::from));
+            outputs.extend(VALIDATORS_OUTPUTS.iter().map(PathBuf::from));
+        }
+
+        //All contracts share the same generated modules so the outputs
+        //are the same for any contract
+        if !contracts_with_changed_abi.is_empty() {
+            outputs.extend(CONTRACT_EVENT_OUTPUTS.iter().map(PathBuf::from));
+            outputs.extend(VALIDATORS_OUTPUTS.iter().map(PathBuf::from));
+        }
+
+        Self::Targeted(outputs)
+    }
+
+    ///Whether the dynamic template at the given path relative to the generated dir
+    ///should be rendered
+    pub fn includes_output(&self, output_path: &Path) -> bool {
+        match self {
+            Self::Full => true,
+            Self::Targeted(outputs) => outputs.contains(output_path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        CodegenScope, CONTRACT_EVENT_OUTPUTS, GRAPHQL_CLIENT_SCHEMA_OUTPUTS,
+        PERSISTED_STATE_OUTPUT, SCHEMA_OUTPUTS, VALIDATORS_OUTPUTS,
+    };
+    use crate::hbs_templating::{
+        graphql_client_templates::GRAPHQL_CLIENT_DIR, validators_templates::VALIDATORS_DIR,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn outputs_have_templates() {
+        for output in SCHEMA_OUTPUTS
+            .iter()
+            .chain(CONTRACT_EVENT_OUTPUTS)
+            .chain([&PERSISTED_STATE_OUTPUT])
+        {
+            let template_path =
+                PathBuf::from("templates/dynamic/codegen").join(format!("{output}.hbs"));
+            assert!(
+                template_path.exists(),
+                "Missing template for output {output}"
+            );
+        }
+
+        for output in GRAPHQL_CLIENT_SCHEMA_OUTPUTS {
+            let client_output = PathBuf::from(output)
+                .strip_prefix(GRAPHQL_CLIENT_DIR)
+                .unwrap()
+                .to_path_buf();
+            let template_path = PathBuf::from("templates/dynamic/graphql_client")
+                .join(format!("{}.hbs", client_output.display()));
+            assert!(
+                template_path.exists(),
+                "Missing template for output {output}"
+            );
+        }
+
+        for output in VALIDATORS_OUTPUTS {
+            let validators_output = PathBuf::from(output)
+                .strip_prefix(VALIDATORS_DIR)
+                .unwrap()
+                .to_path_buf();
+            let template_path = PathBuf::from("templates/dynamic/validators")
+                .join(format!("{}.hbs", validators_output.display()));
+            assert!(
+                template_path.exists(),
+                "Missing template for output {output}"
+            );
+        }
+    }
+
+    #[test]
+    fn abi_change_targets_event_outputs() {
+        let scope = CodegenScope::from_changes(false, &["Token".to_string()]);
+
+        assert!(scope.includes_output(&PathBuf::from("src/Handlers.res")));
+        assert!(scope.includes_output(&PathBuf::from("persisted_state.envio.json")));
+        assert!(!scope.includes_output(&PathBuf::from("src/db/Entities.res")));
+        assert!(!scope.includes_output(&PathBuf::from("package.json")));
+    }
+
+    #[test]
+    fn schema_change_targets_entity_outputs() {
+        let scope = CodegenScope::from_changes(true, &[]);
+
+        assert!(scope.includes_output(&PathBuf::from("src/db/Entities.res")));
+        assert!(!scope.includes_output(&PathBuf::from("src/Handlers.res")));
+    }
+}
@@ -1,26 +1,45 @@
-use super::{PersistedState, PersistedStateExists};
-use sqlx::postgres::{PgPool, PgPoolOptions, PgQueryResult};
-use std::env;
+use super::{
+    raw_events_coverage::BlockRange, DbSchemaSnapshot, PersistedState, PersistedStateExists,
+    ProjectSnapshot, RawEventsCoverage,
+};
+use crate::dev_services::PostgresSettings;
+use sqlx::{
+    postgres::{PgPool, PgPoolOptions, PgQueryResult, PgTypeInfo, PgValueRef},
+    Decode, Postgres, Type,
+};
 
-fn get_env_with_default(var: &str, default: &str) -> String {
-    env::var(var).unwrap_or_else(|_| default.to_string())
-}
+///Snapshots are stored as json strings in text columns of the persisted_state table
+macro_rules! impl_json_text_column {
+    ($snapshot:ty) => {
+        impl Type<Postgres> for $snapshot {
+            fn type_info() -> PgTypeInfo {
+                <String as Type<Postgres>>::type_info()
+            }
+        }
 
-async fn get_pg_pool() -> Result<PgPool, sqlx::Error> {
-    let host = get_env_with_default("ENVIO_PG_HOST", "localhost");
-    let port = get_env_with_default("ENVIO_PG_PORT", "5433");
-    let user = get_env_with_default("ENVIO_PG_USER", "postgres");
-    let password = get_env_with_default("ENVIO_POSTGRES_PASSWORD", "testing");
-    let database = get_env_with_default("ENVIO_PG_DATABASE", "envio-dev");
+        impl<'r> Decode<'r, Postgres> for $snapshot {
+            fn decode(value: PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+                let json_string = <&str as Decode<Postgres>>::decode(value)?;
+                Ok(serde_json::from_str(json_string)?)
+            }
+        }
+    };
+}
 
-    let connection_url = format!("postgres://{user}:{password}@{host}:{port}/{database}");
+impl_json_text_column!(DbSchemaSnapshot);
+impl_json_text_column!(ProjectSnapshot);
 
+async fn get_pg_pool(postgres: &PostgresSettings) -> Result<PgPool, sqlx::Error> {
+    let connection_url = postgres.get_connection_url();
     PgPoolOptions::new().connect(&connection_url).await
 }
 
 impl PersistedState {
-    pub async fn upsert_to_db(&self) -> Result<PgQueryResult, sqlx::Error> {
-        let pool = get_pg_pool().await?;
+    pub async fn upsert_to_db(
+        &self,
+        postgres: &PostgresSettings,
+    ) -> Result<PgQueryResult, sqlx::Error> {
+        let pool = get_pg_pool(postgres).await?;
         self.upsert_to_db_with_pool(&pool).await
     }
 
@@ -35,26 +54,46 @@ impl PersistedState {
             config_hash,
             schema_hash,
             handler_files_hash,
-            abi_files_hash
+            abi_files_hash,
+            templates_hash,
+            db_templates_hash,
+            hooks_hash,
+            schema_snapshot,
+            project_snapshot
         ) VALUES (
             $1, 
             $2, 
             $3, 
             $4, 
             $5, 
-            $6 
+            $6,
+            $7,
+            $8,
+            $9,
+            $10,
+            $11
         ) ON CONFLICT (id) DO UPDATE SET (
             envio_version,
             config_hash,
             schema_hash,
             handler_files_hash,
-            abi_files_hash
+            abi_files_hash,
+            templates_hash,
+            db_templates_hash,
+            hooks_hash,
+            schema_snapshot,
+            project_snapshot
         ) = (
             $2, 
             $3, 
             $4, 
             $5, 
-            $6 
+            $6,
+            $7,
+            $8,
+            $9,
+            $10,
+            $11
         )",
         )
         .bind(1) //Always only 1 id to update
@@ -63,14 +102,21 @@ impl PersistedState {
         .bind(&self.schema_hash)
         .bind(&self.handler_files_hash)
         .bind(&self.abi_files_hash)
+        .bind(&self.templates_hash)
+        .bind(&self.db_templates_hash)
+        .bind(&self.hooks_hash)
+        .bind(self.schema_snapshot.to_string())
+        .bind(self.project_snapshot.to_string())
         .execute(pool)
         .await
     }
 }
 
 impl PersistedStateExists {
-    pub async fn read_from_db() -> Result<PersistedStateExists, sqlx::Error> {
-        let pool = get_pg_pool().await?;
+    pub async fn read_from_db(
+        postgres: &PostgresSettings,
+    ) -> Result<PersistedStateExists, sqlx::Error> {
+        let pool = get_pg_pool(postgres).await?;
         Self::read_from_db_with_pool(&pool).await
     }
     pub async fn read_from_db_with_pool(
@@ -82,7 +128,12 @@ impl PersistedStateExists {
             config_hash,
             schema_hash,
             handler_files_hash,
-            abi_files_hash
+            abi_files_hash,
+            templates_hash,
+            db_templates_hash,
+            hooks_hash,
+            COALESCE(schema_snapshot, '{}') AS schema_snapshot,
+            COALESCE(project_snapshot, '{}') AS project_snapshot
          from public.persisted_state WHERE id = 1",
         )
         .fetch_optional(pool)
@@ -95,7 +146,9 @@ impl PersistedStateExists {
                 | sqlx::Error::ColumnNotFound(_)
                 | sqlx::Error::Database(_)
                 | sqlx::Error::ColumnDecode { .. }
-                | sqlx::Error::TypeNotFound { .. } => Ok(PersistedStateExists::Corrupted),
+                | sqlx::Error::TypeNotFound { .. } => {
+                    Ok(PersistedStateExists::Corrupted(e.to_string()))
+                }
                 _ => Err(e),
             },
             Ok(opt_state) => match opt_state {
@@ -105,10 +158,82 @@ impl PersistedStateExists {
         }
     }
 }
+///Tables that don't exist yet read as empty, since the db could have been set up
+///by a previous envio version or not at all
+async fn fetch_all_or_empty<T>(
+    query: sqlx::query::QueryAs<'_, Postgres, T, sqlx::postgres::PgArguments>,
+    pool: &PgPool,
+) -> Result<Vec<T>, sqlx::Error>
+where
+    T: Send + Unpin + for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow>,
+{
+    match query.fetch_all(pool).await {
+        Err(sqlx::Error::Database(_)) => Ok(vec![]),
+        rows => rows,
+    }
+}
+
+impl RawEventsCoverage {
+    pub async fn read_from_db(
+        postgres: &PostgresSettings,
+    ) -> Result<RawEventsCoverage, sqlx::Error> {
+        let pool = get_pg_pool(postgres).await?;
+
+        let coverage = fetch_all_or_empty(
+            sqlx::query_as::<_, (i32, i32, i32)>(
+                "SELECT chain_id, start_block, end_block FROM public.raw_events_coverage",
+            ),
+            &pool,
+        )
+        .await?
+        .into_iter()
+        .map(|(chain_id, start_block, end_block)| {
+            let range = BlockRange {
+                start_block: start_block as u64,
+                end_block: end_block as u64,
+            };
+            (chain_id as u64, range)
+        })
+        .collect();
+
+        let processed_blocks = fetch_all_or_empty(
+            sqlx::query_as::<_, (i32, i32)>(
+                "SELECT chain_id, block_number FROM public.event_sync_state",
+            ),
+            &pool,
+        )
+        .await?
+        .into_iter()
+        .map(|(chain_id, block_number)| (chain_id as u64, block_number as u64))
+        .collect();
+
+        let first_event_blocks = fetch_all_or_empty(
+            sqlx::query_as::<_, (i32, i32)>(
+                "SELECT chain_id, first_event_block_number FROM public.chain_metadata \
+                 WHERE first_event_block_number IS NOT NULL",
+            ),
+            &pool,
+        )
+        .await?
+        .into_iter()
+        .map(|(chain_id, block_number)| (chain_id as u64, block_number as u64))
+        .collect();
+
+        Ok(RawEventsCoverage {
+            coverage,
+            processed_blocks,
+            first_event_blocks,
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{config_parsing::system_config::SystemConfig, project_paths::ParsedProjectPaths};
+    use crate::{
+        config_parsing::system_config::{LocalServices, SystemConfig},
+        project_paths::ParsedProjectPaths,
+    };
     use anyhow::{Context, Result};
 
     #[tokio::test]
@@ -129,7 +254,7 @@ mod test {
             PersistedState::get_current_state(&system_cfg).context("persisted_state")?;
 
         persisted_state
-            .upsert_to_db()
+            .upsert_to_db(&PostgresSettings::from_env(&system_cfg.local_services))
             .await
             .context("write_to_db")?;
 
@@ -142,7 +267,8 @@ mod test {
         println!(
             "This test only works if the db migrations have been run and the db is up and running"
         );
-        let val = PersistedStateExists::read_from_db()
+        let local_services = LocalServices::from_config("indexer", None)?;
+        let val = PersistedStateExists::read_from_db(&PostgresSettings::from_env(&local_services))
             .await
             .context("read from db")?;
 
@@ -0,0 +1,96 @@
+use super::hash_string::HashString;
+use crate::config_parsing::system_config::SystemConfig;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+///Hashes of the individual abi and handler files of a project. Unlike the combined
+///hashes these can tell which contract a changed file belongs to.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct FileHashes {
+    ///Contract names mapped to the hash of their abi file
+    pub abi_files: BTreeMap<String, HashString>,
+    ///Handler paths as defined in the config mapped to the hash of the handler file
+    pub handler_files: BTreeMap<String, HashString>,
+}
+
+///Gets the keys that were added, removed or have a different value in the new map
+fn get_changed_keys(
+    old: &BTreeMap<String, HashString>,
+    new: &BTreeMap<String, HashString>,
+) -> Vec<String> {
+    let mut changed_keys: Vec<String> = old
+        .iter()
+        .filter(|(key, hash)| new.get(*key) != Some(*hash))
+        .map(|(key, _)| key.clone())
+        .chain(new.keys().filter(|key| !old.contains_key(*key)).cloned())
+        .collect();
+    changed_keys.sort();
+    changed_keys
+}
+
+impl FileHashes {
+    pub fn from_config(config: &SystemConfig) -> anyhow::Result<Self> {
+        const HANDLER_FILE_MUST_EXIST: bool = false;
+        const ABI_FILE_MUST_EXIST: bool = true;
+
+        let mut abi_files = BTreeMap::new();
+        let mut handler_files = BTreeMap::new();
+
+        for contract in config.get_contracts() {
+            if let Some(abi_path) = contract.abi.get_path() {
+                let hash =
+                    HashString::from_file_paths(vec![abi_path], ABI_FILE_MUST_EXIST).context(
+                        format!("Failed hashing abi file of contract {}", contract.name),
+                    )?;
+                abi_files.insert(contract.name.clone(), hash);
+            }
+
+            let handler_path = contract
+                .get_path_to_handler(&config.parsed_project_paths)
+                .context("Failed getting handler path")?;
+            let hash =
+                HashString::from_file_paths(vec![handler_path], HANDLER_FILE_MUST_EXIST).context(
+                    format!("Failed hashing handler file {}", contract.handler_path),
+                )?;
+            handler_files.insert(contract.handler_path.clone(), hash);
+        }
+
+        Ok(Self {
+            abi_files,
+            handler_files,
+        })
+    }
+
+    ///Gets the names of contracts with an added, removed or changed abi file
+    pub fn get_contracts_with_changed_abi(&self, new: &Self) -> Vec<String> {
+        get_changed_keys(&self.abi_files, &new.abi_files)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FileHashes;
+    use serde_json::json;
+
+    #[test]
+    fn gets_changed_files() {
+        let old: FileHashes = serde_json::from_value(json!({
+            "abi_files": {"Token": "0x01", "Pool": "0x02", "Factory": "0x03"},
+            "handler_files": {"src/EventHandlers.ts": "0x04"}
+        }))
+        .unwrap();
+
+        let new: FileHashes = serde_json::from_value(json!({
+            "abi_files": {"Token": "0x01", "Pool": "0x05", "Vault": "0x06"},
+            "handler_files": {"src/EventHandlers.ts": "0x04"}
+        }))
+        .unwrap();
+
+        assert_eq!(
+            old.get_contracts_with_changed_abi(&new),
+            vec!["Factory", "Pool", "Vault"]
+        );
+    }
+}
@@ -6,10 +6,10 @@ use std::{
     fmt::{self, Display},
     fs::File,
     io::Read,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, sqlx::FromRow, sqlx::Type)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, sqlx::FromRow, sqlx::Type)]
 #[sqlx(type_name = "Text")]
 #[serde(transparent)]
 pub struct HashString(String);
@@ -50,6 +50,38 @@ impl HashString {
         Self::from_file_paths(vec![file_path], true)
     }
 
+    ///Hashes the files at the relative paths within a dir along with the paths, so
+    ///that moving or renaming a file also changes the hash
+    pub fn from_dir_files(dir: &Path, relative_paths: Vec<PathBuf>) -> anyhow::Result<Self> {
+        let mut buffer = Vec::new();
+
+        for relative_path in relative_paths {
+            buffer.extend(relative_path.to_string_lossy().as_bytes());
+            //Separates the path from the contents
+            buffer.push(0);
+            let mut file = File::open(dir.join(&relative_path))
+                .context("Opening file in HashString::from_dir_files")?;
+            file.read_to_end(&mut buffer)
+                .context("Reading file in HashString::from_dir_files")?;
+        }
+
+        let hash = Sha256::digest(&buffer);
+        Ok(HashString(format!("{:x}", hash)))
+    }
+
+    ///Hashes the strings in order, separated so that moving text from one string to
+    ///the next also changes the hash
+    pub fn from_strings(strings: impl IntoIterator<Item = String>) -> Self {
+        let mut buffer = Vec::new();
+        for string in strings {
+            buffer.extend(string.as_bytes());
+            buffer.push(0);
+        }
+
+        let hash = Sha256::digest(&buffer);
+        HashString(format!("{:x}", hash))
+    }
+
     #[cfg(test)]
     fn inner(&self) -> String {
         self.0.clone()
@@ -101,6 +133,19 @@ mod test {
         );
     }
 
+    #[test]
+    fn strings_hash_changes_with_the_split() {
+        let hash = HashString::from_strings(vec!["ab".to_string(), "c".to_string()]);
+        assert_ne!(
+            hash,
+            HashString::from_strings(vec!["a".to_string(), "bc".to_string()])
+        );
+        assert_eq!(
+            hash,
+            HashString::from_strings(vec!["ab".to_string(), "c".to_string()])
+        );
+    }
+
     #[test]
     #[should_panic]
     fn fail_hash_empty_fail() {
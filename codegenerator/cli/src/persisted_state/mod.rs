@@ -1,14 +1,28 @@
+mod codegen_scope;
 mod db;
+mod file_hashes;
 mod hash_string;
+mod project_snapshot;
+mod raw_events_coverage;
+mod schema_diff;
 
-use crate::{config_parsing::system_config::SystemConfig, project_paths::ParsedProjectPaths};
+use crate::{
+    config_parsing::system_config::SystemConfig, project_paths::ParsedProjectPaths,
+    template_dirs::TemplateOverrides,
+};
 use anyhow::Context;
+pub use codegen_scope::CodegenScope;
+use file_hashes::FileHashes;
 use hash_string::HashString;
+pub use project_snapshot::ProjectSnapshot;
+pub use raw_events_coverage::RawEventsCoverage;
+pub use schema_diff::{DbSchemaSnapshot, SchemaChange};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::{
+    collections::BTreeMap,
     fmt::{self, Display},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
@@ -20,8 +34,36 @@ pub struct PersistedState {
     pub schema_hash: HashString,
     pub handler_files_hash: HashString,
     pub abi_files_hash: HashString,
+    ///Hash of the project local template overrides. States from before overrides
+    ///default to an empty hash, which never matches and so reruns codegen.
+    #[serde(default)]
+    pub templates_hash: HashString,
+    ///Hash of the overrides of the db templates, the only overrides that affect the db
+    #[serde(default)]
+    pub db_templates_hash: HashString,
+    ///Hash of the pre and post codegen hook commands
+    #[serde(default)]
+    pub hooks_hash: HashString,
+    ///Structure of the db created by the migrations, used to diff schema changes.
+    ///States from before snapshots default to an empty one, which is treated as unknown.
+    #[serde(default)]
+    pub schema_snapshot: DbSchemaSnapshot,
+    ///Canonical representation of the parsed schema and config, used to describe changes
+    #[serde(default)]
+    pub project_snapshot: ProjectSnapshot,
+    ///Hashes of each abi and handler file, only persisted in the generated file
+    ///since they are used to target codegen
+    #[serde(default)]
+    #[sqlx(skip)]
+    pub file_hashes: FileHashes,
+    ///Hashes of the files written by codegen as the post_codegen hooks left them, keyed
+    ///by their path relative to the generated dir. Only recorded in the generated file
+    ///when there are post_codegen hooks, since their changes can't be rendered again.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[sqlx(skip)]
+    pub generated_files: BTreeMap<String, HashString>,
 }
-const PERSISTED_STATE_FILE_NAME: &str = "persisted_state.envio.json";
+pub const PERSISTED_STATE_FILE_NAME: &str = "persisted_state.envio.json";
 pub static CURRENT_CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[derive(Debug, strum::Display, EnumIter, PartialEq, Clone)]
@@ -32,6 +74,9 @@ pub enum StateField {
     Schema,
     AbiFiles,
     HandlerFiles,
+    Templates,
+    DbTemplates,
+    Hooks,
 }
 
 ///Gets the path to the persisted file in generated folder
@@ -49,9 +94,31 @@ impl PersistedState {
             StateField::Schema => self.schema_hash == other_state.schema_hash,
             StateField::AbiFiles => self.abi_files_hash == other_state.abi_files_hash,
             StateField::HandlerFiles => self.handler_files_hash == other_state.handler_files_hash,
+            StateField::Templates => self.templates_hash == other_state.templates_hash,
+            StateField::DbTemplates => self.db_templates_hash == other_state.db_templates_hash,
+            StateField::Hooks => self.hooks_hash == other_state.hooks_hash,
         }
     }
 
+    ///Gets the value stored for a given field, used to display states side by side
+    pub fn get_field_value(&self, field: &StateField) -> String {
+        match field {
+            StateField::Config => self.config_hash.to_string(),
+            StateField::EnvioVersion => self.envio_version.clone(),
+            StateField::Schema => self.schema_hash.to_string(),
+            StateField::AbiFiles => self.abi_files_hash.to_string(),
+            StateField::HandlerFiles => self.handler_files_hash.to_string(),
+            StateField::Templates => self.templates_hash.to_string(),
+            StateField::DbTemplates => self.db_templates_hash.to_string(),
+            StateField::Hooks => self.hooks_hash.to_string(),
+        }
+    }
+
+    ///Gets all the fields that differ from the other state
+    pub fn get_changed_fields(&self, other_state: &Self) -> Vec<StateField> {
+        self.get_non_matching_fields(other_state, StateField::iter().collect())
+    }
+
     ///Given a vec of fields and two states to compare
     ///Returns a vec of the fields that have changed
     fn get_non_matching_fields(
@@ -81,6 +148,14 @@ impl PersistedState {
             .get_all_paths_to_abi_files()
             .context("Failed getting abi file paths")?;
 
+        let template_overrides = TemplateOverrides::new(&config.parsed_project_paths.project_root);
+        let template_override_paths = template_overrides
+            .get_paths()
+            .context("Failed getting template override paths")?;
+        let db_template_override_paths = template_overrides
+            .get_db_paths()
+            .context("Failed getting db template override paths")?;
+
         const HANDLER_FILES_MUST_EXIST: bool = false;
         const ABI_FILES_MUST_EXIST: bool = true;
 
@@ -97,6 +172,36 @@ impl PersistedState {
             .context("Failed hashing handler files")?,
             abi_files_hash: HashString::from_file_paths(all_abi_file_paths, ABI_FILES_MUST_EXIST)
                 .context("Failed hashing abi files")?,
+            templates_hash: HashString::from_dir_files(
+                template_overrides.get_dir(),
+                template_override_paths,
+            )
+            .context("Failed hashing template overrides")?,
+            db_templates_hash: HashString::from_dir_files(
+                template_overrides.get_dir(),
+                db_template_override_paths,
+            )
+            .context("Failed hashing db template overrides")?,
+            hooks_hash: HashString::from_strings(
+                config
+                    .hooks
+                    .pre_codegen
+                    .iter()
+                    .map(|command| format!("pre_codegen: {command}"))
+                    .chain(
+                        config
+                            .hooks
+                            .post_codegen
+                            .iter()
+                            .map(|command| format!("post_codegen: {command}")),
+                    ),
+            ),
+            schema_snapshot: DbSchemaSnapshot::from_schema(&config.schema)
+                .context("Failed creating db snapshot of schema")?,
+            project_snapshot: ProjectSnapshot::from_config(config),
+            file_hashes: FileHashes::from_config(config)
+                .context("Failed hashing individual project files")?,
+            generated_files: BTreeMap::new(),
         })
     }
 
@@ -113,6 +218,10 @@ impl PersistedState {
             //If the version envio changes, this could infer differences that need to be re-code
             //generated
             StateField::EnvioVersion,
+            //Template overrides could change any generated file
+            StateField::Templates,
+            //Hooks could write any generated file or the files read by codegen
+            StateField::Hooks,
         ];
 
         let non_matching_fields =
@@ -121,24 +230,100 @@ impl PersistedState {
         (!non_matching_fields.is_empty(), non_matching_fields)
     }
 
+    ///Compares the inputs that the generated code is rendered from with a persisted
+    ///state file, leaving out the hashes of the handlers and the individual files
+    pub fn get_changed_codegen_inputs(&self, persisted_state_file: &Self) -> Vec<StateField> {
+        self.get_non_matching_fields(
+            persisted_state_file,
+            vec![
+                StateField::EnvioVersion,
+                StateField::Config,
+                StateField::Schema,
+                StateField::AbiFiles,
+            ],
+        )
+    }
+
+    ///Compares the current state and a persisted state file, returning which generated
+    ///files are affected by the changes. Anything other than schema and abi changes
+    ///could affect any generated file and needs a full codegen.
+    pub fn get_codegen_scope(&self, persisted_state_file: &Self) -> CodegenScope {
+        let (_, non_matching_fields) = self.should_run_codegen(persisted_state_file);
+
+        let contracts_with_changed_abi = persisted_state_file
+            .file_hashes
+            .get_contracts_with_changed_abi(&self.file_hashes);
+
+        let can_target = non_matching_fields.iter().all(|field| match field {
+            StateField::Schema => true,
+            //State files from before per file hashes can't tell which abi changed
+            StateField::AbiFiles => !contracts_with_changed_abi.is_empty(),
+            _ => false,
+        });
+
+        if can_target {
+            CodegenScope::from_changes(
+                non_matching_fields.contains(&StateField::Schema),
+                &contracts_with_changed_abi,
+            )
+        } else {
+            CodegenScope::Full
+        }
+    }
+
+    ///The fields that the db created by the migrations and the data indexed into it
+    ///depend on. Template overrides only count when they override a db template, and
+    ///hooks don't count since the files they write are hashed with the other fields.
+    fn get_db_affecting_fields() -> Vec<StateField> {
+        vec![
+            StateField::EnvioVersion,
+            StateField::Config,
+            StateField::Schema,
+            StateField::AbiFiles,
+            StateField::HandlerFiles,
+            StateField::DbTemplates,
+        ]
+    }
+
     ///Compares the current state and a persisted state on the db, returning a boolean of whether
     ///migrations should be run and a vector of the changed fields that make the rerun necessary
     pub fn should_run_db_migrations(&self, persisted_state_db: &Self) -> (bool, Vec<StateField>) {
         //Check if any changes to the state and report which fields. All should invoke a migration
-        let codegen_affecting_fields: Vec<_> = StateField::iter().collect();
-
         let non_matching_fields =
-            self.get_non_matching_fields(persisted_state_db, codegen_affecting_fields);
+            self.get_non_matching_fields(persisted_state_db, Self::get_db_affecting_fields());
 
         (!non_matching_fields.is_empty(), non_matching_fields)
     }
 
+    ///Compares the current state and a persisted state on the db, returning the
+    ///structural changes to the db when the schema is the only thing that changed.
+    ///Returns None if anything else changed, in which case the db needs a full reset.
+    pub fn get_schema_only_changes(&self, persisted_state_db: &Self) -> Option<Vec<SchemaChange>> {
+        let non_matching_fields =
+            self.get_non_matching_fields(persisted_state_db, Self::get_db_affecting_fields());
+
+        if non_matching_fields == vec![StateField::Schema] {
+            //Without a snapshot of what was migrated the changes can't be known to be safe
+            if persisted_state_db.schema_snapshot.is_empty() {
+                Some(vec![SchemaChange::UnknownPreviousSchema])
+            } else {
+                Some(
+                    persisted_state_db
+                        .schema_snapshot
+                        .diff(&self.schema_snapshot),
+                )
+            }
+        } else {
+            None
+        }
+    }
+
     ///Compares the current state and a persisted state on the db,
     ///returning a boolean of whether it should resync from raw events
     ///ie. in the case where nothing has changed except event handlers
     pub fn should_sync_from_raw_events(&self, persisted_state_db: &Self) -> bool {
-        let any_changes = StateField::iter().collect::<Vec<_>>();
-        let non_matching_fields = self.get_non_matching_fields(persisted_state_db, any_changes);
+        let non_matching_fields =
+            self.get_non_matching_fields(persisted_state_db, Self::get_db_affecting_fields());
 
         let only_handler_file_change = vec![StateField::HandlerFiles];
 
@@ -150,10 +335,12 @@ impl PersistedState {
 }
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum PersistedStateExists {
     Exists(PersistedState),
     NotExists,
-    Corrupted,
+    ///Holds the reason the persisted state couldn't be read
+    Corrupted(String),
 }
 
 impl PersistedStateExists {
@@ -170,7 +357,7 @@ impl PersistedStateExists {
                 //representation of PersistedState. In either of these cases we
                 //need to re-codegen so we can just treat it as an option type
                 match serde_json::from_str(&file_str) {
-                    Err(_) => Self::Corrupted,
+                    Err(err) => Self::Corrupted(err.to_string()),
                     Ok(state) => Self::Exists(state),
                 }
             }
@@ -178,6 +365,58 @@ impl PersistedStateExists {
     }
 }
 
+impl PersistedState {
+    ///Records the hashes of the files written by codegen, relative to the generated dir,
+    ///as they are now. The persisted state file itself is left out.
+    pub fn set_generated_files(
+        &mut self,
+        project_paths: &ParsedProjectPaths,
+        paths: Vec<PathBuf>,
+    ) -> anyhow::Result<()> {
+        const GENERATED_FILE_MUST_EXIST: bool = false;
+        self.generated_files = paths
+            .into_iter()
+            .filter(|path| path != Path::new(PERSISTED_STATE_FILE_NAME))
+            .map(|path| {
+                let hash = HashString::from_file_paths(
+                    vec![project_paths.generated.join(&path)],
+                    GENERATED_FILE_MUST_EXIST,
+                )
+                .context(format!("Failed hashing generated file {}", path.display()))?;
+                Ok((path.to_string_lossy().to_string(), hash))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(())
+    }
+
+    ///Gets the recorded generated files that changed since codegen and its
+    ///post_codegen hooks wrote them
+    pub fn get_changed_generated_files(&self, project_paths: &ParsedProjectPaths) -> Vec<String> {
+        const GENERATED_FILE_MUST_EXIST: bool = false;
+        self.generated_files
+            .iter()
+            .filter(|(path, hash)| {
+                HashString::from_file_paths(
+                    vec![project_paths.generated.join(path)],
+                    GENERATED_FILE_MUST_EXIST,
+                )
+                .map_or(true, |current_hash| &current_hash != *hash)
+            })
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    ///Overwrites the persisted state file in the generated code, which is otherwise
+    ///only written by codegen
+    pub fn write_to_file(&self, project_paths: &ParsedProjectPaths) -> anyhow::Result<()> {
+        let file_path = get_generated_file_path(project_paths);
+        std::fs::write(&file_path, self.to_string()).context(format!(
+            "Failed writing persisted state to {}",
+            file_path.display()
+        ))
+    }
+}
+
 impl Display for PersistedState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let json_string = serde_json::to_string(self).map_err(|_| fmt::Error)?;
@@ -196,7 +435,10 @@ impl From<PersistedState> for PersistedStateJsonString {
 #[cfg(test)]
 mod test {
     use super::PersistedState;
+    use crate::project_paths::ParsedProjectPaths;
     use serde_json::json;
+    use std::{fs, path::PathBuf};
+    use tempdir::TempDir;
 
     #[test]
     fn should_run_codegen() {
@@ -205,7 +447,9 @@ mod test {
             "config_hash": "<HASH_STRING>",
             "schema_hash": "<HASH_STRING>",
             "handler_files_hash": "<HASH_STRING>",
-            "abi_files_hash": "<HASH_STRING>"
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
         }))
         .unwrap();
 
@@ -214,7 +458,9 @@ mod test {
             "config_hash": "<CHANGED_HASH_STRING>",
             "schema_hash": "<HASH_STRING>",
             "handler_files_hash": "<HASH_STRING>",
-            "abi_files_hash": "<HASH_STRING>"
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
         }))
         .unwrap();
 
@@ -234,7 +480,9 @@ mod test {
             "config_hash": "<HASH_STRING>",
             "schema_hash": "<HASH_STRING>",
             "handler_files_hash": "<HASH_STRING>",
-            "abi_files_hash": "<HASH_STRING>"
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
         }))
         .unwrap();
 
@@ -243,7 +491,9 @@ mod test {
             "config_hash": "<HASH_STRING>",
             "schema_hash": "<HASH_STRING>",
             "handler_files_hash": "<CHANGED_HASH_STRING>",
-            "abi_files_hash": "<HASH_STRING>"
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
         }))
         .unwrap();
 
@@ -263,7 +513,9 @@ mod test {
             "config_hash": "<HASH_STRING>",
             "schema_hash": "<HASH_STRING>",
             "handler_files_hash": "<HASH_STRING>",
-            "abi_files_hash": "<HASH_STRING>"
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
         }))
         .unwrap();
 
@@ -272,7 +524,9 @@ mod test {
             "config_hash": "<HASH_STRING>",
             "schema_hash": "<CHANGED_HASH_STRING>",
             "handler_files_hash": "<CHANGED_HASH_STRING>",
-            "abi_files_hash": "<HASH_STRING>"
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
         }))
         .unwrap();
 
@@ -300,7 +554,9 @@ mod test {
             "config_hash": "<HASH_STRING>",
             "schema_hash": "<HASH_STRING>",
             "handler_files_hash": "<HASH_STRING>",
-            "abi_files_hash": "<HASH_STRING>"
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
         }))
         .unwrap();
 
@@ -309,7 +565,9 @@ mod test {
             "config_hash": "<HASH_STRING>",
             "schema_hash": "<HASH_STRING>",
             "handler_files_hash": "<CHANGED_HASH_STRING>",
-            "abi_files_hash": "<HASH_STRING>"
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
         }))
         .unwrap();
 
@@ -336,7 +594,9 @@ mod test {
             "config_hash": "<HASH_STRING>",
             "schema_hash": "<HASH_STRING>",
             "handler_files_hash": "<HASH_STRING>",
-            "abi_files_hash": "<HASH_STRING>"
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
         }))
         .unwrap();
 
@@ -345,7 +605,9 @@ mod test {
             "config_hash": "<HASH_STRING>",
             "schema_hash": "<HASH_STRING>",
             "handler_files_hash": "<HASH_STRING>",
-            "abi_files_hash": "<HASH_STRING>"
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
         }))
         .unwrap();
 
@@ -357,4 +619,335 @@ mod test {
             "should run codegen should be false since nothing changed"
         );
     }
+
+    #[test]
+    fn get_schema_only_changes() {
+        let persisted_db: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {"Kind": ["A"]}},
+            "project_snapshot": {}
+        }))
+        .unwrap();
+
+        let schema_changed_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<CHANGED_HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {
+                "tables": {},
+                "enums": {"Kind": ["A"], "Status": ["ACTIVE"]}
+            },
+            "project_snapshot": {}
+        }))
+        .unwrap();
+
+        assert_eq!(
+            schema_changed_state.get_schema_only_changes(&persisted_db),
+            Some(vec![super::SchemaChange::AddedEnum("Status".to_string())]),
+            "should diff the schema snapshots since only the schema changed"
+        );
+
+        let schema_and_config_changed_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<CHANGED_HASH_STRING>",
+            "schema_hash": "<CHANGED_HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {
+                "tables": {},
+                "enums": {"Kind": ["A"], "Status": ["ACTIVE"]}
+            },
+            "project_snapshot": {}
+        }))
+        .unwrap();
+
+        assert_eq!(
+            schema_and_config_changed_state.get_schema_only_changes(&persisted_db),
+            None,
+            "should not diff the schema since the config changed as well"
+        );
+    }
+
+    #[test]
+    fn get_schema_only_changes_without_snapshot() {
+        //States persisted before snapshots have no snapshot fields
+        let persisted_db: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>"
+        }))
+        .unwrap();
+
+        let schema_changed_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<CHANGED_HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {"Status": ["ACTIVE"]}},
+            "project_snapshot": {}
+        }))
+        .unwrap();
+
+        let changes = schema_changed_state.get_schema_only_changes(&persisted_db);
+        assert_eq!(
+            changes,
+            Some(vec![super::SchemaChange::UnknownPreviousSchema]),
+            "should not diff against a missing snapshot"
+        );
+        assert!(
+            changes
+                .unwrap()
+                .iter()
+                .all(|change| change.is_destructive()),
+            "should prompt before resetting the db"
+        );
+    }
+
+    #[test]
+    fn get_codegen_scope() {
+        let persisted_file: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {},
+            "file_hashes": {"abi_files": {"Token": "<HASH_STRING>"}}
+        }))
+        .unwrap();
+
+        let abi_changed_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<CHANGED_HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {},
+            "file_hashes": {"abi_files": {"Token": "<CHANGED_HASH_STRING>"}}
+        }))
+        .unwrap();
+
+        assert!(
+            matches!(
+                abi_changed_state.get_codegen_scope(&persisted_file),
+                super::CodegenScope::Targeted(_)
+            ),
+            "should target the event outputs since only an abi changed"
+        );
+
+        let config_changed_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<CHANGED_HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<CHANGED_HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {},
+            "file_hashes": {"abi_files": {"Token": "<CHANGED_HASH_STRING>"}}
+        }))
+        .unwrap();
+
+        assert_eq!(
+            config_changed_state.get_codegen_scope(&persisted_file),
+            super::CodegenScope::Full,
+            "should run a full codegen since the config changed"
+        );
+    }
+
+    #[test]
+    fn only_db_template_changes_affect_the_db() {
+        let persisted_db: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+            "templates_hash": "<HASH_STRING>",
+            "db_templates_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
+        }))
+        .unwrap();
+
+        let templates_changed_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+            "templates_hash": "<CHANGED_HASH_STRING>",
+            "db_templates_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
+        }))
+        .unwrap();
+
+        assert_eq!(
+            templates_changed_state.should_run_db_migrations(&persisted_db),
+            (false, vec![])
+        );
+
+        let handlers_changed_state = PersistedState {
+            handler_files_hash: serde_json::from_value(json!("<CHANGED_HASH_STRING>")).unwrap(),
+            ..templates_changed_state
+        };
+        assert!(handlers_changed_state.should_sync_from_raw_events(&persisted_db));
+
+        let db_templates_changed_state = PersistedState {
+            db_templates_hash: serde_json::from_value(json!("<CHANGED_HASH_STRING>")).unwrap(),
+            ..handlers_changed_state
+        };
+        assert_eq!(
+            db_templates_changed_state.should_run_db_migrations(&persisted_db),
+            (
+                true,
+                vec![
+                    super::StateField::HandlerFiles,
+                    super::StateField::DbTemplates
+                ]
+            )
+        );
+        assert!(!db_templates_changed_state.should_sync_from_raw_events(&persisted_db));
+    }
+
+    #[test]
+    fn hook_changes_dont_affect_the_db() {
+        let persisted_db: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+            "hooks_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {"Kind": ["A"]}},
+            "project_snapshot": {}
+        }))
+        .unwrap();
+
+        let hooks_changed_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+            "hooks_hash": "<CHANGED_HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {"Kind": ["A"]}},
+            "project_snapshot": {}
+        }))
+        .unwrap();
+
+        assert_eq!(
+            hooks_changed_state.should_run_db_migrations(&persisted_db),
+            (false, vec![])
+        );
+
+        let handlers_changed_state = PersistedState {
+            handler_files_hash: serde_json::from_value(json!("<CHANGED_HASH_STRING>")).unwrap(),
+            ..hooks_changed_state
+        };
+        assert!(handlers_changed_state.should_sync_from_raw_events(&persisted_db));
+
+        let schema_changed_state = PersistedState {
+            handler_files_hash: persisted_db.handler_files_hash.clone(),
+            schema_hash: serde_json::from_value(json!("<CHANGED_HASH_STRING>")).unwrap(),
+            schema_snapshot: serde_json::from_value(
+                json!({"tables": {}, "enums": {"Kind": ["A", "B"]}}),
+            )
+            .unwrap(),
+            ..handlers_changed_state
+        };
+        assert!(schema_changed_state
+            .get_schema_only_changes(&persisted_db)
+            .is_some());
+    }
+
+    #[test]
+    fn get_changed_fields() {
+        let persisted_db: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
+        }))
+        .unwrap();
+
+        let current_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.2",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<CHANGED_HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+            "schema_snapshot": {"tables": {}, "enums": {}},
+            "project_snapshot": {}
+        }))
+        .unwrap();
+
+        assert_eq!(
+            current_state.get_changed_fields(&persisted_db),
+            vec![super::StateField::EnvioVersion, super::StateField::Schema]
+        );
+        assert_eq!(
+            current_state.get_field_value(&super::StateField::Schema),
+            "<CHANGED_HASH_STRING>"
+        );
+    }
+
+    #[test]
+    fn detects_changes_to_generated_files_after_post_codegen() {
+        let dir = TempDir::new("generated_files").unwrap();
+        let project_paths =
+            ParsedProjectPaths::new(dir.path().to_str().unwrap(), "generated", "config.yaml")
+                .unwrap();
+        fs::create_dir_all(project_paths.generated.join("src")).unwrap();
+        fs::write(project_paths.generated.join("src/Types.res"), "//License").unwrap();
+        fs::write(project_paths.generated.join("package.json"), "{}").unwrap();
+
+        let mut persisted_state: PersistedState = serde_json::from_value(json!({
+            "envio_version": "0.0.1",
+            "config_hash": "<HASH_STRING>",
+            "schema_hash": "<HASH_STRING>",
+            "handler_files_hash": "<HASH_STRING>",
+            "abi_files_hash": "<HASH_STRING>",
+        }))
+        .unwrap();
+        persisted_state
+            .set_generated_files(
+                &project_paths,
+                vec![
+                    PathBuf::from("package.json"),
+                    PathBuf::from("persisted_state.envio.json"),
+                    PathBuf::from("src/Types.res"),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(
+            persisted_state.generated_files.keys().collect::<Vec<_>>(),
+            vec!["package.json", "src/Types.res"],
+            "The persisted state file itself shouldn't be hashed"
+        );
+        assert!(persisted_state
+            .get_changed_generated_files(&project_paths)
+            .is_empty());
+
+        fs::write(project_paths.generated.join("src/Types.res"), "type t").unwrap();
+        assert_eq!(
+            persisted_state.get_changed_generated_files(&project_paths),
+            vec!["src/Types.res"]
+        );
+    }
 }
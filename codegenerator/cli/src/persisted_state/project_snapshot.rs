@@ -0,0 +1,446 @@
+use crate::config_parsing::{
+    entity_parsing::Schema,
+    system_config::{Ecosystem, SyncSource, SystemConfig},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct SchemaSnapshot {
+    ///Entity names mapped to their fields and GraphQL field types
+    pub entities: BTreeMap<String, BTreeMap<String, String>>,
+    pub enums: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ContractSnapshot {
+    pub handler: String,
+    ///Event names mapped to their sighash
+    pub events: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct NetworkSnapshot {
+    pub sync_source: String,
+    pub start_block: u64,
+    pub end_block: Option<u64>,
+    pub confirmed_block_threshold: i32,
+    ///Contract names mapped to their addresses on the network
+    pub contracts: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ConfigSnapshot {
+    pub name: String,
+    pub ecosystem: String,
+    pub contracts: BTreeMap<String, ContractSnapshot>,
+    pub networks: BTreeMap<u64, NetworkSnapshot>,
+    ///Config flags like rollback_on_reorg mapped to their value
+    pub options: BTreeMap<String, bool>,
+    pub field_selection: Vec<String>,
+}
+
+///A canonical json representation of the parsed schema and config. Unlike the file hashes
+///it can be diffed so that the cli can tell what changed between runs.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct ProjectSnapshot {
+    pub schema: SchemaSnapshot,
+    pub config: ConfigSnapshot,
+}
+
+impl SchemaSnapshot {
+    fn from_schema(schema: &Schema) -> Self {
+        let entities = schema
+            .entities
+            .values()
+            .map(|entity| {
+                let fields = entity
+                    .fields
+                    .values()
+                    .map(|field| (field.name.clone(), field.field_type.to_string()))
+                    .collect();
+                (entity.name.clone(), fields)
+            })
+            .collect();
+
+        let enums = schema
+            .enums
+            .values()
+            .map(|gql_enum| (gql_enum.name.clone(), gql_enum.values.clone()))
+            .collect();
+
+        Self { entities, enums }
+    }
+}
+
+impl ConfigSnapshot {
+    fn from_config(config: &SystemConfig) -> Self {
+        let contracts = config
+            .get_contracts()
+            .into_iter()
+            .map(|contract| {
+                let events = contract
+                    .events
+                    .iter()
+                    .map(|event| (event.name.clone(), event.sighash.clone()))
+                    .collect();
+                (
+                    contract.name.clone(),
+                    ContractSnapshot {
+                        handler: contract.handler_path.clone(),
+                        events,
+                    },
+                )
+            })
+            .collect();
+
+        let networks = config
+            .get_networks()
+            .into_iter()
+            .map(|network| {
+                let sync_source = match &network.sync_source {
+                    SyncSource::RpcConfig(rpc_config) => {
+                        format!("rpc {}", rpc_config.urls.join(", "))
+                    }
+                    SyncSource::HypersyncConfig(hypersync_config) => {
+                        format!("hypersync {}", hypersync_config.endpoint_url)
+                    }
+                    SyncSource::HyperfuelConfig(hyperfuel_config) => {
+                        format!("hyperfuel {}", hyperfuel_config.endpoint_url)
+                    }
+                };
+                let contracts = network
+                    .contracts
+                    .iter()
+                    .map(|contract| (contract.name.clone(), contract.addresses.clone()))
+                    .collect();
+                (
+                    network.id,
+                    NetworkSnapshot {
+                        sync_source,
+                        start_block: network.start_block,
+                        end_block: network.end_block,
+                        confirmed_block_threshold: network.confirmed_block_threshold,
+                        contracts,
+                    },
+                )
+            })
+            .collect();
+
+        let options = BTreeMap::from([
+            (
+                "unordered_multichain_mode".to_string(),
+                config.unordered_multichain_mode,
+            ),
+            ("rollback_on_reorg".to_string(), config.rollback_on_reorg),
+            ("save_full_history".to_string(), config.save_full_history),
+            ("raw_events".to_string(), config.enable_raw_events),
+            ("hasura".to_string(), config.enable_hasura),
+            ("graphql_client".to_string(), config.enable_graphql_client),
+        ]);
+
+        let field_selection = config
+            .field_selection
+            .block_fields
+            .iter()
+            .map(|field| format!("block.{}", field.name))
+            .chain(
+                config
+                    .field_selection
+                    .transaction_fields
+                    .iter()
+                    .map(|field| format!("transaction.{}", field.name)),
+            )
+            .collect();
+
+        Self {
+            name: config.name.clone(),
+            ecosystem: match config.ecosystem {
+                Ecosystem::Evm => "evm",
+                Ecosystem::Fuel => "fuel",
+            }
+            .to_string(),
+            contracts,
+            networks,
+            options,
+            field_selection,
+        }
+    }
+}
+
+///Diffs two maps, describing added and removed keys and calling on_changed
+///for keys with different values
+fn diff_maps<K: Ord + Display, V: PartialEq>(
+    kind: &str,
+    old: &BTreeMap<K, V>,
+    new: &BTreeMap<K, V>,
+    changes: &mut Vec<String>,
+    mut on_changed: impl FnMut(&K, &V, &V, &mut Vec<String>),
+) {
+    for (key, old_value) in old {
+        match new.get(key) {
+            None => changes.push(format!("Removed {kind} {key}")),
+            Some(new_value) if new_value != old_value => {
+                on_changed(key, old_value, new_value, changes)
+            }
+            Some(_) => (),
+        }
+    }
+    for key in new.keys() {
+        if !old.contains_key(key) {
+            changes.push(format!("Added {kind} {key}"));
+        }
+    }
+}
+
+fn diff_values<T: PartialEq + std::fmt::Debug>(
+    description: String,
+    old: &T,
+    new: &T,
+    changes: &mut Vec<String>,
+) {
+    if old != new {
+        changes.push(format!("Changed {description} from {old:?} to {new:?}"));
+    }
+}
+
+impl ProjectSnapshot {
+    pub fn from_config(config: &SystemConfig) -> Self {
+        Self {
+            schema: SchemaSnapshot::from_schema(&config.schema),
+            config: ConfigSnapshot::from_config(config),
+        }
+    }
+
+    ///Gets a human readable description of each change from this snapshot to the new one
+    pub fn diff(&self, new: &Self) -> Vec<String> {
+        let mut changes = vec![];
+
+        diff_maps(
+            "entity",
+            &self.schema.entities,
+            &new.schema.entities,
+            &mut changes,
+            |entity, old_fields, new_fields, changes| {
+                diff_maps(
+                    "field",
+                    &prefix_keys(entity, old_fields),
+                    &prefix_keys(entity, new_fields),
+                    changes,
+                    |field, old_type, new_type, changes| {
+                        diff_values(format!("type of {field}"), old_type, new_type, changes)
+                    },
+                )
+            },
+        );
+        diff_maps(
+            "enum",
+            &self.schema.enums,
+            &new.schema.enums,
+            &mut changes,
+            |name, old_values, new_values, changes| {
+                diff_values(
+                    format!("values of enum {name}"),
+                    old_values,
+                    new_values,
+                    changes,
+                )
+            },
+        );
+
+        diff_values(
+            "project name".to_string(),
+            &self.config.name,
+            &new.config.name,
+            &mut changes,
+        );
+        diff_values(
+            "ecosystem".to_string(),
+            &self.config.ecosystem,
+            &new.config.ecosystem,
+            &mut changes,
+        );
+        diff_maps(
+            "contract",
+            &self.config.contracts,
+            &new.config.contracts,
+            &mut changes,
+            |contract, old_contract, new_contract, changes| {
+                diff_values(
+                    format!("handler of contract {contract}"),
+                    &old_contract.handler,
+                    &new_contract.handler,
+                    changes,
+                );
+                diff_maps(
+                    "event",
+                    &prefix_keys(contract, &old_contract.events),
+                    &prefix_keys(contract, &new_contract.events),
+                    changes,
+                    |event, _, _, changes| {
+                        changes.push(format!("Changed signature of event {event}"))
+                    },
+                );
+            },
+        );
+        diff_maps(
+            "network",
+            &self.config.networks,
+            &new.config.networks,
+            &mut changes,
+            |network_id, old_network, new_network, changes| {
+                diff_values(
+                    format!("sync source of network {network_id}"),
+                    &old_network.sync_source,
+                    &new_network.sync_source,
+                    changes,
+                );
+                diff_values(
+                    format!("start block of network {network_id}"),
+                    &old_network.start_block,
+                    &new_network.start_block,
+                    changes,
+                );
+                diff_values(
+                    format!("end block of network {network_id}"),
+                    &old_network.end_block,
+                    &new_network.end_block,
+                    changes,
+                );
+                diff_values(
+                    format!("confirmed block threshold of network {network_id}"),
+                    &old_network.confirmed_block_threshold,
+                    &new_network.confirmed_block_threshold,
+                    changes,
+                );
+                diff_maps(
+                    &format!("contract on network {network_id}:"),
+                    &old_network.contracts,
+                    &new_network.contracts,
+                    changes,
+                    |contract, old_addresses, new_addresses, changes| {
+                        diff_values(
+                            format!("addresses of contract {contract} on network {network_id}"),
+                            old_addresses,
+                            new_addresses,
+                            changes,
+                        )
+                    },
+                );
+            },
+        );
+        diff_maps(
+            "option",
+            &self.config.options,
+            &new.config.options,
+            &mut changes,
+            |option, old_value, new_value, changes| {
+                diff_values(format!("option {option}"), old_value, new_value, changes)
+            },
+        );
+        diff_values(
+            "field selection".to_string(),
+            &self.config.field_selection,
+            &new.config.field_selection,
+            &mut changes,
+        );
+
+        changes
+    }
+}
+
+impl Display for ProjectSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json_string = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        write!(f, "{}", json_string)
+    }
+}
+
+///Prefixes the keys of a nested map with the name of its parent, eg. "Entity.field"
+fn prefix_keys<V: Clone>(prefix: &str, map: &BTreeMap<String, V>) -> BTreeMap<String, V> {
+    map.iter()
+        .map(|(key, value)| (format!("{prefix}.{key}"), value.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NetworkSnapshot, ProjectSnapshot};
+    use serde_json::json;
+
+    fn snapshot(value: serde_json::Value) -> ProjectSnapshot {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn diff_describes_changes() {
+        let old = snapshot(json!({
+            "schema": {
+                "entities": {
+                    "User": {"id": "ID!", "name": "String!", "age": "Int"},
+                    "Vault": {"id": "ID!"}
+                },
+                "enums": {}
+            },
+            "config": {
+                "name": "indexer",
+                "ecosystem": "evm",
+                "contracts": {
+                    "Token": {
+                        "handler": "src/EventHandlers.ts",
+                        "events": {"Transfer": "0x01"}
+                    }
+                },
+                "networks": {
+                    "1": {
+                        "sync_source": "hypersync https://eth.hypersync.xyz",
+                        "start_block": 0,
+                        "end_block": null,
+                        "confirmed_block_threshold": 200,
+                        "contracts": {"Token": ["0xa"]}
+                    }
+                },
+                "options": {"rollback_on_reorg": true},
+                "field_selection": []
+            }
+        }));
+
+        let mut new = old.clone();
+        let user = new.schema.entities.get_mut("User").unwrap();
+        user.remove("age");
+        user.insert("name".to_string(), "String".to_string());
+        user.insert("email".to_string(), "String".to_string());
+        new.schema.entities.remove("Vault");
+        new.config
+            .contracts
+            .get_mut("Token")
+            .unwrap()
+            .events
+            .insert("Approval".to_string(), "0x02".to_string());
+        new.config.networks.get_mut(&1).unwrap().start_block = 100;
+        new.config.networks.insert(137, NetworkSnapshot::default());
+
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                "Removed field User.age",
+                "Changed type of User.name from \"String!\" to \"String\"",
+                "Added field User.email",
+                "Removed entity Vault",
+                "Added event Token.Approval",
+                "Changed start block of network 1 from 0 to 100",
+                "Added network 137",
+            ]
+        );
+    }
+
+    #[test]
+    fn no_diff_for_same_snapshot() {
+        let snapshot = ProjectSnapshot::default();
+        assert!(snapshot.diff(&snapshot.clone()).is_empty());
+    }
+}
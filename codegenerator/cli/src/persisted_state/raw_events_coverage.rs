@@ -0,0 +1,187 @@
+use crate::config_parsing::system_config::SystemConfig;
+use std::collections::BTreeMap;
+
+///Block range of a chain where every processed event is stored in raw_events,
+///as recorded by the indexer in the raw_events_coverage table
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockRange {
+    pub start_block: u64,
+    pub end_block: u64,
+}
+
+///What the db holds to replay raw events, keyed by chain id
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RawEventsCoverage {
+    pub coverage: BTreeMap<u64, BlockRange>,
+    ///Latest processed block of each chain from the event_sync_state table
+    pub processed_blocks: BTreeMap<u64, u64>,
+    ///Block of the first event of each chain from the chain_metadata table
+    pub first_event_blocks: BTreeMap<u64, u64>,
+}
+
+impl RawEventsCoverage {
+    ///Gets the reasons why replaying the raw events wouldn't reproduce everything
+    ///that was processed. Replaying is only safe when there are none.
+    pub fn get_replay_blockers(&self, config: &SystemConfig) -> Vec<String> {
+        let mut blockers = vec![];
+
+        if !config.enable_raw_events {
+            blockers.push("raw_events is not enabled in the config".to_string());
+        }
+
+        //Only the raw event block fields are stored with each raw event
+        let unstored_block_fields: Vec<_> = config
+            .field_selection
+            .block_fields
+            .iter()
+            .filter(|field| !field.skip_raw_events)
+            .map(|field| field.name.clone())
+            .collect();
+        if !unstored_block_fields.is_empty() {
+            blockers.push(format!(
+                "Selected block fields {} are not stored with the raw events",
+                unstored_block_fields.join(", ")
+            ));
+        }
+
+        for network in config.get_networks() {
+            //Chains without processed events have nothing to replay
+            let Some(processed_block) = self.processed_blocks.get(&network.id) else {
+                continue;
+            };
+
+            let first_event_block = self
+                .first_event_blocks
+                .get(&network.id)
+                .copied()
+                .unwrap_or(network.start_block);
+
+            match self.coverage.get(&network.id) {
+                None => blockers.push(format!(
+                    "No raw events coverage is recorded for chain {}",
+                    network.id
+                )),
+                //The coverage starts at the first stored event, so it only misses
+                //events when that is after the first event of the chain
+                Some(range) if range.start_block > first_event_block => blockers.push(format!(
+                    "Raw events of chain {} start at block {} but the first event is at block {}",
+                    network.id, range.start_block, first_event_block
+                )),
+                Some(range) if range.end_block < *processed_block => blockers.push(format!(
+                    "Raw events of chain {} end at block {} but block {} was processed",
+                    network.id, range.end_block, processed_block
+                )),
+                Some(_) => (),
+            }
+        }
+
+        blockers
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BlockRange, RawEventsCoverage};
+    use crate::{config_parsing::system_config::SystemConfig, project_paths::ParsedProjectPaths};
+    use std::collections::BTreeMap;
+
+    fn get_test_config() -> SystemConfig {
+        let project_root = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
+        let project_paths =
+            ParsedProjectPaths::new(&project_root, "generated", "configs/config1.yaml").unwrap();
+        let mut config = SystemConfig::parse_from_project_files(&project_paths).unwrap();
+        config.enable_raw_events = true;
+        config
+    }
+
+    fn get_full_coverage(config: &SystemConfig) -> RawEventsCoverage {
+        let coverage = config
+            .get_networks()
+            .iter()
+            .map(|network| {
+                let range = BlockRange {
+                    start_block: network.start_block + 10,
+                    end_block: network.start_block + 100,
+                };
+                (network.id, range)
+            })
+            .collect();
+        let processed_blocks = config
+            .get_networks()
+            .iter()
+            .map(|network| (network.id, network.start_block + 100))
+            .collect();
+
+        let first_event_blocks = config
+            .get_networks()
+            .iter()
+            .map(|network| (network.id, network.start_block + 10))
+            .collect();
+
+        RawEventsCoverage {
+            coverage,
+            processed_blocks,
+            first_event_blocks,
+        }
+    }
+
+    #[test]
+    fn full_coverage_can_be_replayed() {
+        let config = get_test_config();
+        let raw_events_coverage = get_full_coverage(&config);
+
+        assert_eq!(
+            raw_events_coverage.get_replay_blockers(&config),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn incomplete_coverage_blocks_replay() {
+        let mut config = get_test_config();
+        let mut raw_events_coverage = get_full_coverage(&config);
+        let network = config.get_networks()[0];
+        let (chain_id, start_block) = (network.id, network.start_block);
+        raw_events_coverage
+            .processed_blocks
+            .insert(chain_id, start_block + 200);
+
+        assert_eq!(
+            raw_events_coverage.get_replay_blockers(&config),
+            vec![format!(
+                "Raw events of chain {} end at block {} but block {} was processed",
+                chain_id,
+                start_block + 100,
+                start_block + 200
+            )]
+        );
+
+        raw_events_coverage
+            .processed_blocks
+            .insert(chain_id, start_block + 100);
+        raw_events_coverage
+            .first_event_blocks
+            .insert(chain_id, start_block + 5);
+
+        assert_eq!(
+            raw_events_coverage.get_replay_blockers(&config),
+            vec![format!(
+                "Raw events of chain {} start at block {} but the first event is at block {}",
+                chain_id,
+                start_block + 10,
+                start_block + 5
+            )]
+        );
+
+        config.enable_raw_events = false;
+        raw_events_coverage.coverage = BTreeMap::new();
+
+        assert_eq!(
+            raw_events_coverage.get_replay_blockers(&config),
+            vec![
+                "raw_events is not enabled in the config".to_string(),
+                format!("No raw events coverage is recorded for chain {chain_id}")
+            ]
+        );
+    }
+}
@@ -0,0 +1,486 @@
+use crate::config_parsing::entity_parsing::{Entity, Schema};
+use anyhow::Context;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display},
+};
+
+///A column of an entity table as it gets created by the db migrations
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSnapshot {
+    pub field_type: String,
+    pub is_array: bool,
+    pub is_nullable: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct TableSnapshot {
+    pub columns: BTreeMap<String, ColumnSnapshot>,
+    ///Single and composite indices by the fields they index
+    pub indices: BTreeSet<Vec<String>>,
+    ///Fulltext index names mapped to their language and fields
+    pub full_text_indices: BTreeMap<String, String>,
+    ///A canonical description of the rollup of an aggregation entity, so that
+    ///changing how an entity is aggregated requires its data to be rebuilt
+    pub aggregation: Option<String>,
+}
+
+///The structure of the db that the migrations create for a schema. It's persisted
+///with the rest of the state so that a schema change can be diffed against what
+///was actually migrated.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct DbSchemaSnapshot {
+    pub tables: BTreeMap<String, TableSnapshot>,
+    pub enums: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    AddedTable(String),
+    RemovedTable(String),
+    AddedColumn {
+        table: String,
+        column: String,
+        is_nullable: bool,
+    },
+    RemovedColumn {
+        table: String,
+        column: String,
+    },
+    ChangedColumn {
+        table: String,
+        column: String,
+    },
+    AddedIndex {
+        table: String,
+        fields: Vec<String>,
+    },
+    RemovedIndex {
+        table: String,
+        fields: Vec<String>,
+    },
+    AddedFullTextIndex {
+        table: String,
+        name: String,
+    },
+    ChangedFullTextIndex {
+        table: String,
+        name: String,
+    },
+    ChangedAggregation(String),
+    AddedEnum(String),
+    ChangedEnum(String),
+    ///The db was migrated without a snapshot of its schema, eg. by a previous envio version
+    UnknownPreviousSchema,
+}
+
+impl SchemaChange {
+    ///Only additive changes can be applied to an existing db by rerunning the
+    ///migrations, since they create tables, columns and indices if they don't exist.
+    ///Anything else needs the db to be reset and the indexer to resync.
+    pub fn is_destructive(&self) -> bool {
+        !matches!(
+            self,
+            Self::AddedTable(_)
+                | Self::AddedColumn {
+                    is_nullable: true,
+                    ..
+                }
+                | Self::AddedIndex { .. }
+                | Self::AddedFullTextIndex { .. }
+                | Self::AddedEnum(_)
+        )
+    }
+}
+
+impl Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AddedTable(table) => write!(f, "Added table \"{table}\""),
+            Self::RemovedTable(table) => write!(f, "Removed table \"{table}\""),
+            Self::AddedColumn {
+                table,
+                column,
+                is_nullable: true,
+            } => write!(f, "Added nullable column \"{table}.{column}\""),
+            Self::AddedColumn {
+                table,
+                column,
+                is_nullable: false,
+            } => write!(f, "Added non-nullable column \"{table}.{column}\""),
+            Self::RemovedColumn { table, column } => {
+                write!(f, "Removed column \"{table}.{column}\"")
+            }
+            Self::ChangedColumn { table, column } => {
+                write!(f, "Changed type of column \"{table}.{column}\"")
+            }
+            Self::AddedIndex { table, fields } => {
+                write!(f, "Added index on \"{table}\" ({})", fields.join(", "))
+            }
+            Self::RemovedIndex { table, fields } => {
+                write!(f, "Removed index on \"{table}\" ({})", fields.join(", "))
+            }
+            Self::AddedFullTextIndex { table, name } => {
+                write!(f, "Added fulltext index \"{name}\" on \"{table}\"")
+            }
+            Self::ChangedFullTextIndex { table, name } => {
+                write!(
+                    f,
+                    "Changed or removed fulltext index \"{name}\" on \"{table}\""
+                )
+            }
+            Self::ChangedAggregation(table) => write!(f, "Changed aggregation of \"{table}\""),
+            Self::AddedEnum(name) => write!(f, "Added enum \"{name}\""),
+            Self::ChangedEnum(name) => write!(f, "Changed or removed enum \"{name}\""),
+            Self::UnknownPreviousSchema => {
+                write!(f, "The schema the db was migrated with is unknown")
+            }
+        }
+    }
+}
+
+impl TableSnapshot {
+    fn from_entity(entity: &Entity, schema: &Schema) -> anyhow::Result<Self> {
+        let mut columns = BTreeMap::new();
+        let mut indices = BTreeSet::new();
+
+        for field in entity.get_fields() {
+            if let Some(pg_field) = field.get_postgres_field(schema, entity)? {
+                let column = field.get_db_column_name(schema)?;
+                if pg_field.is_index {
+                    indices.insert(vec![column.clone()]);
+                }
+                columns.insert(
+                    column,
+                    ColumnSnapshot {
+                        field_type: pg_field.field_type.to_string(),
+                        is_array: pg_field.is_array,
+                        is_nullable: pg_field.is_nullable,
+                    },
+                );
+            }
+        }
+
+        indices.extend(entity.get_composite_indices());
+
+        let full_text_indices = entity
+            .full_text_indexes
+            .iter()
+            .map(|index| {
+                (
+                    index.name.clone(),
+                    format!("{}({})", index.language, index.fields.join(", ")),
+                )
+            })
+            .collect();
+
+        let aggregation = entity.aggregation.as_ref().map(|aggregation| {
+            let aggregates = aggregation
+                .aggregates
+                .iter()
+                .sorted_by_key(|(field_name, _)| field_name.to_string())
+                .map(|(field_name, aggregate)| {
+                    format!(
+                        "{field_name}={}({})",
+                        aggregate.function,
+                        aggregate.arg.clone().unwrap_or_default()
+                    )
+                })
+                .join(", ");
+            format!(
+                "{} [{}] {{{aggregates}}}",
+                aggregation.source,
+                aggregation.intervals.iter().join(", ")
+            )
+        });
+
+        Ok(Self {
+            columns,
+            indices,
+            full_text_indices,
+            aggregation,
+        })
+    }
+
+    fn diff(&self, new: &Self, table: &str) -> Vec<SchemaChange> {
+        let table = table.to_string();
+        let mut changes = vec![];
+
+        for (column, old_column) in &self.columns {
+            match new.columns.get(column) {
+                None => changes.push(SchemaChange::RemovedColumn {
+                    table: table.clone(),
+                    column: column.clone(),
+                }),
+                Some(new_column) if new_column != old_column => {
+                    changes.push(SchemaChange::ChangedColumn {
+                        table: table.clone(),
+                        column: column.clone(),
+                    })
+                }
+                Some(_) => (),
+            }
+        }
+        for (column, new_column) in &new.columns {
+            if !self.columns.contains_key(column) {
+                changes.push(SchemaChange::AddedColumn {
+                    table: table.clone(),
+                    column: column.clone(),
+                    is_nullable: new_column.is_nullable,
+                });
+            }
+        }
+
+        for fields in self.indices.difference(&new.indices) {
+            changes.push(SchemaChange::RemovedIndex {
+                table: table.clone(),
+                fields: fields.clone(),
+            });
+        }
+        for fields in new.indices.difference(&self.indices) {
+            changes.push(SchemaChange::AddedIndex {
+                table: table.clone(),
+                fields: fields.clone(),
+            });
+        }
+
+        for (name, old_index) in &self.full_text_indices {
+            if new.full_text_indices.get(name) != Some(old_index) {
+                changes.push(SchemaChange::ChangedFullTextIndex {
+                    table: table.clone(),
+                    name: name.clone(),
+                });
+            }
+        }
+        for name in new.full_text_indices.keys() {
+            if !self.full_text_indices.contains_key(name) {
+                changes.push(SchemaChange::AddedFullTextIndex {
+                    table: table.clone(),
+                    name: name.clone(),
+                });
+            }
+        }
+
+        if self.aggregation != new.aggregation {
+            changes.push(SchemaChange::ChangedAggregation(table));
+        }
+
+        changes
+    }
+}
+
+impl DbSchemaSnapshot {
+    pub fn from_schema(schema: &Schema) -> anyhow::Result<Self> {
+        let tables = schema
+            .entities
+            .values()
+            .map(|entity| {
+                let table = TableSnapshot::from_entity(entity, schema).context(format!(
+                    "Failed creating db snapshot of entity {}",
+                    entity.name
+                ))?;
+                Ok((entity.get_table_name(), table))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        let enums = schema
+            .enums
+            .values()
+            .map(|gql_enum| (gql_enum.name.clone(), gql_enum.values.clone()))
+            .collect();
+
+        Ok(Self { tables, enums })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty() && self.enums.is_empty()
+    }
+
+    ///Gets the changes needed to migrate a db created from this snapshot to
+    ///the structure of the new snapshot
+    pub fn diff(&self, new: &Self) -> Vec<SchemaChange> {
+        let mut changes = vec![];
+
+        for (name, old_values) in &self.enums {
+            if new.enums.get(name) != Some(old_values) {
+                changes.push(SchemaChange::ChangedEnum(name.clone()));
+            }
+        }
+        for name in new.enums.keys() {
+            if !self.enums.contains_key(name) {
+                changes.push(SchemaChange::AddedEnum(name.clone()));
+            }
+        }
+
+        for (table_name, old_table) in &self.tables {
+            match new.tables.get(table_name) {
+                None => changes.push(SchemaChange::RemovedTable(table_name.clone())),
+                Some(new_table) => changes.extend(old_table.diff(new_table, table_name)),
+            }
+        }
+        for table_name in new.tables.keys() {
+            if !self.tables.contains_key(table_name) {
+                changes.push(SchemaChange::AddedTable(table_name.clone()));
+            }
+        }
+
+        changes
+    }
+}
+
+impl Display for DbSchemaSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json_string = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        write!(f, "{}", json_string)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DbSchemaSnapshot, SchemaChange};
+    use crate::config_parsing::entity_parsing::Schema;
+
+    fn snapshot(schema_str: &str) -> DbSchemaSnapshot {
+        let schema_doc = graphql_parser::schema::parse_schema::<String>(schema_str).unwrap();
+        let schema = Schema::from_document(schema_doc).unwrap();
+        DbSchemaSnapshot::from_schema(&schema).unwrap()
+    }
+
+    const SCHEMA: &str = r#"
+enum Status {
+  ACTIVE
+  INACTIVE
+}
+
+type User {
+  id: ID!
+  name: String!
+  status: Status!
+  vault: Vault
+}
+
+type Vault {
+  id: ID!
+  balance: BigInt!
+  owner: User!
+}
+    "#;
+
+    #[test]
+    fn no_changes() {
+        assert_eq!(snapshot(SCHEMA).diff(&snapshot(SCHEMA)), vec![]);
+    }
+
+    #[test]
+    fn additive_changes() {
+        let new_schema = r#"
+enum Status {
+  ACTIVE
+  INACTIVE
+}
+
+enum Kind {
+  A
+  B
+}
+
+type User {
+  id: ID!
+  name: String! @index
+  status: Status!
+  vault: Vault
+  nickname: String
+  kind: Kind
+}
+
+type Vault {
+  id: ID!
+  balance: BigInt!
+  owner: User!
+}
+
+type Deposit @index(fields: ["vault", "amount"]) {
+  id: ID!
+  vault: Vault!
+  amount: BigInt!
+}
+    "#;
+
+        let changes = snapshot(SCHEMA).diff(&snapshot(new_schema));
+
+        assert_eq!(
+            changes,
+            vec![
+                SchemaChange::AddedEnum("Kind".to_string()),
+                SchemaChange::AddedColumn {
+                    table: "User".to_string(),
+                    column: "kind".to_string(),
+                    is_nullable: true
+                },
+                SchemaChange::AddedColumn {
+                    table: "User".to_string(),
+                    column: "nickname".to_string(),
+                    is_nullable: true
+                },
+                SchemaChange::AddedIndex {
+                    table: "User".to_string(),
+                    fields: vec!["name".to_string()]
+                },
+                SchemaChange::AddedTable("Deposit".to_string()),
+            ]
+        );
+        assert!(changes.iter().all(|change| !change.is_destructive()));
+    }
+
+    #[test]
+    fn destructive_changes() {
+        let new_schema = r#"
+enum Status {
+  ACTIVE
+  INACTIVE
+  CLOSED
+}
+
+type User {
+  id: ID!
+  name: String
+  status: Status!
+  age: Int!
+}
+    "#;
+
+        let changes = snapshot(SCHEMA).diff(&snapshot(new_schema));
+
+        assert_eq!(
+            changes,
+            vec![
+                SchemaChange::ChangedEnum("Status".to_string()),
+                SchemaChange::ChangedColumn {
+                    table: "User".to_string(),
+                    column: "name".to_string()
+                },
+                SchemaChange::RemovedColumn {
+                    table: "User".to_string(),
+                    column: "vault_id".to_string()
+                },
+                SchemaChange::AddedColumn {
+                    table: "User".to_string(),
+                    column: "age".to_string(),
+                    is_nullable: false
+                },
+                SchemaChange::RemovedTable("Vault".to_string()),
+            ]
+        );
+        assert!(changes.iter().all(|change| change.is_destructive()));
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_json() {
+        let snapshot = snapshot(SCHEMA);
+        let parsed: DbSchemaSnapshot = serde_json::from_str(&snapshot.to_string()).unwrap();
+        assert_eq!(parsed, snapshot);
+    }
+}
@@ -7,7 +7,6 @@ use std::path::PathBuf;
 
 #[derive(Serialize, Debug, Eq, PartialEq, Clone)]
 pub struct HandlerPathsTemplate {
-    absolute: String,
     relative_to_generated_src: String,
     relative_to_config: String,
     relative_to_project_root: String,
@@ -46,13 +45,8 @@ impl HandlerPathsTemplate {
             .ok_or_else(|| anyhow!("Handler path should be unicode"))?
             .to_string();
 
-        let absolute = absolute_path
-            .to_str()
-            .ok_or_else(|| anyhow!("Handler path should be unicode"))?
-            .to_string();
-
+        //Only relative paths are templated so that the output is the same on any machine
         Ok(HandlerPathsTemplate {
-            absolute,
             relative_to_generated_src,
             relative_to_config,
             relative_to_project_root,
@@ -128,8 +122,6 @@ mod tests {
         .expect("Failed getting contract handler_paths_template");
 
         let expected_handler_paths = super::HandlerPathsTemplate {
-            absolute: "test/configs/src/EventHandler.js".to_string(),
-
             relative_to_generated_src: "../../configs/src/EventHandler.js".to_string(),
             relative_to_config: "src/EventHandler.js".to_string(),
             relative_to_project_root: "configs/src/EventHandler.js".to_string(), //Note since its
@@ -6,6 +6,7 @@ use anyhow::{anyhow, Result};
 use core::fmt;
 use itertools::Itertools;
 use serde::Serialize;
+use serde_json::{json, Value};
 use std::{collections::HashSet, fmt::Display};
 
 pub struct RescriptTypeDeclMulti(Vec<RescriptTypeDecl>);
@@ -261,6 +262,120 @@ impl RescriptTypeExpr {
         }
     }
 
+    ///The TypeScript type genType generates for the ReScript type. Record fields use
+    ///their @as name and variants are tagged by the case field.
+    pub fn to_ts_type(&self) -> String {
+        match self {
+            Self::Identifier(type_ident) => type_ident.to_ts_type(),
+            Self::Record(fields) => {
+                let fields_str = fields
+                    .iter()
+                    .map(|field| {
+                        format!(
+                            "readonly {}: {}",
+                            field.get_js_name(),
+                            field.type_ident.to_ts_type()
+                        )
+                    })
+                    .join("; ");
+                if fields_str.is_empty() {
+                    "{}".to_string()
+                } else {
+                    format!("{{ {fields_str} }}")
+                }
+            }
+            Self::Variant(constructors) => constructors
+                .iter()
+                .map(|constr| {
+                    format!(
+                        "{{ readonly case: \"{}\"; readonly payload: {} }}",
+                        constr.name,
+                        constr.payload.to_ts_type()
+                    )
+                })
+                .join(" | "),
+        }
+    }
+
+    ///The JSON Schema of the JSON representation of the ReScript type. Optional record
+    ///fields aren't required and can be null.
+    pub fn to_json_schema(&self) -> Value {
+        match self {
+            Self::Identifier(type_ident) => type_ident.to_json_schema(),
+            Self::Record(fields) => {
+                let properties: serde_json::Map<String, Value> = fields
+                    .iter()
+                    .map(|field| {
+                        (
+                            field.get_js_name().clone(),
+                            field.type_ident.to_json_schema(),
+                        )
+                    })
+                    .collect();
+                let required: Vec<&String> = fields
+                    .iter()
+                    .filter(|field| !matches!(field.type_ident, RescriptTypeIdent::Option(_)))
+                    .map(|field| field.get_js_name())
+                    .collect();
+                json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                })
+            }
+            Self::Variant(constructors) => json!({
+                "anyOf": constructors
+                    .iter()
+                    .map(|constr| {
+                        json!({
+                            "type": "object",
+                            "properties": {
+                                "case": { "const": constr.name },
+                                "payload": constr.payload.to_json_schema(),
+                            },
+                            "required": ["case", "payload"],
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            }),
+        }
+    }
+
+    ///The Zod schema of the JSON representation of the ReScript type, the same as
+    ///to_json_schema
+    pub fn to_zod_schema(&self) -> String {
+        match self {
+            Self::Identifier(type_ident) => type_ident.to_zod_schema(),
+            Self::Record(fields) => {
+                let fields_str = fields
+                    .iter()
+                    .map(|field| {
+                        format!(
+                            "{}: {}",
+                            serde_json::to_string(field.get_js_name())
+                                .expect("Failed serializing string"),
+                            field.type_ident.to_zod_schema()
+                        )
+                    })
+                    .join(", ");
+                format!("z.object({{ {fields_str} }})")
+            }
+            Self::Variant(constructors) => {
+                let constrs_str = constructors
+                    .iter()
+                    .map(|constr| {
+                        format!(
+                            "z.object({{ case: z.literal(\"{}\"), payload: {} }})",
+                            constr.name,
+                            constr.payload.to_zod_schema()
+                        )
+                    })
+                    .join(", ");
+                format!("z.discriminatedUnion(\"case\", [{constrs_str}])")
+            }
+        }
+    }
+
     pub fn dependencies(&self) -> Vec<String> {
         match self {
             Self::Identifier(type_ident) => type_ident.dependencies(),
@@ -276,6 +391,10 @@ impl RescriptTypeExpr {
     }
 }
 
+const BIG_INT_PATTERN: &str = "^-?[0-9]+$";
+const BIG_DECIMAL_PATTERN: &str = "^-?[0-9]+(\\.[0-9]+)?(e[-+]?[0-9]+)?$";
+const ADDRESS_PATTERN: &str = "^0x[0-9a-fA-F]+$";
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct RescriptRecordField {
     pub name: String,
@@ -312,6 +431,11 @@ impl RescriptRecordField {
         }
     }
 
+    ///The name of the field in JS, which is the @as name if there is one
+    pub fn get_js_name(&self) -> &String {
+        self.as_name.as_ref().unwrap_or(&self.name)
+    }
+
     fn to_string(&self) -> String {
         let as_prefix = self
             .as_name
@@ -478,6 +602,103 @@ impl RescriptTypeIdent {
         }
     }
 
+    ///The TypeScript type genType generates for the ReScript type
+    pub fn to_ts_type(&self) -> String {
+        match self {
+            Self::Unit => "void".to_string(),
+            Self::Int | Self::Float => "number".to_string(),
+            Self::BigInt => "bigint".to_string(),
+            Self::BigDecimal => "BigDecimal".to_string(),
+            Self::Address => "Address".to_string(),
+            Self::String => "string".to_string(),
+            Self::ID => "Id".to_string(),
+            Self::Bool => "boolean".to_string(),
+            Self::Unknown => "unknown".to_string(),
+            Self::Timestamp => "Date".to_string(),
+            Self::Array(inner_type) => format!("{}[]", inner_type.to_ts_type()),
+            Self::Option(inner_type) => format!("(undefined | {})", inner_type.to_ts_type()),
+            Self::Tuple(inner_types) => {
+                format!(
+                    "[{}]",
+                    inner_types.iter().map(|t| t.to_ts_type()).join(", ")
+                )
+            }
+            Self::SchemaEnum(enum_name) => enum_name.capitalized.clone(),
+            Self::GenericParam(name) => name.clone(),
+            Self::TypeApplication { name, type_params } if type_params.is_empty() => name.clone(),
+            Self::TypeApplication { name, type_params } => {
+                format!(
+                    "{name}<{}>",
+                    type_params.iter().map(|p| p.to_ts_type()).join(", ")
+                )
+            }
+        }
+    }
+
+    ///The JSON Schema of the JSON representation of the ReScript type. BigInt and
+    ///BigDecimal values are strings since JSON numbers can't hold them without losing
+    ///precision.
+    pub fn to_json_schema(&self) -> Value {
+        match self {
+            Self::Unit => json!({ "type": "null" }),
+            Self::Int => json!({ "type": "integer" }),
+            Self::Float => json!({ "type": "number" }),
+            Self::BigInt => json!({ "type": "string", "pattern": BIG_INT_PATTERN }),
+            Self::BigDecimal => json!({ "type": "string", "pattern": BIG_DECIMAL_PATTERN }),
+            Self::Address => json!({ "type": "string", "pattern": ADDRESS_PATTERN }),
+            Self::String | Self::ID => json!({ "type": "string" }),
+            Self::Bool => json!({ "type": "boolean" }),
+            Self::Timestamp => json!({ "type": "string", "format": "date-time" }),
+            Self::Array(inner_type) => json!({
+                "type": "array",
+                "items": inner_type.to_json_schema(),
+            }),
+            Self::Option(inner_type) => json!({
+                "anyOf": [inner_type.to_json_schema(), { "type": "null" }],
+            }),
+            Self::Tuple(inner_types) => json!({
+                "type": "array",
+                "prefixItems": inner_types
+                    .iter()
+                    .map(|t| t.to_json_schema())
+                    .collect::<Vec<_>>(),
+                "minItems": inner_types.len(),
+                "maxItems": inner_types.len(),
+            }),
+            Self::SchemaEnum(enum_name) => {
+                json!({ "$ref": format!("#/$defs/{}", enum_name.capitalized) })
+            }
+            //Not used by event params or entities, so any value is valid
+            Self::Unknown | Self::GenericParam(_) | Self::TypeApplication { .. } => json!({}),
+        }
+    }
+
+    ///The Zod schema of the JSON representation of the ReScript type, the same as
+    ///to_json_schema
+    pub fn to_zod_schema(&self) -> String {
+        match self {
+            Self::Unit => "z.null()".to_string(),
+            Self::Int => "z.number().int()".to_string(),
+            Self::Float => "z.number()".to_string(),
+            Self::BigInt => format!("z.string().regex(/{BIG_INT_PATTERN}/)"),
+            Self::BigDecimal => format!("z.string().regex(/{BIG_DECIMAL_PATTERN}/)"),
+            Self::Address => format!("z.string().regex(/{ADDRESS_PATTERN}/)"),
+            Self::String | Self::ID => "z.string()".to_string(),
+            Self::Bool => "z.boolean()".to_string(),
+            Self::Timestamp => "z.string().datetime()".to_string(),
+            Self::Array(inner_type) => format!("z.array({})", inner_type.to_zod_schema()),
+            Self::Option(inner_type) => format!("{}.nullish()", inner_type.to_zod_schema()),
+            Self::Tuple(inner_types) => format!(
+                "z.tuple([{}])",
+                inner_types.iter().map(|t| t.to_zod_schema()).join(", ")
+            ),
+            Self::SchemaEnum(enum_name) => enum_name.capitalized.clone(),
+            Self::Unknown | Self::GenericParam(_) | Self::TypeApplication { .. } => {
+                "z.unknown()".to_string()
+            }
+        }
+    }
+
     pub fn dependencies(&self) -> Vec<String> {
         match self {
             Self::Unit
@@ -681,6 +902,26 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_to_ts_type() {
+        assert_eq!(
+            RescriptTypeIdent::option(RescriptTypeIdent::array(RescriptTypeIdent::Tuple(vec![
+                RescriptTypeIdent::BigInt,
+                RescriptTypeIdent::Address
+            ])))
+            .to_ts_type(),
+            "(undefined | [bigint, Address][])".to_string()
+        );
+        assert_eq!(
+            RescriptTypeExpr::Record(vec![
+                RescriptRecordField::new("From".to_string(), RescriptTypeIdent::Address),
+                RescriptRecordField::new("value".to_string(), RescriptTypeIdent::Int),
+            ])
+            .to_ts_type(),
+            "{ readonly From: Address; readonly value: number }".to_string()
+        );
+    }
+
     #[test]
     fn test_to_rescript_schema() {
         assert_eq!(
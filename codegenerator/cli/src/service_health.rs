@@ -1,19 +1,40 @@
+use crate::dev_services::{DevServices, PostgresSettings};
+use sqlx::{Connection, PgConnection};
 use tokio::time::{timeout, Duration};
 
 // NOTE: This assumes the hasura graphql availability means the postgres database is also available
 const MAXIMUM_BACKOFF: Duration = Duration::from_secs(120); // Likely the user will kill this before it gets here but wanted to make it quite large to allow for users with slow computers
 const BACKOFF_INCREMENT: Duration = Duration::from_secs(1);
-const HASURA_ENDPOINT: &str = "http://localhost:8080/"; // todo: is this available somewhere
 
 // Function to fetch the health of the Hasura service
-pub async fn fetch_hasura_healthz() -> Result<bool, reqwest::Error> {
+pub async fn fetch_hasura_healthz(hasura_url: &str) -> Result<bool, reqwest::Error> {
     let client = reqwest::Client::new();
-    let url = format!("{}/hasura/healthz?strict=true", HASURA_ENDPOINT);
+    let url = format!("{}/hasura/healthz?strict=true", hasura_url);
     let response = client.get(&url).send().await?;
     let is_success = response.status().is_success();
     Ok(is_success)
 }
 
+///Checks that the db accepts connections and queries
+pub async fn fetch_postgres_health(postgres: &PostgresSettings) -> Result<bool, sqlx::Error> {
+    let mut connection = PgConnection::connect(&postgres.get_connection_url()).await?;
+    sqlx::query("SELECT 1").execute(&mut connection).await?;
+    connection.close().await?;
+    Ok(true)
+}
+
+///Checks Hasura when it's enabled and Postgres directly otherwise
+pub async fn fetch_services_health(dev_services: &DevServices) -> Result<bool, String> {
+    match &dev_services.hasura_url {
+        Some(hasura_url) => fetch_hasura_healthz(hasura_url)
+            .await
+            .map_err(|err| err.to_string()),
+        None => fetch_postgres_health(&dev_services.postgres)
+            .await
+            .map_err(|err| err.to_string()),
+    }
+}
+
 fn delete_last_println() {
     const ERASE_ANSI_ESCAPE_CODE: &str = "\x1B[1A\x1B[2K";
     print!("{ERASE_ANSI_ESCAPE_CODE}");
@@ -25,13 +46,13 @@ pub enum EndpointHealth {
     Unhealthy(String),
 }
 
-pub async fn fetch_hasura_healthz_with_retry() -> EndpointHealth {
+pub async fn fetch_services_health_with_retry(dev_services: &DevServices) -> EndpointHealth {
     let mut refetch_delay = BACKOFF_INCREMENT;
 
     let mut first_run = true;
 
     loop {
-        match timeout(refetch_delay, fetch_hasura_healthz()).await {
+        match timeout(refetch_delay, fetch_services_health(dev_services)).await {
             Ok(Ok(success)) => {
                 if success {
                     break EndpointHealth::Healthy;
@@ -54,7 +75,7 @@ pub async fn fetch_hasura_healthz_with_retry() -> EndpointHealth {
                     first_run = false;
                 }
                 println!(
-                    "Waiting for the docker services to become available. {} seconds.",
+                    "Waiting for the services to become available. {} seconds.",
                     refetch_delay.as_secs()
                 );
             }
@@ -1,4 +1,7 @@
-use crate::cli_args::init_config::{evm, fuel, Language};
+use crate::{
+    cli_args::init_config::{evm, fuel, Language},
+    constants::project_paths::{TEMPLATE_OVERRIDES_PATH, TEMPLATE_PARTIALS_PATH},
+};
 use anyhow::{anyhow, Context, Result};
 use include_dir::{include_dir, Dir, DirEntry};
 use pathdiff::diff_paths;
@@ -104,6 +107,62 @@ impl<'a> RelativeDir<'a> {
         self.dir.entries()
     }
 
+    ///Gets the contents of all files in the dir and its sub dirs, paired with their
+    ///path relative to the parent path, which is where extract would write them.
+    ///Files overridden by project local templates have the contents of the override.
+    pub fn get_files_with_overrides(
+        &self,
+        overrides: &TemplateOverrides,
+    ) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        let mut files = vec![];
+        for entry in self.dir.entries() {
+            match entry {
+                DirEntry::Dir(dir) => {
+                    files.extend(self.new_child(dir).get_files_with_overrides(overrides)?)
+                }
+                DirEntry::File(f) => {
+                    let contents = match overrides.get_file(f.path())? {
+                        Some(contents) => contents,
+                        None => f.contents().to_vec(),
+                    };
+                    files.push((self.diff_path_from_parent(f.path())?, contents));
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    ///Gets the paths of all files in the dir and its sub dirs within the embedded
+    ///templates, which is the path that overrides shadow
+    pub fn get_embedded_file_paths(&self) -> Vec<PathBuf> {
+        let mut paths = vec![];
+        for entry in self.dir.entries() {
+            match entry {
+                DirEntry::Dir(dir) => paths.extend(self.new_child(dir).get_embedded_file_paths()),
+                DirEntry::File(f) => paths.push(f.path().to_path_buf()),
+            }
+        }
+        paths
+    }
+
+    ///Extracts the files like extract, writing the project local override instead
+    ///of the embedded file where one exists
+    pub fn extract_with_overrides<S: AsRef<Path>>(
+        &self,
+        base_path: S,
+        overrides: &TemplateOverrides,
+    ) -> Result<()> {
+        let base_path = base_path.as_ref();
+        for (rel_path, contents) in self.get_files_with_overrides(overrides)? {
+            let path = base_path.join(rel_path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, contents)?;
+        }
+        Ok(())
+    }
+
     /// Create directories and extract all files to real filesystem.
     /// Creates parent directories of `path` if they do not already exist.
     /// Fails if some files already exist.
@@ -133,6 +192,118 @@ impl<'a> RelativeDir<'a> {
     }
 }
 
+///Templates in the templates dir of a project, which shadow the embedded codegen
+///templates with the same relative path. For example
+///templates/dynamic/codegen/src/Handlers.res.hbs replaces the embedded
+///dynamic/codegen/src/Handlers.res.hbs
+pub struct TemplateOverrides {
+    dir: PathBuf,
+}
+
+impl TemplateOverrides {
+    pub fn new(project_root: &Path) -> Self {
+        Self {
+            dir: project_root.join(TEMPLATE_OVERRIDES_PATH),
+        }
+    }
+
+    pub fn get_dir(&self) -> &Path {
+        &self.dir
+    }
+
+    ///Gets the contents of the override of an embedded file, given its path within
+    ///the embedded templates
+    pub fn get_file(&self, embedded_path: &Path) -> Result<Option<Vec<u8>>> {
+        let path = self.dir.join(embedded_path);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        fs::read(&path).map(Some).context(format!(
+            "Failed reading template override {}",
+            path.display()
+        ))
+    }
+
+    fn read_paths_recursive(&self, dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+        for entry in fs::read_dir(dir).context(format!("Failed reading {}", dir.display()))? {
+            let path = entry?.path();
+            if path.is_dir() {
+                self.read_paths_recursive(&path, paths)?;
+            } else {
+                paths.push(self.diff_path(&path)?);
+            }
+        }
+        Ok(())
+    }
+
+    fn diff_path(&self, path: &Path) -> Result<PathBuf> {
+        diff_paths(path, &self.dir)
+            .ok_or_else(|| anyhow!("Failed getting path of {} in templates", path.display()))
+    }
+
+    ///Gets the paths of all files in the overrides dir relative to it. Sorted, so
+    ///that hashing them doesn't depend on the order of the file system.
+    pub fn get_paths(&self) -> Result<Vec<PathBuf>> {
+        let mut paths = vec![];
+        if self.dir.is_dir() {
+            self.read_paths_recursive(&self.dir, &mut paths)?;
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    ///Gets the paths of the overrides of the templates in src/db, which define the
+    ///tables and how the indexer writes to them
+    pub fn get_db_paths(&self) -> Result<Vec<PathBuf>> {
+        let db_template_dirs = [
+            Path::new("static/codegen/src/db"),
+            Path::new("dynamic/codegen/src/db"),
+        ];
+        Ok(self
+            .get_paths()?
+            .into_iter()
+            .filter(|path| db_template_dirs.iter().any(|dir| path.starts_with(dir)))
+            .collect())
+    }
+
+    ///Gets the handlebars partials in templates/partials paired with their name, which
+    ///is their path without the .hbs extension. Overridden templates can use them as
+    ///helpers with {{> name}}.
+    pub fn get_partials(&self) -> Result<Vec<(String, String)>> {
+        let mut partials = vec![];
+        for path in self.get_paths()? {
+            let Ok(partial_path) = path.strip_prefix(TEMPLATE_PARTIALS_PATH) else {
+                continue;
+            };
+            if partial_path.extension().is_none_or(|ext| ext != "hbs") {
+                continue;
+            }
+            let name = partial_path
+                .with_extension("")
+                .to_str()
+                .ok_or_else(|| anyhow!("Invalid partial path {}", partial_path.display()))?
+                .replace('\\', "/");
+            let contents = fs::read_to_string(self.dir.join(&path))
+                .context(format!("Failed reading partial {}", path.display()))?;
+            partials.push((name, contents));
+        }
+        Ok(partials)
+    }
+
+    ///Gets the override files that don't shadow any embedded codegen template and so
+    ///are never used, which is usually a typo in the path
+    pub fn get_unmatched_paths(&self) -> Result<Vec<PathBuf>> {
+        let codegen_paths = TemplateDirs::new().get_codegen_file_paths()?;
+        Ok(self
+            .get_paths()?
+            .into_iter()
+            .filter(|path| {
+                !path.starts_with(TEMPLATE_PARTIALS_PATH) && !codegen_paths.contains(path)
+            })
+            .collect())
+    }
+}
+
 ///A Client object for interfacing with the templates directory
 pub struct TemplateDirs<'a> {
     dir: TemplateDir<'a>,
@@ -183,6 +354,35 @@ impl<'a> TemplateDirs<'a> {
         self.get_codegen_dir(TemplateType::Dynamic)
     }
 
+    ///Gets the templates/dynamic/graphql_client directory
+    pub fn get_graphql_client_dynamic_dir(&self) -> Result<RelativeDir<'a>> {
+        self.get_dynamic_dir("graphql_client")
+    }
+
+    ///Gets the templates/dynamic/validators directory
+    pub fn get_validators_dynamic_dir(&self) -> Result<RelativeDir<'a>> {
+        self.get_dynamic_dir("validators")
+    }
+
+    ///Gets the paths of all static and dynamic codegen templates within the embedded
+    ///templates, which are the files that can be overridden
+    pub fn get_codegen_file_paths(&self) -> Result<Vec<PathBuf>> {
+        let mut paths = self.get_codegen_static_dir()?.get_embedded_file_paths();
+        paths.extend(self.get_codegen_dynamic_dir()?.get_embedded_file_paths());
+        paths.extend(
+            self.get_graphql_client_dynamic_dir()?
+                .get_embedded_file_paths(),
+        );
+        paths.extend(self.get_validators_dynamic_dir()?.get_embedded_file_paths());
+        paths.sort();
+        Ok(paths)
+    }
+
+    ///Gets the contents of a file given its path within the embedded templates
+    pub fn get_file_contents(&self, path: &Path) -> Option<&'a [u8]> {
+        self.dir.get_file(path).map(|f| f.contents())
+    }
+
     ///Gets directories within dynamic
     fn get_dynamic_dir<T: Display>(&self, dirname: T) -> Result<RelativeDir<'a>> {
         let template_dir = self
@@ -397,6 +597,60 @@ mod test {
         template_dirs
             .get_codegen_dynamic_dir()
             .expect("codegen dynamic");
+
+        template_dirs
+            .get_graphql_client_dynamic_dir()
+            .expect("graphql client dynamic");
+
+        template_dirs
+            .get_validators_dynamic_dir()
+            .expect("validators dynamic");
+    }
+
+    #[test]
+    fn overrides_shadow_codegen_templates() {
+        let project_root = TempDir::new("template_overrides").unwrap();
+        let overrides = TemplateOverrides::new(project_root.path());
+        assert_eq!(overrides.get_paths().unwrap(), Vec::<PathBuf>::new());
+
+        let write_override = |path: &str, contents: &str| {
+            let path = overrides.get_dir().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write_override("static/codegen/src/Env.res", "let overridden = true");
+        write_override("static/codegen/src/Typo.res", "");
+        write_override("partials/logging.hbs", "Logging.info(\"{{name}}\")");
+        write_override("static/codegen/src/db/Migrations.res", "");
+        write_override("dynamic/codegen/src/Handlers.res.hbs", "");
+
+        assert_eq!(
+            overrides.get_db_paths().unwrap(),
+            vec![PathBuf::from("static/codegen/src/db/Migrations.res")]
+        );
+
+        assert_eq!(
+            overrides.get_unmatched_paths().unwrap(),
+            vec![PathBuf::from("static/codegen/src/Typo.res")]
+        );
+        assert_eq!(
+            overrides.get_partials().unwrap(),
+            vec![(
+                "logging".to_string(),
+                "Logging.info(\"{{name}}\")".to_string()
+            )]
+        );
+
+        let static_files = TemplateDirs::new()
+            .get_codegen_static_dir()
+            .unwrap()
+            .get_files_with_overrides(&overrides)
+            .unwrap();
+        let env_contents = static_files
+            .iter()
+            .find(|(path, _)| path == &PathBuf::from("src/Env.res"))
+            .map(|(_, contents)| contents.as_slice());
+        assert_eq!(env_contents, Some("let overridden = true".as_bytes()));
     }
 
     #[test]
@@ -0,0 +1,158 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+///Last modified time of each watched file, None when the file doesn't exist
+type ModifiedTimes = BTreeMap<PathBuf, Option<SystemTime>>;
+
+fn read_modified_times(paths: &[PathBuf]) -> ModifiedTimes {
+    paths
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+///Gets the paths with a different modified time, including created and deleted files
+fn get_changed_paths(old: &ModifiedTimes, new: &ModifiedTimes) -> Vec<PathBuf> {
+    new.iter()
+        .filter(|(path, modified)| old.get(*path) != Some(*modified))
+        .map(|(path, _)| path.clone())
+        .collect()
+}
+
+///Polls the modified times of a set of files. Polling avoids depending on platform
+///specific file system events, and the watched files are few.
+pub struct FileWatcher {
+    paths: Vec<PathBuf>,
+    modified_times: ModifiedTimes,
+    poll_interval: Duration,
+    ///How long the files need to stay unchanged before changes are reported, so that
+    ///an editor saving several files at once only causes a single rerun
+    debounce: Duration,
+}
+
+impl FileWatcher {
+    pub fn new(paths: Vec<PathBuf>, poll_interval: Duration, debounce: Duration) -> Self {
+        let modified_times = read_modified_times(&paths);
+        Self {
+            paths,
+            modified_times,
+            poll_interval,
+            debounce,
+        }
+    }
+
+    ///Replaces the watched paths. The modified times already recorded are kept, so that
+    ///changes made since the last call to wait_for_changes are still reported.
+    pub fn set_paths(&mut self, paths: Vec<PathBuf>) {
+        let mut modified_times = read_modified_times(&paths);
+        for (path, modified) in modified_times.iter_mut() {
+            if let Some(recorded) = self.modified_times.get(path) {
+                *modified = *recorded;
+            }
+        }
+        self.paths = paths;
+        self.modified_times = modified_times;
+    }
+
+    ///Waits until any of the watched files changed and then stopped changing for the
+    ///debounce duration. Returns the paths that changed since the last call.
+    pub async fn wait_for_changes(&mut self) -> Vec<PathBuf> {
+        let mut latest_times = loop {
+            tokio::time::sleep(self.poll_interval).await;
+            let latest_times = read_modified_times(&self.paths);
+            if latest_times != self.modified_times {
+                break latest_times;
+            }
+        };
+
+        loop {
+            tokio::time::sleep(self.debounce).await;
+            let debounced_times = read_modified_times(&self.paths);
+            if debounced_times == latest_times {
+                break;
+            }
+            latest_times = debounced_times;
+        }
+
+        let changed_paths = get_changed_paths(&self.modified_times, &latest_times);
+        self.modified_times = latest_times;
+        changed_paths
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FileWatcher;
+    use std::{fs, time::Duration};
+    use tempdir::TempDir;
+
+    #[tokio::test]
+    async fn reports_changed_files() {
+        let dir = TempDir::new("file_watcher").unwrap();
+        let config_path = dir.path().join("config.yaml");
+        let schema_path = dir.path().join("schema.graphql");
+        let handler_path = dir.path().join("EventHandlers.ts");
+        fs::write(&config_path, "name: indexer").unwrap();
+        fs::write(&schema_path, "type User { id: ID! }").unwrap();
+
+        let mut watcher = FileWatcher::new(
+            vec![
+                config_path.clone(),
+                schema_path.clone(),
+                handler_path.clone(),
+            ],
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        );
+
+        //Make sure the modified time differs on file systems with coarse timestamps
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        fs::write(&config_path, "name: renamed_indexer").unwrap();
+        fs::write(&handler_path, "").unwrap();
+
+        let changed_paths =
+            tokio::time::timeout(Duration::from_secs(5), watcher.wait_for_changes())
+                .await
+                .expect("Should have detected the changes");
+
+        //Paths are sorted, so the handler comes before the config
+        assert_eq!(changed_paths, vec![handler_path, config_path]);
+    }
+
+    #[tokio::test]
+    async fn keeps_changes_made_before_setting_paths() {
+        let dir = TempDir::new("file_watcher").unwrap();
+        let config_path = dir.path().join("config.yaml");
+        let abi_path = dir.path().join("abi.json");
+        fs::write(&config_path, "name: indexer").unwrap();
+
+        let mut watcher = FileWatcher::new(
+            vec![config_path.clone()],
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        );
+
+        //A change saved while the project reruns, before the watched paths are updated
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        fs::write(&config_path, "name: renamed_indexer").unwrap();
+        fs::write(&abi_path, "[]").unwrap();
+        watcher.set_paths(vec![config_path.clone(), abi_path]);
+
+        let changed_paths =
+            tokio::time::timeout(Duration::from_secs(5), watcher.wait_for_changes())
+                .await
+                .expect("Should have detected the change");
+
+        //The newly watched abi is only compared from the time it was added
+        assert_eq!(changed_paths, vec![config_path]);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod file_system;
+pub mod file_watcher;
 pub mod normalized_list;
 pub mod text;
 pub mod unique_hashmap;
@@ -93,6 +93,7 @@ type t = {
   chainMap: ChainMap.t<chainConfig>,
   defaultChain: option<chainConfig>,
   enableRawEvents: bool,
+  enableHasura: bool,
   entities: array<module(Entities.InternalEntity)>,
 }
 
@@ -102,6 +103,7 @@ let make = (
   ~isUnorderedMultichainMode=false,
   ~chains=[],
   ~enableRawEvents=false,
+  ~enableHasura=true,
   ~entities=[],
 ) => {
   {
@@ -121,6 +123,7 @@ let make = (
     ->ChainMap.fromArrayUnsafe,
     defaultChain: chains->Array.get(0),
     enableRawEvents,
+    enableHasura,
     entities: entities->(
       Utils.magic: array<module(Entities.Entity)> => array<module(Entities.InternalEntity)>
     ),
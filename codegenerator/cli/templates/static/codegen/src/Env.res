@@ -108,7 +108,8 @@ let logStrategy =
 
 module Db = {
   let host = envSafe->EnvSafe.get("ENVIO_PG_HOST", S.string, ~devFallback="localhost")
-  let port = envSafe->EnvSafe.get("ENVIO_PG_PORT", S.int->S.port, ~devFallback=5433)
+  let port =
+    envSafe->EnvSafe.get("ENVIO_PG_PORT", S.int->S.port, ~devFallback=LocalServices.postgresPort)
   let user = envSafe->EnvSafe.get("ENVIO_PG_USER", S.string, ~devFallback="postgres")
   let password = envSafe->EnvSafe.get("ENVIO_POSTGRES_PASSWORD", S.string, ~devFallback="testing")
   let database = envSafe->EnvSafe.get("ENVIO_PG_DATABASE", S.string, ~devFallback="envio-dev")
@@ -124,11 +125,15 @@ module Db = {
 module Hasura = {
   let responseLimit = envSafe->EnvSafe.get("HASURA_RESPONSE_LIMIT", S.option(S.int))
 
+  //The host port docker compose publishes Hasura on
+  let externalPort =
+    envSafe->EnvSafe.get("HASURA_EXTERNAL_PORT", S.int->S.port, ~fallback=LocalServices.hasuraPort)
+
   let graphqlEndpoint =
     envSafe->EnvSafe.get(
       "HASURA_GRAPHQL_ENDPOINT",
       S.string,
-      ~devFallback="http://localhost:8080/v1/metadata",
+      ~devFallback=`http://localhost:${externalPort->Belt.Int.toString}/v1/metadata`,
     )
 
   let role = envSafe->EnvSafe.get("HASURA_GRAPHQL_ROLE", S.string, ~devFallback="admin")
@@ -161,21 +161,90 @@ let executeBatch = async (sql, ~inMemoryStore: InMemoryStore.t, ~isInReorgThresh
     ~items=inMemoryStore.rawEvents->InMemoryTable.values,
   )
 
-  let setEntities = Entities.allEntities->Belt.Array.map(entityMod => {
-    entityDbExecutionComposer(_, ~entityMod, ~inMemoryStore)
-  })
+  //Every processed event is stored in raw_events, so the coverage of each chain
+  //extends up to its latest processed block. A new coverage row starts at the
+  //first block stored, the existing ones keep their start block.
+  let setRawEventsCoverage = executeSet(
+    _,
+    ~dbFunction=DbFunctions.RawEventsCoverage.batchSet,
+    ~items=config.enableRawEvents
+      ? {
+          let firstStoredBlocks = Js.Dict.empty()
+          inMemoryStore.rawEvents
+          ->InMemoryTable.values
+          ->Belt.Array.forEach(({chainId, blockNumber}) => {
+            let key = chainId->Belt.Int.toString
+            switch firstStoredBlocks->Js.Dict.get(key) {
+            | Some(firstStoredBlock) if firstStoredBlock <= blockNumber => ()
+            | _ => firstStoredBlocks->Js.Dict.set(key, blockNumber)
+            }
+          })
+          inMemoryStore.eventSyncState
+          ->InMemoryTable.values
+          ->Belt.Array.keepMap(({chainId, blockNumber, isPreRegisteringDynamicContracts}) =>
+            switch isPreRegisteringDynamicContracts {
+            | true => None
+            | false =>
+              let startBlock =
+                firstStoredBlocks
+                ->Js.Dict.get(chainId->Belt.Int.toString)
+                ->Belt.Option.getWithDefault(blockNumber)
+              Some(({chainId, startBlock, endBlock: blockNumber}: TablesStatic.RawEventsCoverage.t))
+            }
+          )
+        }
+      : [],
+  )
+
+  //Aggregation entities are rolled up from their source entities,
+  //so they are written once the source entities are
+  let (aggregationEntities, sourceEntities) =
+    Entities.allEntities->Belt.Array.partition(Aggregations.isAggregationEntity)
+
+  let setEntitiesAndRollUpAggregations = async sql => {
+    //The buckets the source entities are in before the batch is written,
+    //so that the buckets they are moved out of or deleted from are recomputed too
+    let previousBuckets = await sql->Aggregations.getBuckets(~inMemoryStore)
+    let _ =
+      await sourceEntities
+      ->Belt.Array.map(entityMod => sql->entityDbExecutionComposer(~entityMod, ~inMemoryStore))
+      ->Promise.all
+    await sql->Aggregations.rollUp(~inMemoryStore, ~previousBuckets)
+    let _ =
+      await aggregationEntities
+      ->Belt.Array.map(entityMod => sql->entityDbExecutionComposer(~entityMod, ~inMemoryStore))
+      ->Promise.all
+    ()
+  }
 
   //In the event of a rollback, rollback all meta tables based on the given
   //valid event identifier, where all rows created after this eventIdentifier should
   //be deleted
   let rollbackTables = switch inMemoryStore.rollBackEventIdentifier {
-  | Some(eventIdentifier) => [
-      DbFunctions.EntityHistory.deleteAllEntityHistoryAfterEventIdentifier(
-        _,
-        ~isUnorderedMultichainMode=config.isUnorderedMultichainMode,
-        ~eventIdentifier,
-      ),
-    ]
+  | Some(eventIdentifier) =>
+    Belt.Array.concat(
+      [
+        DbFunctions.EntityHistory.deleteAllEntityHistoryAfterEventIdentifier(
+          _,
+          ~isUnorderedMultichainMode=config.isUnorderedMultichainMode,
+          ~eventIdentifier,
+        ),
+      ],
+      config.enableRawEvents
+        ? [
+            DbFunctions.RawEvents.deleteRolledBack(
+              _,
+              ~chainId=eventIdentifier.chainId,
+              ~safeBlockNumber=eventIdentifier.blockNumber,
+            ),
+            DbFunctions.RawEventsCoverage.rollBack(
+              _,
+              ~chainId=eventIdentifier.chainId,
+              ~safeBlockNumber=eventIdentifier.blockNumber,
+            ),
+          ]
+        : [],
+    )
   | None => []
   }
 
@@ -183,8 +252,12 @@ let executeBatch = async (sql, ~inMemoryStore: InMemoryStore.t, ~isInReorgThresh
     Belt.Array.concatMany([
       //Rollback tables need to happen first in the traction
       rollbackTables,
-      [setEventSyncState, setRawEvents],
-      setEntities,
+      [
+        setEventSyncState,
+        setRawEvents,
+        setRawEventsCoverage,
+        setEntitiesAndRollUpAggregations,
+      ],
     ])->Belt.Array.map(dbFunc => sql->dbFunc)
   })
 
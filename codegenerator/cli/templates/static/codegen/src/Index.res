@@ -158,8 +158,13 @@ let main = async () => {
     let config = RegisterHandlers.registerAllHandlers()
     let mainArgs: mainArgs = process->argv->Yargs.hideBin->Yargs.yargs->Yargs.argv
     let shouldUseTui = !(mainArgs.tuiOff->Belt.Option.getWithDefault(Env.tuiOffEnvVar))
-    let chainManager = await ChainManager.makeFromDbState(~config)
     let loadLayer = LoadLayer.makeWithDbConnection()
+    if mainArgs.syncFromRawEvents->Belt.Option.getWithDefault(false) {
+      //Replay before reading the chain state from the db, so that
+      //fetching continues after the last replayed event
+      await RawEventsReplay.replay(~config, ~loadLayer)
+    }
+    let chainManager = await ChainManager.makeFromDbState(~config)
     let globalState = GlobalState.make(~config, ~chainManager, ~loadLayer)
     let stateUpdatedHook = if shouldUseTui {
       let rerender = EnvioInkApp.startApp(makeAppState(globalState))
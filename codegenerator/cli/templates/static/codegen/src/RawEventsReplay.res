@@ -0,0 +1,111 @@
+/*
+Reprocesses the events stored in raw_events with the current handlers instead of
+fetching them from the chains again. The cli only replays when every selected block
+field is stored with the raw events and the raw_events_coverage table covers the full
+block range that was processed on every chain.
+*/
+let pageSize = 5_000
+
+exception UnknownEvent({contractName: string, eventName: string})
+
+let makeEventModLookup = (~config: Config.t) => {
+  let lookup = Js.Dict.empty()
+  config.chainMap
+  ->ChainMap.values
+  ->Belt.Array.forEach(chainConfig =>
+    chainConfig.contracts->Belt.Array.forEach(contract =>
+      contract.events->Belt.Array.forEach(eventMod => {
+        let module(Event) = eventMod
+        lookup->Js.Dict.set(
+          Event.contractName ++ "_" ++ Event.name,
+          eventMod->Types.eventModWithoutArgTypeToInternal,
+        )
+      })
+    )
+  )
+  lookup
+}
+
+let makeEventBatchQueueItem = (
+  rawEvent: DbFunctions.RawEvents.rawEventRow,
+  ~eventModLookup,
+): Types.eventBatchQueueItem => {
+  let {contractName, eventName, chainId, blockNumber, logIndex, blockTimestamp} = rawEvent
+  let module(Event) = switch eventModLookup->Js.Dict.get(contractName ++ "_" ++ eventName) {
+  | Some(eventMod) => eventMod
+  | None => raise(UnknownEvent({contractName, eventName}))
+  }
+
+  //Only the raw event block fields are stored, which the cli checks to be
+  //all the selected block fields before replaying
+  let block =
+    rawEvent.blockFields
+    ->S.parseOrRaiseWith(Types.Block.rawEventSchema)
+    ->(Utils.magic: Types.Block.rawEventFields => Types.Block.t)
+
+  {
+    eventName,
+    contractName,
+    handlerRegister: Event.handlerRegister,
+    timestamp: blockTimestamp,
+    chain: ChainMap.Chain.makeUnsafe(~chainId),
+    blockNumber,
+    logIndex,
+    event: {
+      params: rawEvent.params->S.parseAnyOrRaiseWith(Event.paramsRawEventSchema),
+      chainId,
+      srcAddress: rawEvent.srcAddress,
+      logIndex,
+      transaction: rawEvent.transactionFields->S.parseOrRaiseWith(Types.Transaction.schema),
+      block,
+    },
+    paramsRawEventSchema: Event.paramsRawEventSchema,
+  }
+}
+
+let replay = async (~config: Config.t, ~loadLayer) => {
+  let logger = Logging.createChild(~params={"context": "Replaying raw events"})
+  let eventModLookup = makeEventModLookup(~config)
+  //Raw events are already stored, so they shouldn't be written again
+  let config = {...config, enableRawEvents: false}
+  //Contracts registered while the events were first processed are still
+  //in the db, so registering them again is skipped
+  let checkContractIsRegistered = (~chain as _, ~contractAddress as _, ~contractName as _) => true
+
+  let latestProcessedBlocks = ref(EventProcessing.EventsProcessed.makeEmpty(~config))
+  let lastSerial = ref(0)
+  let numReplayed = ref(0)
+  let isDone = ref(false)
+
+  while !isDone.contents {
+    let rawEvents =
+      await Db.sql->DbFunctions.RawEvents.readAfterSerial(
+        ~serial=lastSerial.contents,
+        ~limit=pageSize,
+      )
+
+    switch rawEvents->Belt.Array.get(rawEvents->Array.length - 1) {
+    | None => isDone := true
+    | Some({serial}) =>
+      let eventBatch =
+        rawEvents->Belt.Array.map(rawEvent => rawEvent->makeEventBatchQueueItem(~eventModLookup))
+
+      switch await EventProcessing.processEventBatch(
+        ~eventBatch,
+        ~inMemoryStore=InMemoryStore.make(),
+        ~isInReorgThreshold=false,
+        ~latestProcessedBlocks=latestProcessedBlocks.contents,
+        ~checkContractIsRegistered,
+        ~loadLayer,
+        ~config,
+      ) {
+      | Ok(res) => latestProcessedBlocks := res.latestProcessedBlocks
+      | Error(errHandler) => errHandler->ErrorHandling.logAndRaise
+      }
+
+      lastSerial := serial
+      numReplayed := numReplayed.contents + rawEvents->Array.length
+      logger->Logging.childInfo(`Replayed ${numReplayed.contents->Belt.Int.toString} raw events`)
+    }
+  }
+}
@@ -36,6 +36,7 @@ let allStaticTables: array<Table.table> = [
   TablesStatic.PersistedState.table,
   TablesStatic.EndOfBlockRangeScannedData.table,
   TablesStatic.RawEvents.table,
+  TablesStatic.RawEventsCoverage.table,
   TablesStatic.DynamicContractRegistry.table,
 ]
 
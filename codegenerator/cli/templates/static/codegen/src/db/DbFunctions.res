@@ -112,6 +112,35 @@ module RawEvents = {
   @module("./DbFunctionsImplementation.js")
   external batchSet: (Postgres.sql, array<TablesStatic.RawEvents.t>) => promise<unit> =
     "batchSetRawEvents"
+
+  type rawEventRow = {
+    ...TablesStatic.RawEvents.t,
+    serial: int,
+  }
+
+  @module("./DbFunctionsImplementation.js")
+  external readAfterSerial: (
+    Postgres.sql,
+    ~serial: int,
+    ~limit: int,
+  ) => promise<array<rawEventRow>> = "readRawEventsAfterSerial"
+
+  @module("./DbFunctionsImplementation.js")
+  external deleteRolledBack: (
+    Postgres.sql,
+    ~chainId: int,
+    ~safeBlockNumber: int,
+  ) => promise<unit> = "deleteRolledBackRawEvents"
+}
+
+module RawEventsCoverage = {
+  @module("./DbFunctionsImplementation.js")
+  external batchSet: (Postgres.sql, array<TablesStatic.RawEventsCoverage.t>) => promise<unit> =
+    "batchSetRawEventsCoverage"
+
+  @module("./DbFunctionsImplementation.js")
+  external rollBack: (Postgres.sql, ~chainId: int, ~safeBlockNumber: int) => promise<unit> =
+    "rollBackRawEventsCoverage"
 }
 
 module DynamicContractRegistry = {
@@ -172,7 +201,7 @@ module EntityHistory = {
       Postgres.sql,
       ~reorgChainId: int,
       ~safeBlockNumber: int,
-      ~entityName: Enums.EntityType.t,
+      ~tableName: string,
     ) => dynamicSqlQuery = "getFirstChangeSerial_UnorderedMultichain"
   }
 
@@ -183,21 +212,21 @@ module EntityHistory = {
       ~safeBlockTimestamp: int,
       ~reorgChainId: int,
       ~safeBlockNumber: int,
-      ~entityName: Enums.EntityType.t,
+      ~tableName: string,
     ) => dynamicSqlQuery = "getFirstChangeSerial_OrderedMultichain"
   }
 
   @module("./DbFunctionsImplementation.js")
   external getFirstChangeEntityHistoryPerChain: (
     Postgres.sql,
-    ~entityName: Enums.EntityType.t,
+    ~tableName: string,
     ~getFirstChangeSerial: Postgres.sql => dynamicSqlQuery,
   ) => promise<Js.Json.t> = "getFirstChangeEntityHistoryPerChain"
 
   @module("./DbFunctionsImplementation.js")
   external getRollbackDiffInternal: (
     Postgres.sql,
-    ~entityName: Enums.EntityType.t,
+    ~tableName: string,
     ~getFirstChangeSerial: Postgres.sql => dynamicSqlQuery,
   ) => //Returns an array of entity history rows
   promise<Js.Json.t> = "getRollbackDiff"
@@ -205,7 +234,7 @@ module EntityHistory = {
   @module("./DbFunctionsImplementation.js")
   external deleteRolledBackEntityHistory: (
     Postgres.sql,
-    ~entityName: Enums.EntityType.t,
+    ~tableName: string,
     ~getFirstChangeSerial: Postgres.sql => dynamicSqlQuery,
   ) => promise<unit> = "deleteRolledBackEntityHistory"
 
@@ -217,7 +246,7 @@ module EntityHistory = {
   @module("./DbFunctionsImplementation.js")
   external pruneStaleEntityHistoryInternal: (
     Postgres.sql,
-    ~entityName: Enums.EntityType.t,
+    ~tableName: string,
     ~safeChainIdAndBlockNumberArray: array<chainIdAndBlockNumber>,
     // shouldDeepClean is a boolean that determines whether to delete stale history
     // items of entities that are in the reorg threshold (expensive to calculate)
@@ -229,12 +258,12 @@ module EntityHistory = {
 
   let pruneStaleEntityHistory = async (
     sql,
-    ~entityName,
+    ~tableName,
     ~safeChainIdAndBlockNumberArray,
     ~shouldDeepClean,
   ) => {
     try await sql->pruneStaleEntityHistoryInternal(
-      ~entityName,
+      ~tableName,
       ~safeChainIdAndBlockNumberArray,
       ~shouldDeepClean,
     ) catch {
@@ -243,7 +272,7 @@ module EntityHistory = {
         ~msg=`Failed to prune stale entity history`,
         ~logger=Logging.createChild(
           ~params={
-            "entityName": entityName,
+            "tableName": tableName,
             "safeChainIdAndBlockNumberArray": safeChainIdAndBlockNumberArray,
           },
         ),
@@ -268,7 +297,7 @@ module EntityHistory = {
     Ordered multichain mode needs to ensure that all chains rollback to any event that occurred after the reorg chain
     block number. Regardless of whether the reorg chain incurred any changes or not to entities.
     */
-    let makeGetFirstChangeSerial = (self: t, ~entityName) =>
+    let makeGetFirstChangeSerial = (self: t, ~tableName) =>
       switch self {
       | OrderedMultichain({safeBlockTimestamp, reorgChainId, safeBlockNumber}) =>
         sql =>
@@ -277,7 +306,7 @@ module EntityHistory = {
             ~safeBlockTimestamp,
             ~reorgChainId,
             ~safeBlockNumber,
-            ~entityName,
+            ~tableName,
           )
       | UnorderedMultichain({reorgChainId, safeBlockNumber}) =>
         sql =>
@@ -285,7 +314,7 @@ module EntityHistory = {
             sql,
             ~reorgChainId,
             ~safeBlockNumber,
-            ~entityName,
+            ~tableName,
           )
       }
 
@@ -337,8 +366,10 @@ module EntityHistory = {
         let module(Entity) = entityMod
         try await deleteRolledBackEntityHistory(
           sql,
-          ~entityName=Entity.name,
-          ~getFirstChangeSerial=args->Args.makeGetFirstChangeSerial(~entityName=Entity.name),
+          ~tableName=Entity.table.tableName,
+          ~getFirstChangeSerial=args->Args.makeGetFirstChangeSerial(
+            ~tableName=Entity.table.tableName,
+          ),
         ) catch {
         | exn =>
           exn->ErrorHandling.mkLogAndRaise(
@@ -371,8 +402,10 @@ module EntityHistory = {
 
     let diffRes = switch await getRollbackDiffInternal(
       sql,
-      ~getFirstChangeSerial=args->Args.makeGetFirstChangeSerial(~entityName=Entity.name),
-      ~entityName=Entity.name,
+      ~getFirstChangeSerial=args->Args.makeGetFirstChangeSerial(
+        ~tableName=Entity.table.tableName,
+      ),
+      ~tableName=Entity.table.tableName,
     ) {
     | exception exn =>
       exn->ErrorHandling.mkLogAndRaise(
@@ -436,8 +469,10 @@ module EntityHistory = {
         let module(Entity) = entityMod
         let res = try await getFirstChangeEntityHistoryPerChain(
           sql,
-          ~entityName=Entity.name,
-          ~getFirstChangeSerial=args->Args.makeGetFirstChangeSerial(~entityName=Entity.name),
+          ~tableName=Entity.table.tableName,
+          ~getFirstChangeSerial=args->Args.makeGetFirstChangeSerial(
+            ~tableName=Entity.table.tableName,
+          ),
         ) catch {
         | exn =>
           exn->ErrorHandling.mkLogAndRaise(
@@ -129,6 +129,9 @@ let makeWhereEq = (
   | value => value
   }
 
+  //fieldName is the key on the entity record which can differ from the db column
+  let fieldName = Entity.table->Table.getDbFieldNameByRecordKey(fieldName)
+
   switch await whereEqQuery(~table=Entity.table, ~sql, ~fieldName, ~value) {
   | exception exn => exn->ErrorHandling.mkLogAndRaise(~logger, ~msg=`Failed to execute query`)
   | res =>
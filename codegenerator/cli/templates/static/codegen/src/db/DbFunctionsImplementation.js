@@ -180,8 +180,50 @@ module.exports.batchDeleteRawEvents = (sql, entityIdArray) => sql`
   DELETE
   FROM "public"."raw_events"
   WHERE (chain_id, event_id) IN ${sql(entityIdArray)};`;
+
+module.exports.readRawEventsAfterSerial = (sql, serial, limit) => sql`
+  SELECT *
+  FROM "public"."raw_events"
+  WHERE serial > ${serial}
+  ORDER BY serial
+  LIMIT ${limit};`;
+
+module.exports.deleteRolledBackRawEvents = (sql, chainId, safeBlockNumber) => sql`
+  WITH deleted AS (
+    DELETE
+    FROM "public"."raw_events"
+    WHERE serial >= (
+      -- Everything processed after the first rolled back event gets reprocessed
+      SELECT MIN(serial)
+      FROM "public"."raw_events"
+      WHERE chain_id = ${chainId} AND block_number > ${safeBlockNumber}
+    )
+    RETURNING chain_id, block_number
+  ), first_deleted AS (
+    SELECT chain_id, MIN(block_number) AS block_number
+    FROM deleted
+    GROUP BY chain_id
+  )
+  -- The deleted events can be of any chain, so the coverage of each of them
+  -- ends before its first deleted block
+  UPDATE "public"."raw_events_coverage" AS coverage
+  SET "end_block" = LEAST(coverage."end_block", first_deleted.block_number - 1)
+  FROM first_deleted
+  WHERE coverage.chain_id = first_deleted.chain_id;`;
 // end db operations for raw_events
 
+module.exports.batchSetRawEventsCoverage = (sql, rowDataArray) => sql`
+    INSERT INTO "public"."raw_events_coverage"
+  ${sql(rowDataArray, "chain_id", "start_block", "end_block")}
+    ON CONFLICT(chain_id) DO UPDATE
+    SET
+    "end_block" = GREATEST("raw_events_coverage"."end_block", EXCLUDED."end_block");`;
+
+module.exports.rollBackRawEventsCoverage = (sql, chainId, safeBlockNumber) => sql`
+  UPDATE "public"."raw_events_coverage"
+  SET "end_block" = LEAST("end_block", ${safeBlockNumber})
+  WHERE chain_id = ${chainId};`;
+
 const batchSetEndOfBlockRangeScannedDataCore = (sql, rowDataArray) => {
   return sql`
     INSERT INTO "public"."end_of_block_range_scanned_data"
@@ -256,7 +298,7 @@ module.exports.readDynamicContractsOnChainIdMatchingEvents = (
   `;
 };
 
-const makeHistoryTableName = (entityName) => entityName + "_history";
+const makeHistoryTableName = (tableName) => tableName + "_history";
 
 /**
   Find the "first change" serial originating from the reorg chain above the safe block number 
@@ -266,13 +308,13 @@ module.exports.getFirstChangeSerial_UnorderedMultichain = (
   sql,
   reorgChainId,
   safeBlockNumber,
-  entityName
+  tableName
 ) =>
   sql`
     SELECT
       MIN(serial) AS first_change_serial
     FROM
-      public.${sql(makeHistoryTableName(entityName))}
+      public.${sql(makeHistoryTableName(tableName))}
     WHERE
       entity_history_chain_id = ${reorgChainId}
       AND entity_history_block_number > ${safeBlockNumber}
@@ -286,13 +328,13 @@ module.exports.getFirstChangeSerial_OrderedMultichain = (
   safeBlockTimestamp,
   reorgChainId,
   safeBlockNumber,
-  entityName
+  tableName
 ) =>
   sql`
     SELECT
       MIN(serial) AS first_change_serial
     FROM
-      public.${sql(makeHistoryTableName(entityName))}
+      public.${sql(makeHistoryTableName(tableName))}
     WHERE
       entity_history_block_timestamp > ${safeBlockTimestamp}
       OR
@@ -303,7 +345,7 @@ module.exports.getFirstChangeSerial_OrderedMultichain = (
 
 module.exports.getFirstChangeEntityHistoryPerChain = (
   sql,
-  entityName,
+  tableName,
   getFirstChangeSerial
 ) => sql`
   WITH
@@ -317,7 +359,7 @@ module.exports.getFirstChangeEntityHistoryPerChain = (
   SELECT DISTINCT
     ON (entity_history_chain_id) *
   FROM
-    public.${sql(makeHistoryTableName(entityName))}
+    public.${sql(makeHistoryTableName(tableName))}
   WHERE
     serial >= (
       SELECT
@@ -333,7 +375,7 @@ module.exports.getFirstChangeEntityHistoryPerChain = (
 
 module.exports.deleteRolledBackEntityHistory = (
   sql,
-  entityName,
+  tableName,
   getFirstChangeSerial
 ) => sql`
   WITH
@@ -344,7 +386,7 @@ module.exports.deleteRolledBackEntityHistory = (
     )
   -- Step 2: Delete all rows that have a serial >= the first change serial
   DELETE FROM
-    public.${sql(makeHistoryTableName(entityName))}
+    public.${sql(makeHistoryTableName(tableName))}
   WHERE
     serial >= (
       SELECT
@@ -358,20 +400,20 @@ const Utils = require("envio/src/Utils.bs.js");
 
 module.exports.pruneStaleEntityHistory = (
   sql,
-  entityName,
+  tableName,
   safeChainIdAndBlockNumberArray,
   // shouldDeepCleanHistory is a boolean that determines whether to delete stale history
   // items of entities that are in the reorg threshold (expensive to calculate)
   // or to do a shallow clean (only deletes history items of entities that are not in the reorg threshold)
   shouldDeepClean
 ) => {
-  const tableName = makeHistoryTableName(entityName);
+  const historyTableName = makeHistoryTableName(tableName);
   return sql`
   WITH first_change AS (
     SELECT
       MIN(serial) AS first_change_serial
     FROM
-      public.${sql(tableName)}
+      public.${sql(historyTableName)}
     WHERE
       ${Utils.$$Array.interleave(
         safeChainIdAndBlockNumberArray.map(
@@ -385,7 +427,7 @@ module.exports.pruneStaleEntityHistory = (
     SELECT DISTINCT
       ON (id) *
     FROM
-      public.${sql(tableName)}
+      public.${sql(historyTableName)}
     WHERE
       serial >= (SELECT first_change_serial FROM first_change)
     ORDER BY
@@ -400,7 +442,7 @@ module.exports.pruneStaleEntityHistory = (
       prev.id,
       prev.serial
     FROM
-      public.${sql(tableName)} prev
+      public.${sql(historyTableName)} prev
     INNER JOIN
       items_in_reorg_threshold r
     ON
@@ -415,7 +457,7 @@ module.exports.pruneStaleEntityHistory = (
       : sql``
   }
   DELETE FROM
-    public.${sql(tableName)} eh
+    public.${sql(historyTableName)} eh
   WHERE
     -- Delete all entity history of entities that are not in the reorg threshold
     eh.id NOT IN (SELECT id FROM items_in_reorg_threshold)
@@ -430,7 +472,7 @@ module.exports.pruneStaleEntityHistory = (
 `;
 };
 
-module.exports.getRollbackDiff = (sql, entityName, getFirstChangeSerial) => sql`
+module.exports.getRollbackDiff = (sql, tableName, getFirstChangeSerial) => sql`
   WITH
     first_change AS (
       -- Step 1: Find the "first change" serial originating from the reorg chain above the safe block number 
@@ -442,7 +484,7 @@ module.exports.getRollbackDiff = (sql, entityName, getFirstChangeSerial) => sql`
       SELECT DISTINCT
         ON (id) after.*
       FROM
-        public.${sql(makeHistoryTableName(entityName))} after
+        public.${sql(makeHistoryTableName(tableName))} after
       WHERE
         after.serial >= (
           SELECT
@@ -469,7 +511,7 @@ module.exports.getRollbackDiff = (sql, entityName, getFirstChangeSerial) => sql`
     COALESCE(before.entity_history_log_index, 0) AS entity_history_log_index
   FROM
     -- Use a RIGHT JOIN, to ensure that nulls get returned if there is no "before" row
-    public.${sql(makeHistoryTableName(entityName))} before
+    public.${sql(makeHistoryTableName(tableName))} before
     RIGHT JOIN rollback_ids after ON before.id = after.id
     AND before.entity_history_block_timestamp = after.previous_entity_history_block_timestamp
     AND before.entity_history_chain_id = after.previous_entity_history_chain_id
@@ -1,22 +1,22 @@
 let sql = Db.sql
 let unsafe = Postgres.unsafe
 
+let makeColumnDefinition = (field: Table.field) => {
+  let {fieldType, isNullable, isArray, defaultValue} = field
+  let fieldName = field->Table.getDbFieldName
+
+  `"${fieldName}" ${(fieldType :> string)}${isArray ? "[]" : ""}${switch defaultValue {
+    | Some(defaultValue) => ` DEFAULT ${defaultValue}`
+    | None => isNullable ? `` : ` NOT NULL`
+    }}`
+}
+
 let creatTableIfNotExists = (sql, table) => {
   open Belt
   let fieldsMapped =
     table
     ->Table.getFields
-    ->Array.map(field => {
-      let {fieldType, isNullable, isArray, defaultValue} = field
-      let fieldName = field->Table.getDbFieldName
-
-      {
-        `"${fieldName}" ${(fieldType :> string)}${isArray ? "[]" : ""}${switch defaultValue {
-          | Some(defaultValue) => ` DEFAULT ${defaultValue}`
-          | None => isNullable ? `` : ` NOT NULL`
-          }}`
-      }
-    })
+    ->Array.map(makeColumnDefinition)
     ->Js.Array2.joinWith(", ")
 
   let primaryKeyFieldNames = table->Table.getPrimaryKeyFieldNames
@@ -33,6 +33,27 @@ let creatTableIfNotExists = (sql, table) => {
   sql->unsafe(query)
 }
 
+/*
+Adds nullable columns that are missing on a table which was created from a previous
+version of the schema. Non nullable columns can't be added to existing rows, so the cli
+resets the db for those instead.
+*/
+let addNullableColumnsIfNotExist = (sql, table: Table.table) => {
+  let query =
+    table
+    ->Table.getFields
+    ->Belt.Array.keepMap(field =>
+      field.isNullable
+        ? Some(
+            `ALTER TABLE "public"."${table.tableName}" ADD COLUMN IF NOT EXISTS ${field->makeColumnDefinition};`,
+          )
+        : None
+    )
+    ->Js.Array2.joinWith("\n")
+
+  sql->unsafe(query)
+}
+
 let makeCreateIndexQuery = (~tableName, ~indexFields) => {
   let indexName = tableName ++ "_" ++ indexFields->Js.Array2.joinWith("_")
   let index = indexFields->Belt.Array.map(idx => `"${idx}"`)->Js.Array2.joinWith(", ")
@@ -57,6 +78,53 @@ let createTableIndices = (sql, table: Table.table) => {
   sql->unsafe(query)
 }
 
+let makeCreateFullTextIndexQuery = (~tableName, ~fullTextIndex: Table.fullTextIndex) => {
+  let {name, language, fields} = fullTextIndex
+  let document =
+    fields->Belt.Array.map(field => `coalesce("${field}", '')`)->Js.Array2.joinWith(` || ' ' || `)
+  `ALTER TABLE "public"."${tableName}" ADD COLUMN IF NOT EXISTS "${name}" tsvector GENERATED ALWAYS AS (to_tsvector('${language}', ${document})) STORED;
+  CREATE INDEX IF NOT EXISTS "${tableName}_${name}" ON "public"."${tableName}" USING GIN ("${name}");
+  CREATE OR REPLACE FUNCTION "public"."${name}"(search text) RETURNS SETOF "public"."${tableName}" AS $$
+    SELECT * FROM "public"."${tableName}" WHERE "${name}" @@ websearch_to_tsquery('${language}', search);
+  $$ LANGUAGE sql STABLE;`
+}
+
+let createTableFullTextIndices = (sql, table: Table.table) => {
+  let tableName = table.tableName
+  let query =
+    table.fullTextIndices
+    ->Belt.Array.map(fullTextIndex => makeCreateFullTextIndexQuery(~tableName, ~fullTextIndex))
+    ->Js.Array2.joinWith("\n")
+
+  sql->unsafe(query)
+}
+
+let escapeSqlString = str => str->Js.String2.replaceByRe(%re("/'/g"), "''")
+
+/*
+Schema descriptions are stored as comments on the table and its columns
+so that Hasura shows them in the public api
+*/
+let makeCommentQueries = (table: Table.table) => {
+  let tableName = table.tableName
+  let tableComment = switch table.description {
+  | Some(description) => [
+      `COMMENT ON TABLE "public"."${tableName}" IS '${description->escapeSqlString}';`,
+    ]
+  | None => []
+  }
+  let columnComments = table->Table.getFields->Belt.Array.keepMap(field =>
+    field.description->Belt.Option.map(description =>
+      `COMMENT ON COLUMN "public"."${tableName}"."${field->Table.getDbFieldName}" IS '${description->escapeSqlString}';`
+    )
+  )
+  Belt.Array.concat(tableComment, columnComments)
+}
+
+let createTableComments = (sql, table: Table.table) => {
+  sql->unsafe(table->makeCommentQueries->Js.Array2.joinWith("\n"))
+}
+
 let createDerivedFromDbIndex = (~derivedFromField: Table.derivedFromField, ~schema: Schema.t) => {
   let indexField = schema->Schema.getDerivedFromFieldName(derivedFromField)->Utils.unwrapResultExn
   let query = makeCreateIndexQuery(
@@ -92,6 +160,27 @@ let deleteAllTables: unit => promise<unit> = async () => {
   )
 }
 
+//Tables that keep their rows when the db is set up for replaying raw events.
+//Dynamic contracts are kept since replaying doesn't register them again.
+let rawEventsTables = [
+  TablesStatic.RawEvents.table,
+  TablesStatic.RawEventsCoverage.table,
+  TablesStatic.DynamicContractRegistry.table,
+]
+
+let deleteAllTablesExceptRawEvents: unit => promise<unit> = async () => {
+  Logging.trace("Dropping all tables except raw events")
+  let rawEventsTableNames = rawEventsTables->Belt.Array.map(table => table.tableName)
+  let query =
+    [Db.allStaticTables, Db.allEntityTables, Db.allEntityHistoryTables]
+    ->Belt.Array.concatMany
+    ->Belt.Array.keep(table => !(rawEventsTableNames->Js.Array2.includes(table.tableName)))
+    ->Belt.Array.map(table => `DROP TABLE IF EXISTS "public"."${table.tableName}" CASCADE;`)
+    ->Js.Array2.joinWith("\n")
+
+  await sql->unsafe(query)
+}
+
 type t
 @module external process: t = "process"
 
@@ -101,7 +190,7 @@ type exitCode = | @as(0) Success | @as(1) Failure
 let awaitEach = Utils.Array.awaitEach
 
 // TODO: all the migration steps should run as a single transaction
-let runUpMigrations = async (~shouldExit) => {
+let runUpMigrations = async (~shouldExit, ~shouldTrackTables=true) => {
   let exitCode = ref(Success)
   let logger = Logging.createChild(~params={"context": "Running DB Migrations"})
 
@@ -128,9 +217,18 @@ let runUpMigrations = async (~shouldExit) => {
     await creatTableIfNotExists(Db.sql, table)->handleFailure(
       ~msg=`EE800: Error creating ${table.tableName} table`,
     )
+    await addNullableColumnsIfNotExist(Db.sql, table)->handleFailure(
+      ~msg=`EE800: Error adding new columns to ${table.tableName} table`,
+    )
     await createTableIndices(Db.sql, table)->handleFailure(
       ~msg=`EE800: Error creating ${table.tableName} indices`,
     )
+    await createTableFullTextIndices(Db.sql, table)->handleFailure(
+      ~msg=`EE800: Error creating ${table.tableName} fulltext indices`,
+    )
+    await createTableComments(Db.sql, table)->handleFailure(
+      ~msg=`EE800: Error creating ${table.tableName} comments`,
+    )
   })
 
   await Db.allEntityHistory->awaitEach(async entityHistory => {
@@ -151,19 +249,33 @@ let runUpMigrations = async (~shouldExit) => {
     })
   })
 
-  await TrackTables.trackAllTables()->Promise.catch(err => {
-    Logging.errorWithExn(err, `EE803: Error tracking tables`)->Promise.resolve
+  //Aggregation tables added to an existing db are filled from the source entity rows
+  //indexed before (must be done after all tables are created)
+  await Aggregations.aggregations->awaitEach(aggregation => {
+    let module(AggregationMod) = aggregation.aggregationMod
+    Db.sql
+    ->Aggregations.backfill(aggregation)
+    ->handleFailure(~msg=`EE800: Error backfilling ${AggregationMod.table.tableName} aggregation`)
   })
 
+  //Without Hasura the tables are only queried directly from Postgres
+  if shouldTrackTables {
+    await TrackTables.trackAllTables()->Promise.catch(err => {
+      Logging.errorWithExn(err, `EE803: Error tracking tables`)->Promise.resolve
+    })
+  }
+
   if shouldExit {
     process->exit(exitCode.contents)
   }
   exitCode.contents
 }
 
-let runDownMigrations = async (~shouldExit) => {
+let runDownMigrations = async (~shouldExit, ~shouldDropRawEvents=true) => {
   let exitCode = ref(Success)
-  await deleteAllTables()->Promise.catch(err => {
+  await (
+    shouldDropRawEvents ? deleteAllTables() : deleteAllTablesExceptRawEvents()
+  )->Promise.catch(err => {
     exitCode := Failure
     err
     ->ErrorHandling.make(~msg="EE804: Error dropping entity tables")
@@ -176,16 +288,16 @@ let runDownMigrations = async (~shouldExit) => {
   exitCode.contents
 }
 
-let setupDb = async () => {
+let setupDb = async (~shouldDropRawEvents, ~shouldTrackTables=true) => {
   Logging.info("Provisioning Database")
   // TODO: we should make a hash of the schema file (that gets stored in the DB) and either drop the tables and create new ones or keep this migration.
   //       for now we always run the down migration.
   // if (process.env.MIGRATE === "force" || hash_of_schema_file !== hash_of_current_schema)
-  let exitCodeDown = await runDownMigrations(~shouldExit=false)
+  let exitCodeDown = await runDownMigrations(~shouldExit=false, ~shouldDropRawEvents)
   // else
   //   await clearDb()
 
-  let exitCodeUp = await runUpMigrations(~shouldExit=false)
+  let exitCodeUp = await runUpMigrations(~shouldExit=false, ~shouldTrackTables)
 
   let exitCode = switch (exitCodeDown, exitCodeUp) {
   | (Success, Success) => Success
@@ -71,6 +71,11 @@ module PersistedState = {
     schema_hash: string,
     handler_files_hash: string,
     abi_files_hash: string,
+    templates_hash: string,
+    db_templates_hash: string,
+    hooks_hash: string,
+    schema_snapshot: string,
+    project_snapshot: string,
   }
 
   let table = mkTable(
@@ -82,6 +87,11 @@ module PersistedState = {
       mkField("schema_hash", Text),
       mkField("handler_files_hash", Text),
       mkField("abi_files_hash", Text),
+      mkField("templates_hash", Text),
+      mkField("db_templates_hash", Text),
+      mkField("hooks_hash", Text),
+      mkField("schema_snapshot", Text),
+      mkField("project_snapshot", Text),
     ],
   )
 }
@@ -144,6 +154,26 @@ module RawEvents = {
   )
 }
 
+//Block range of each chain where every processed event is stored in raw_events,
+//used to check that the raw events can be replayed instead of refetching
+module RawEventsCoverage = {
+  @genType
+  type t = {
+    @as("chain_id") chainId: int,
+    @as("start_block") startBlock: int,
+    @as("end_block") endBlock: int,
+  }
+
+  let table = mkTable(
+    "raw_events_coverage",
+    ~fields=[
+      mkField("chain_id", Integer, ~isPrimaryKey),
+      mkField("start_block", Integer),
+      mkField("end_block", Integer),
+    ],
+  )
+}
+
 module DynamicContractRegistry = {
   let name = Enums.EntityType.DynamicContractRegistry
 
@@ -120,6 +120,52 @@ let trackTable = async (~tableName: string) => {
   }
 }
 
+let trackFunction = async (~functionName: string) => {
+  let body = {
+    "type": "pg_track_function",
+    "args": {
+      "source": "default",
+      "function": {
+        "schema": "public",
+        "name": functionName,
+      },
+    },
+  }
+
+  let response = await fetch(
+    Env.Hasura.graphqlEndpoint,
+    {
+      method: #POST,
+      body: body->Js.Json.stringifyAny->Belt.Option.getExn->Body.string,
+      headers: Headers.fromObject(headers),
+    },
+  )
+
+  let responseJson = await response->Response.json
+  let statusCode = response->Response.status
+
+  switch validateHasuraResponse(~statusCode, ~responseJson) {
+  | Error(_) =>
+    Logging.error({
+      "msg": `EE809: There was an issue tracking the ${functionName} search function in hasura - indexing may still work - but you may not be able to run fulltext searches in hasura.`,
+      "functionName": functionName,
+      "requestStatusCode": statusCode,
+      "requestResponseJson": responseJson,
+    })
+  | Ok(case) =>
+    let msg = switch case {
+    | QuerySucceeded => "Function Tracked"
+    | AlreadyDone => "Function Already Tracked"
+    }
+    Logging.trace({
+      "msg": msg,
+      "functionName": functionName,
+      "requestStatusCode": statusCode,
+      "requestResponseJson": responseJson,
+    })
+  }
+}
+
 let createSelectPermissions = async (~tableName: string) => {
   let body = {
     "type": "pg_create_select_permission",
@@ -177,7 +223,7 @@ let createEntityRelationship = async (
   ~mappedEntity: string,
   ~isDerivedFrom: bool,
 ) => {
-  let derivedFromTo = isDerivedFrom ? `"id": "${relationalKey}"` : `"${relationalKey}_id" : "id"`
+  let derivedFromTo = isDerivedFrom ? `"id": "${relationalKey}"` : `"${relationalKey}" : "id"`
 
   let bodyString = `{"type": "pg_create_${relationshipType}_relationship","args": {"table": "${tableName}","name": "${objectName}","source": "default","using": {"manual_configuration": {"remote_table": "${mappedEntity}","column_mapping": {${derivedFromTo}}}}}}`
 
@@ -255,9 +301,14 @@ let trackAllTables = async () => {
         ~relationshipType="object",
         ~isDerivedFrom=false,
         ~objectName=field.fieldName,
-        ~relationalKey=field.fieldName,
+        ~relationalKey=field->Table.getDbFieldName,
         ~mappedEntity=linkedEntityName,
       )
     })
+
+    //Expose fulltext search functions
+    await table.fullTextIndices->Utils.Array.awaitEach(async ({name}) => {
+      await trackFunction(~functionName=name)
+    })
   })
 }
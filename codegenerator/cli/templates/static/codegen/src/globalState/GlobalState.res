@@ -980,7 +980,7 @@ let injectedTaskReducer = (
               let module(Entity) = entityMod
 
               sql->DbFunctions.EntityHistory.pruneStaleEntityHistory(
-                ~entityName=Entity.name,
+                ~tableName=Entity.table.tableName,
                 ~safeChainIdAndBlockNumberArray,
                 ~shouldDeepClean,
               )
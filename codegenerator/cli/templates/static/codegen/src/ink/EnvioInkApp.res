@@ -33,8 +33,7 @@ module App = {
   @react.component
   let make = (~appState: appState) => {
     let {chains, indexerStartTime, config, isPreRegisteringDynamicContracts} = appState
-    let hasuraPort = "8080"
-    let hasuraLink = `http://localhost:${hasuraPort}`
+    let hasuraLink = Env.Hasura.graphqlEndpoint->Js.String2.replace("/v1/metadata", "")
     let totalEventsProcessed = getTotalNumEventsProcessed(~chains)
     <Box flexDirection={Column}>
       <BigText text="envio" colors=[Secondary, Primary] font={Block} />
@@ -46,10 +45,12 @@ module App = {
       <TotalEventsProcessed totalEventsProcessed isPreRegisteringDynamicContracts />
       <SyncETA chains indexerStartTime isPreRegisteringDynamicContracts />
       <Newline />
-      <Box flexDirection={Column}>
-        <Text bold=true> {"GraphQL:"->React.string} </Text>
-        <Text color={Info} underline=true> {hasuraLink->React.string} </Text>
-      </Box>
+      {config.enableHasura
+        ? <Box flexDirection={Column}>
+            <Text bold=true> {"GraphQL:"->React.string} </Text>
+            <Text color={Info} underline=true> {hasuraLink->React.string} </Text>
+          </Box>
+        : React.null}
       <Messages config />
     </Box>
   }
@@ -1,6 +1,6 @@
 mod hypersync_health;
 use envio::{
-    clap_definitions::{self, InitArgs, InitFlow, ProjectPaths},
+    clap_definitions::{self, InitArgs, InitFlow, PackageManager, ProjectPaths},
     constants::project_paths::{DEFAULT_CONFIG_PATH, DEFAULT_GENERATED_PATH},
     executor::init::run_init_args,
     init_config::{self, Language},
@@ -114,7 +114,7 @@ async fn run_all_init_combinations() {
 
         match timeout(
             timeout_duration,
-            run_init_args(init_args.clone(), &project_paths),
+            run_init_args(init_args.clone(), &project_paths, PackageManager::Pnpm),
         )
         .await
         {
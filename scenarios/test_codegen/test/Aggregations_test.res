@@ -0,0 +1,105 @@
+open RescriptMocha
+
+let config = Config.make(~shouldRollbackOnReorg=true)
+
+let hour = 60. *. 60. *. 1000.
+
+let makeEventIdentifier = (~blockNumber): Types.eventIdentifier => {
+  chainId: 1,
+  blockTimestamp: blockNumber,
+  blockNumber,
+  logIndex: 0,
+}
+
+let makeTrade = (~id, ~pool, ~amount, ~hours): Entities.AggregationTrade.t => {
+  id,
+  pool,
+  amount: amount->BigInt.fromInt,
+  timestamp: Js.Date.fromFloat(hours *. hour),
+}
+
+let writeBatch = async (updates, ~blockNumber) => {
+  let inMemoryStore = InMemoryStore.make()
+  let trades =
+    inMemoryStore.entities->InMemoryStore.EntityTables.get(module(Entities.AggregationTrade))
+  updates->Belt.Array.forEach(((entityId, entityUpdateAction)) =>
+    trades->InMemoryTable.Entity.set(
+      entityUpdateAction->Types.mkEntityUpdate(
+        ~eventIdentifier=makeEventIdentifier(~blockNumber),
+        ~entityId,
+      ),
+      ~shouldSaveHistory=true,
+    )
+  )
+  await Db.sql->IO.executeBatch(~inMemoryStore, ~isInReorgThreshold=true, ~config)
+}
+
+//(pool, bucket start in hours, volume, count) sorted by bucket and pool
+let getCandles = async () =>
+  (await Db.sql->Postgres.unsafe(`SELECT * FROM "public"."AggregationTradeCandle"`))
+  ->S.parseAnyOrRaiseWith(Entities.AggregationTradeCandle.rowsSchema)
+  ->Belt.Array.map((candle: Entities.AggregationTradeCandle.t) => (
+    candle.pool,
+    candle.timestamp->Js.Date.getTime /. hour,
+    candle.volume->BigInt.toString,
+    candle.count,
+  ))
+  ->Js.Array2.sortInPlaceWith(((poolA, startA, _, _), (poolB, startB, _, _)) =>
+    compare((startA, poolA), (startB, poolB))
+  )
+
+describe("Aggregation rollups", () => {
+  Async.before(() => {
+    DbHelpers.runUpDownMigration()
+  })
+
+  Async.after(() => {
+    DbHelpers.runUpDownMigration()
+  })
+
+  Async.it("Recomputes the buckets touched by sets, deletes and rollbacks", async () => {
+    This.timeout(5 * 1000)
+
+    await writeBatch(
+      [
+        ("a", Types.Set(makeTrade(~id="a", ~pool=Some("x"), ~amount=10, ~hours=0.))),
+        ("b", Types.Set(makeTrade(~id="b", ~pool=None, ~amount=5, ~hours=0.))),
+        ("c", Types.Set(makeTrade(~id="c", ~pool=Some("x"), ~amount=7, ~hours=0.))),
+      ],
+      ~blockNumber=1,
+    )
+    let afterFirstBatch = [(None, 0., "5", 1), (Some("x"), 0., "17", 2)]
+    Assert.deepEqual(
+      await getCandles(),
+      afterFirstBatch,
+      ~message="A null pool should have its own bucket",
+    )
+
+    await writeBatch(
+      [
+        ("b", Types.Delete),
+        ("c", Types.Set(makeTrade(~id="c", ~pool=Some("x"), ~amount=7, ~hours=1.))),
+      ],
+      ~blockNumber=2,
+    )
+    Assert.deepEqual(
+      await getCandles(),
+      [(Some("x"), 0., "10", 1), (Some("x"), 1., "7", 1)],
+      ~message="Buckets emptied by a delete should be removed and the ones moved out of recomputed",
+    )
+
+    let inMemoryStore = await IO.RollBack.rollBack(
+      ~chainId=1,
+      ~blockTimestamp=1,
+      ~blockNumber=1,
+      ~logIndex=0,
+      ~isUnorderedMultichainMode=false,
+    )
+    await Db.sql->IO.executeBatch(~inMemoryStore, ~isInReorgThreshold=true, ~config)
+    Assert.deepEqual(
+      await getCandles(),
+      afterFirstBatch,
+      ~message="A rollback should restore the buckets of the rolled back block",
+    )
+  })
+})
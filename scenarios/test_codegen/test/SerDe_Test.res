@@ -7,39 +7,48 @@ let mockDate = (~year=2024, ~month=1, ~day=1) => {
   Js.Date.fromString(`${year->padInt}-${month->padInt}-${day->padInt}T00:00:00Z`)
 }
 
+let mockEntityWithAllTypes: Entities.EntityWithAllTypes.t = {
+  id: "1",
+  string: "string",
+  optString: Some("optString"),
+  arrayOfStrings: ["arrayOfStrings1", "arrayOfStrings2"],
+  int_: 1,
+  optInt: Some(2),
+  arrayOfInts: [3, 4],
+  float_: 1.1,
+  optFloat: Some(2.2),
+  arrayOfFloats: [3.3, 4.4],
+  bool: true,
+  optBool: Some(false),
+  //TODO: get array of bools working
+  // arrayOfBool: [true, false],
+  bigInt: BigInt.fromInt(1),
+  optBigInt: Some(BigInt.fromInt(2)),
+  arrayOfBigInts: [BigInt.fromInt(3), BigInt.fromInt(4)],
+  bigDecimal: BigDecimal.fromStringUnsafe("1.1"),
+  optBigDecimal: Some(BigDecimal.fromStringUnsafe("2.2")),
+  arrayOfBigDecimals: [BigDecimal.fromStringUnsafe("3.3"), BigDecimal.fromStringUnsafe("4.4")],
+  enumField: ADMIN,
+  optEnumField: Some(USER),
+  arrayOfEnums: [ADMIN, USER],
+  optArrayOfEnums: Some([USER]),
+  nestedArrayOfInts: [[1, 2], [3, 4]],
+  nestedArrayOfStrings: [["a", "b"], ["c", "d"]],
+  nestedArrayOfEnums: [[ADMIN, USER], [USER, ADMIN]],
+  //TODO: get timestamp working
+  // timestamp: mockDate(~day=1),
+  // optTimestamp: Some(mockDate(~day=2)),
+  // arrayOfTimestamps: [Js.Date.fromFloat(3.3), Js.Date.fromFloat(4.4)],
+  // arrayOfTimestamps: [],
+}
+
 describe("SerDe Test", () => {
   Async.before(async () => {
     await DbHelpers.runUpDownMigration()
   })
 
   Async.it("All type entity", async () => {
-    let entity: Entities.EntityWithAllTypes.t = {
-      id: "1",
-      string: "string",
-      optString: Some("optString"),
-      arrayOfStrings: ["arrayOfStrings1", "arrayOfStrings2"],
-      int_: 1,
-      optInt: Some(2),
-      arrayOfInts: [3, 4],
-      float_: 1.1,
-      optFloat: Some(2.2),
-      arrayOfFloats: [3.3, 4.4],
-      bool: true,
-      optBool: Some(false),
-      //TODO: get array of bools working
-      // arrayOfBool: [true, false],
-      bigInt: BigInt.fromInt(1),
-      optBigInt: Some(BigInt.fromInt(2)),
-      arrayOfBigInts: [BigInt.fromInt(3), BigInt.fromInt(4)],
-      bigDecimal: BigDecimal.fromStringUnsafe("1.1"),
-      optBigDecimal: Some(BigDecimal.fromStringUnsafe("2.2")),
-      arrayOfBigDecimals: [BigDecimal.fromStringUnsafe("3.3"), BigDecimal.fromStringUnsafe("4.4")],
-      //TODO: get timestamp working
-      // timestamp: mockDate(~day=1),
-      // optTimestamp: Some(mockDate(~day=2)),
-      // arrayOfTimestamps: [Js.Date.fromFloat(3.3), Js.Date.fromFloat(4.4)],
-      // arrayOfTimestamps: [],
-    }
+    let entity = mockEntityWithAllTypes
 
     let entityHistoryItem: EntityHistory.historyRow<_> = {
       current: {
@@ -105,4 +114,15 @@ describe("SerDe Test", () => {
     | _ => Assert.fail("Should have returned a row")
     }
   })
+
+  it("All type entity round trips through the mock db", () => {
+    let entity = mockEntityWithAllTypes
+    let mockDb = TestHelpers.MockDb.createMockDb().entities.entityWithAllTypes.set(entity)
+
+    Assert.deepEqual(
+      mockDb.entities.entityWithAllTypes.get(entity.id),
+      Some(entity),
+      ~message="Enum and nested array fields should be stored and returned unchanged",
+    )
+  })
 })
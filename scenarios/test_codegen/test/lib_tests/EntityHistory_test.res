@@ -697,7 +697,7 @@ describe("Entity history rollbacks", () => {
 
   Async.it("Prunes history correctly with items in reorg threshold", async () => {
     await Db.sql->DbFunctions.EntityHistory.pruneStaleEntityHistory(
-      ~entityName=TestEntity.name,
+      ~tableName=TestEntity.table.tableName,
       ~safeChainIdAndBlockNumberArray=[{chainId: 1, blockNumber: 3}, {chainId: 2, blockNumber: 2}],
       ~shouldDeepClean=true,
     )
@@ -729,7 +729,7 @@ describe("Entity history rollbacks", () => {
     "Deep clean prunes history correctly with items in reorg threshold without checking for stale history entities in threshold",
     async () => {
       await Db.sql->DbFunctions.EntityHistory.pruneStaleEntityHistory(
-        ~entityName=TestEntity.name,
+        ~tableName=TestEntity.table.tableName,
         ~safeChainIdAndBlockNumberArray=[
           {chainId: 1, blockNumber: 3},
           {chainId: 2, blockNumber: 2},
@@ -755,7 +755,7 @@ describe("Entity history rollbacks", () => {
   )
   Async.it("Prunes history correctly with no items in reorg threshold", async () => {
     await Db.sql->DbFunctions.EntityHistory.pruneStaleEntityHistory(
-      ~entityName=TestEntity.name,
+      ~tableName=TestEntity.table.tableName,
       ~safeChainIdAndBlockNumberArray=[{chainId: 1, blockNumber: 4}, {chainId: 2, blockNumber: 3}],
       ~shouldDeepClean=true,
     )
@@ -816,7 +816,7 @@ describe_skip("Prune performance test", () => {
     let startTime = Hrtime.makeTimer()
 
     try await Db.sql->DbFunctions.EntityHistory.pruneStaleEntityHistory(
-      ~entityName=TestEntity.name,
+      ~tableName=TestEntity.table.tableName,
       ~safeChainIdAndBlockNumberArray=[{chainId: 1, blockNumber: 500}],
       ~shouldDeepClean=false,
     ) catch {